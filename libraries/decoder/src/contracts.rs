@@ -0,0 +1,183 @@
+use super::*;
+
+sol!(
+    #[sol(abi)]
+    #[derive(Debug)]
+    interface IUniswapV2Router02 {
+        function swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+        function swapTokensForExactTokens(uint256 amountOut, uint256 amountInMax, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+        function swapExactETHForTokens(uint256 amountOutMin, address[] path, address to, uint256 deadline) external payable returns (uint256[] amounts);
+        function swapTokensForExactETH(uint256 amountOut, uint256 amountInMax, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+        function swapExactTokensForETH(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) external returns (uint256[] amounts);
+        function swapETHForExactTokens(uint256 amountOut, address[] path, address to, uint256 deadline) external payable returns (uint256[] amounts);
+        function swapExactTokensForTokensSupportingFeeOnTransferTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) external;
+        function swapExactETHForTokensSupportingFeeOnTransferTokens(uint256 amountOutMin, address[] path, address to, uint256 deadline) external payable;
+        function swapExactTokensForETHSupportingFeeOnTransferTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to, uint256 deadline) external;
+    }
+);
+
+sol!(
+    #[sol(abi)]
+    #[derive(Debug)]
+    interface ISwapRouter {
+        struct ExactInputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint24 fee;
+            address recipient;
+            uint256 deadline;
+            uint256 amountIn;
+            uint256 amountOutMinimum;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        struct ExactInputParams {
+            bytes path;
+            address recipient;
+            uint256 deadline;
+            uint256 amountIn;
+            uint256 amountOutMinimum;
+        }
+
+        struct ExactOutputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint24 fee;
+            address recipient;
+            uint256 deadline;
+            uint256 amountOut;
+            uint256 amountInMaximum;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        struct ExactOutputParams {
+            bytes path;
+            address recipient;
+            uint256 deadline;
+            uint256 amountOut;
+            uint256 amountInMaximum;
+        }
+
+        function exactInputSingle(ExactInputSingleParams params) external payable returns (uint256 amountOut);
+        function exactInput(ExactInputParams params) external payable returns (uint256 amountOut);
+        function exactOutputSingle(ExactOutputSingleParams params) external payable returns (uint256 amountIn);
+        function exactOutput(ExactOutputParams params) external payable returns (uint256 amountIn);
+        function multicall(bytes[] data) external payable returns (bytes[] results);
+        function unwrapWETH9(uint256 amountMinimum, address recipient) external payable;
+    }
+);
+
+// SwapRouter02 dropped the deadline from the params in favour of `multicall(deadline, data)`
+sol!(
+    #[sol(abi)]
+    #[derive(Debug)]
+    interface ISwapRouter02 {
+        struct ExactInputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint24 fee;
+            address recipient;
+            uint256 amountIn;
+            uint256 amountOutMinimum;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        struct ExactInputParams {
+            bytes path;
+            address recipient;
+            uint256 amountIn;
+            uint256 amountOutMinimum;
+        }
+
+        struct ExactOutputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint24 fee;
+            address recipient;
+            uint256 amountOut;
+            uint256 amountInMaximum;
+            uint160 sqrtPriceLimitX96;
+        }
+
+        struct ExactOutputParams {
+            bytes path;
+            address recipient;
+            uint256 amountOut;
+            uint256 amountInMaximum;
+        }
+
+        function exactInputSingle(ExactInputSingleParams params) external payable returns (uint256 amountOut);
+        function exactInput(ExactInputParams params) external payable returns (uint256 amountOut);
+        function exactOutputSingle(ExactOutputSingleParams params) external payable returns (uint256 amountIn);
+        function exactOutput(ExactOutputParams params) external payable returns (uint256 amountIn);
+        function swapExactTokensForTokens(uint256 amountIn, uint256 amountOutMin, address[] path, address to) external payable returns (uint256 amountOut);
+        function swapTokensForExactTokens(uint256 amountOut, uint256 amountInMax, address[] path, address to) external payable returns (uint256 amountIn);
+        function multicall(uint256 deadline, bytes[] data) external payable returns (bytes[] results);
+        function multicall(bytes32 previousBlockhash, bytes[] data) external payable returns (bytes[] results);
+        function unwrapWETH9(uint256 amountMinimum) external payable;
+    }
+);
+
+sol!(
+    #[sol(abi)]
+    #[derive(Debug)]
+    interface IUniversalRouter {
+        function execute(bytes commands, bytes[] inputs, uint256 deadline) external payable;
+        function execute(bytes commands, bytes[] inputs) external payable;
+    }
+);
+
+// Universal Router command inputs, encoded as plain params rather than a tuple
+sol!(
+    #[derive(Debug)]
+    struct V3SwapInput {
+        address recipient;
+        uint256 amount;
+        uint256 amountLimit;
+        bytes path;
+        bool payerIsUser;
+    }
+
+    #[derive(Debug)]
+    struct V2SwapInput {
+        address recipient;
+        uint256 amount;
+        uint256 amountLimit;
+        address[] path;
+        bool payerIsUser;
+    }
+
+    #[derive(Debug)]
+    struct WrapEthInput {
+        address recipient;
+        uint256 amount;
+    }
+
+    #[derive(Debug)]
+    struct Permit2TransferFromInput {
+        address token;
+        address recipient;
+        uint160 amount;
+    }
+
+    #[derive(Debug)]
+    struct PermitDetails {
+        address token;
+        uint160 amount;
+        uint48 expiration;
+        uint48 nonce;
+    }
+
+    #[derive(Debug)]
+    struct PermitSingle {
+        PermitDetails details;
+        address spender;
+        uint256 sigDeadline;
+    }
+
+    #[derive(Debug)]
+    struct Permit2PermitInput {
+        PermitSingle permitSingle;
+        bytes signature;
+    }
+);
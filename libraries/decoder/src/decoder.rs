@@ -0,0 +1,284 @@
+use super::*;
+use alloy::sol_types::SolCall;
+use ISwapRouter::ISwapRouterCalls;
+use ISwapRouter02::ISwapRouter02Calls;
+use IUniswapV2Router02::IUniswapV2Router02Calls;
+use IUniversalRouter::IUniversalRouterCalls;
+
+fn selector(input: &[u8]) -> Option<[u8; 4]> {
+    input.get(..4)?.try_into().ok()
+}
+
+/// Decode every swap in router calldata; calls that are not swaps yield none
+pub fn decode_swaps<'a>(input: &[u8], value: U256) -> Result<Vec<SwapIntent>, CustomError<'a>> {
+    let Some(selector) = selector(input) else {
+        return Ok(vec![]);
+    };
+
+    if IUniswapV2Router02Calls::valid_selector(selector) {
+        let call = IUniswapV2Router02Calls::abi_decode(input)?;
+        Ok(vec![decode_v2(call, value)])
+    } else if ISwapRouterCalls::valid_selector(selector) {
+        match ISwapRouterCalls::abi_decode(input)? {
+            ISwapRouterCalls::multicall(call) => decode_multicall(&call.data, None, value),
+            call => Ok(decode_swap_router(call, value)?.into_iter().collect()),
+        }
+    } else if ISwapRouter02Calls::valid_selector(selector) {
+        match ISwapRouter02Calls::abi_decode(input)? {
+            ISwapRouter02Calls::multicall_0(call) => {
+                decode_multicall(&call.data, Some(call.deadline), value)
+            }
+            ISwapRouter02Calls::multicall_1(call) => decode_multicall(&call.data, None, value),
+            call => Ok(decode_swap_router02(call, value)?.into_iter().collect()),
+        }
+    } else if IUniversalRouterCalls::valid_selector(selector) {
+        match IUniversalRouterCalls::abi_decode(input)? {
+            IUniversalRouterCalls::execute_0(call) => {
+                decode_execute(&call.commands, &call.inputs, Some(call.deadline), value)
+            }
+            IUniversalRouterCalls::execute_1(call) => {
+                decode_execute(&call.commands, &call.inputs, None, value)
+            }
+        }
+    } else {
+        Ok(vec![])
+    }
+}
+
+fn is_unwrap_weth(call: &[u8]) -> bool {
+    selector(call).is_some_and(|selector| {
+        selector == ISwapRouter::unwrapWETH9Call::SELECTOR
+            || selector == ISwapRouter02::unwrapWETH9Call::SELECTOR
+    })
+}
+
+fn decode_multicall<'a>(
+    calls: &[Bytes],
+    deadline: Option<U256>,
+    value: U256,
+) -> Result<Vec<SwapIntent>, CustomError<'a>> {
+    let mut intents = vec![];
+    let mut native_out = false;
+
+    for call in calls {
+        native_out |= is_unwrap_weth(call);
+        intents.extend(decode_swaps(call, value)?);
+    }
+
+    // Sibling calls settle the whole batch, so they apply to every swap in it
+    for intent in intents.iter_mut() {
+        intent.native_out |= native_out;
+        intent.deadline = intent.deadline.or(deadline);
+    }
+
+    Ok(intents)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{hex, primitives::U160};
+
+    fn address(byte: u8) -> Address {
+        Address::repeat_byte(byte)
+    }
+
+    fn packed_path(tokens: &[Address], fees: &[u32]) -> Bytes {
+        let mut path = tokens[0].to_vec();
+        for (token, fee) in tokens[1..].iter().zip(fees) {
+            path.extend_from_slice(&fee.to_be_bytes()[1..]);
+            path.extend_from_slice(token.as_slice());
+        }
+        path.into()
+    }
+
+    #[test]
+    fn test_selectors() {
+        let selectors = [
+            ISwapRouter::exactInputSingleCall::SELECTOR,
+            ISwapRouter::exactInputCall::SELECTOR,
+            ISwapRouter::exactOutputSingleCall::SELECTOR,
+            ISwapRouter::exactOutputCall::SELECTOR,
+            ISwapRouter02::exactInputSingleCall::SELECTOR,
+            ISwapRouter02::exactInputCall::SELECTOR,
+            ISwapRouter02::exactOutputSingleCall::SELECTOR,
+            ISwapRouter02::exactOutputCall::SELECTOR,
+            IUniversalRouter::execute_0Call::SELECTOR,
+            IUniversalRouter::execute_1Call::SELECTOR,
+        ];
+
+        assert_eq!(
+            selectors.map(hex::encode),
+            [
+                "414bf389", "c04b8d59", "db3e2198", "f28c0498", "04e45aaf", "b858183f", "5023b4df",
+                "09b81346", "3593564c", "24856bc3"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decode_path() {
+        let tokens = [address(1), address(2), address(3)];
+        let path = packed_path(&tokens, &[500, 3000]);
+
+        let (decoded, fees) = decode_path(&path).unwrap();
+        assert_eq!(decoded, tokens.to_vec());
+        assert_eq!(fees, vec![500, 3000]);
+
+        let (decoded, fees) = decode_path_in_swap_order(&path, true).unwrap();
+        assert_eq!(decoded, vec![address(3), address(2), address(1)]);
+        assert_eq!(fees, vec![3000, 500]);
+
+        assert!(decode_path(&path[..42]).is_err());
+    }
+
+    #[test]
+    fn test_decode_v2() {
+        let input = IUniswapV2Router02::swapETHForExactTokensCall {
+            amountOut: U256::from(5),
+            path: vec![address(1), address(2)],
+            to: address(9),
+            deadline: U256::from(100),
+        }
+        .abi_encode();
+
+        let intents = decode_swaps(&input, U256::from(6)).unwrap();
+        assert_eq!(intents.len(), 1);
+        assert_eq!(intents[0].router, RouterKind::UniswapV2Router02);
+        assert_eq!(
+            intents[0].amount,
+            SwapAmount::ExactOut {
+                amount_out: U256::from(5),
+                amount_in_max: U256::from(6)
+            }
+        );
+        assert!(intents[0].native_in && !intents[0].native_out);
+
+        // Unknown selectors are not swaps
+        assert!(decode_swaps(&[0u8; 4], U256::ZERO).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_decode_multicall() {
+        let swap = ISwapRouter02::exactOutputCall {
+            params: ISwapRouter02::ExactOutputParams {
+                path: packed_path(&[address(3), address(1)], &[3000]),
+                recipient: address(9),
+                amountOut: U256::from(5),
+                amountInMaximum: U256::from(6),
+            },
+        };
+        let unwrap = ISwapRouter02::unwrapWETH9Call {
+            amountMinimum: U256::ZERO,
+        };
+        let input = ISwapRouter02::multicall_0Call {
+            deadline: U256::from(100),
+            data: vec![swap.abi_encode().into(), unwrap.abi_encode().into()],
+        }
+        .abi_encode();
+
+        let intents = decode_swaps(&input, U256::ZERO).unwrap();
+        assert_eq!(intents.len(), 1);
+        assert_eq!(intents[0].function, "exactOutput");
+        assert_eq!(intents[0].path, vec![address(1), address(3)]);
+        assert_eq!(intents[0].fees, vec![3000]);
+        assert_eq!(intents[0].deadline, Some(U256::from(100)));
+        assert!(!intents[0].native_in && intents[0].native_out);
+        assert_eq!(
+            intents[0].amount,
+            SwapAmount::ExactOut {
+                amount_out: U256::from(5),
+                amount_in_max: U256::from(6)
+            }
+        );
+    }
+
+    #[test]
+    fn test_decode_execute() {
+        let value = U256::from(10u64.pow(18));
+        let wrap = WrapEthInput {
+            recipient: address(2),
+            amount: CONTRACT_BALANCE,
+        };
+        let v3_swap = V3SwapInput {
+            recipient: address(9),
+            amount: CONTRACT_BALANCE,
+            amountLimit: U256::from(42),
+            path: packed_path(&[address(1), address(3)], &[500]),
+            payerIsUser: false,
+        };
+        let v2_swap = V2SwapInput {
+            recipient: address(9),
+            amount: U256::from(7),
+            amountLimit: U256::from(8),
+            path: vec![address(3), address(1)],
+            payerIsUser: true,
+        };
+        let permit = Permit2PermitInput {
+            permitSingle: PermitSingle {
+                details: PermitDetails {
+                    token: address(3),
+                    amount: U160::MAX,
+                    expiration: Default::default(),
+                    nonce: Default::default(),
+                },
+                spender: address(4),
+                sigDeadline: U256::ZERO,
+            },
+            signature: Bytes::new(),
+        };
+
+        // 0x80 marks the swap as allowed to revert, which must not change its type
+        let input = IUniversalRouter::execute_0Call {
+            commands: vec![
+                WRAP_ETH,
+                0x80 | V3_SWAP_EXACT_IN,
+                PERMIT2_PERMIT,
+                V2_SWAP_EXACT_OUT,
+                UNWRAP_WETH,
+            ]
+            .into(),
+            inputs: vec![
+                WrapEthInput::abi_encode_params(&wrap).into(),
+                V3SwapInput::abi_encode_params(&v3_swap).into(),
+                Permit2PermitInput::abi_encode_params(&permit).into(),
+                V2SwapInput::abi_encode_params(&v2_swap).into(),
+                WrapEthInput::abi_encode_params(&wrap).into(),
+            ],
+            deadline: U256::from(1_700_000_000u64),
+        }
+        .abi_encode();
+        let intents = decode_swaps(&input, value).unwrap();
+
+        assert_eq!(intents.len(), 2);
+        assert_eq!(intents[0].protocol, Protocol::UniswapV3);
+        assert_eq!(intents[0].path, vec![address(1), address(3)]);
+        assert_eq!(intents[0].fees, vec![500]);
+        assert_eq!(
+            intents[0].amount,
+            SwapAmount::ExactIn {
+                amount_in: value,
+                amount_out_min: U256::from(42)
+            }
+        );
+        assert!(intents[0].native_in && intents[0].native_out);
+        assert_eq!(intents[0].deadline, Some(U256::from(1_700_000_000u64)));
+
+        assert_eq!(intents[1].protocol, Protocol::UniswapV2);
+        assert_eq!(intents[1].path, vec![address(3), address(1)]);
+        assert_eq!(
+            intents[1].amount,
+            SwapAmount::ExactOut {
+                amount_out: U256::from(7),
+                amount_in_max: U256::from(8)
+            }
+        );
+
+        let mismatched = IUniversalRouter::execute_1Call {
+            commands: vec![WRAP_ETH, UNWRAP_WETH].into(),
+            inputs: vec![Bytes::new()],
+        }
+        .abi_encode();
+        assert!(decode_swaps(&mismatched, value).is_err());
+    }
+}
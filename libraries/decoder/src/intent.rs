@@ -0,0 +1,74 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RouterKind {
+    UniswapV2Router02,
+    SwapRouter,
+    SwapRouter02,
+    UniversalRouter,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    UniswapV2,
+    UniswapV3,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SwapAmount {
+    ExactIn {
+        amount_in: U256,
+        amount_out_min: U256,
+    },
+    ExactOut {
+        amount_out: U256,
+        amount_in_max: U256,
+    },
+}
+
+/// One swap a pending transaction asks for, whichever router and encoding it came through
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapIntent {
+    pub router: RouterKind,
+    pub function: String,
+    pub protocol: Protocol,
+    /// Tokens in swap order, from the input token to the output token
+    pub path: Vec<Address>,
+    /// Pool fee of every V3 hop, empty for V2
+    pub fees: Vec<u32>,
+    pub amount: SwapAmount,
+    pub recipient: Address,
+    pub deadline: Option<U256>,
+    pub native_in: bool,
+    pub native_out: bool,
+}
+
+impl Display for SwapIntent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "router: {:?}", self.router)?;
+        writeln!(f, "name: {}", self.function)?;
+        writeln!(f, "protocol: {:?}", self.protocol)?;
+        match self.amount {
+            SwapAmount::ExactIn {
+                amount_in,
+                amount_out_min,
+            } => writeln!(
+                f,
+                "amount_in: {amount_in}\namount_out_min: {amount_out_min}"
+            )?,
+            SwapAmount::ExactOut {
+                amount_out,
+                amount_in_max,
+            } => writeln!(
+                f,
+                "amount_out: {amount_out}\namount_in_max: {amount_in_max}"
+            )?,
+        }
+        writeln!(f, "path: {:?}", self.path)?;
+        writeln!(f, "fees: {:?}", self.fees)?;
+        writeln!(f, "to: {}", self.recipient)?;
+        writeln!(f, "deadline: {:?}", self.deadline)?;
+        writeln!(f, "native_in: {}", self.native_in)?;
+        write!(f, "native_out: {}", self.native_out)
+    }
+}
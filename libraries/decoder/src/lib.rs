@@ -0,0 +1,15 @@
+pub use crate::{contracts::*, decoder::*, intent::*, universal_router::*, v2::*, v3::*};
+use alloy::{
+    primitives::{Address, Bytes, U256},
+    sol,
+    sol_types::{SolInterface, SolType},
+};
+use std::fmt::Display;
+use utils::CustomError;
+
+mod contracts;
+mod decoder;
+mod intent;
+mod universal_router;
+mod v2;
+mod v3;
@@ -0,0 +1,135 @@
+use super::*;
+
+// Universal Router command bytes, see `Commands.sol`
+pub(crate) const V3_SWAP_EXACT_IN: u8 = 0x00;
+pub(crate) const V3_SWAP_EXACT_OUT: u8 = 0x01;
+pub(crate) const PERMIT2_TRANSFER_FROM: u8 = 0x02;
+pub(crate) const V2_SWAP_EXACT_IN: u8 = 0x08;
+pub(crate) const V2_SWAP_EXACT_OUT: u8 = 0x09;
+pub(crate) const PERMIT2_PERMIT: u8 = 0x0a;
+pub(crate) const WRAP_ETH: u8 = 0x0b;
+pub(crate) const UNWRAP_WETH: u8 = 0x0c;
+
+/// The top bits flag whether the command may revert
+pub(crate) const COMMAND_TYPE_MASK: u8 = 0x3f;
+
+/// Amount standing for "everything the router currently holds"
+pub const CONTRACT_BALANCE: U256 = U256::from_limbs([0, 0, 0, 1 << 63]);
+
+/// Decode the commands of `execute`; WRAP_ETH and PERMIT2 transfers fund the router, which
+/// resolves swaps that spend its whole balance
+pub fn decode_execute<'a>(
+    commands: &[u8],
+    inputs: &[Bytes],
+    deadline: Option<U256>,
+    value: U256,
+) -> Result<Vec<SwapIntent>, CustomError<'a>> {
+    if commands.len() != inputs.len() {
+        return Err(CustomError::InvalidCalldata(
+            "universal router commands and inputs differ in length",
+        ));
+    }
+
+    let mut intents = vec![];
+    let mut native_in = false;
+    let mut native_out = false;
+    let mut balance = None;
+
+    for (command, input) in commands.iter().zip(inputs) {
+        let command = command & COMMAND_TYPE_MASK;
+
+        let (function, protocol, exact_in, recipient, amount, limit, path, fees) = match command {
+            V3_SWAP_EXACT_IN | V3_SWAP_EXACT_OUT => {
+                let swap = V3SwapInput::abi_decode_params(input)?;
+                let exact_in = command == V3_SWAP_EXACT_IN;
+                let (path, fees) = decode_path_in_swap_order(&swap.path, !exact_in)?;
+                let function = if exact_in {
+                    "V3_SWAP_EXACT_IN"
+                } else {
+                    "V3_SWAP_EXACT_OUT"
+                };
+
+                (
+                    function,
+                    Protocol::UniswapV3,
+                    exact_in,
+                    swap.recipient,
+                    swap.amount,
+                    swap.amountLimit,
+                    path,
+                    fees,
+                )
+            }
+            V2_SWAP_EXACT_IN | V2_SWAP_EXACT_OUT => {
+                let swap = V2SwapInput::abi_decode_params(input)?;
+                let exact_in = command == V2_SWAP_EXACT_IN;
+                let function = if exact_in {
+                    "V2_SWAP_EXACT_IN"
+                } else {
+                    "V2_SWAP_EXACT_OUT"
+                };
+
+                (
+                    function,
+                    Protocol::UniswapV2,
+                    exact_in,
+                    swap.recipient,
+                    swap.amount,
+                    swap.amountLimit,
+                    swap.path,
+                    vec![],
+                )
+            }
+            WRAP_ETH => {
+                let wrap = WrapEthInput::abi_decode_params(input)?;
+                native_in = true;
+                balance = Some(if wrap.amount == CONTRACT_BALANCE {
+                    value
+                } else {
+                    wrap.amount
+                });
+                continue;
+            }
+            UNWRAP_WETH => {
+                native_out = true;
+                continue;
+            }
+            PERMIT2_TRANSFER_FROM => {
+                let transfer = Permit2TransferFromInput::abi_decode_params(input)?;
+                balance = Some(U256::from(transfer.amount));
+                continue;
+            }
+            PERMIT2_PERMIT => {
+                // Only an allowance, nothing moves until a swap or transfer spends it
+                Permit2PermitInput::abi_decode_params(input)?;
+                continue;
+            }
+            // Sweeps, transfers and the rest move no pool state
+            _ => continue,
+        };
+
+        let amount = match (exact_in, balance) {
+            (true, Some(balance)) if amount == CONTRACT_BALANCE => balance,
+            _ => amount,
+        };
+
+        intents.push(SwapIntent {
+            router: RouterKind::UniversalRouter,
+            function: function.to_string(),
+            protocol,
+            path,
+            fees,
+            amount: v2::amount(exact_in, amount, limit),
+            recipient,
+            deadline,
+            native_in,
+            native_out: false,
+        });
+    }
+
+    for intent in intents.iter_mut() {
+        intent.native_out |= native_out;
+    }
+
+    Ok(intents)
+}
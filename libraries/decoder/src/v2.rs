@@ -0,0 +1,147 @@
+use super::*;
+use IUniswapV2Router02::IUniswapV2Router02Calls;
+
+fn exact_in(amount_in: U256, amount_out_min: U256) -> SwapAmount {
+    SwapAmount::ExactIn {
+        amount_in,
+        amount_out_min,
+    }
+}
+
+fn exact_out(amount_out: U256, amount_in_max: U256) -> SwapAmount {
+    SwapAmount::ExactOut {
+        amount_out,
+        amount_in_max,
+    }
+}
+
+/// Decode one of the nine UniswapV2Router02 `swap*` functions; ETH inputs are paid with `value`
+pub fn decode_v2(call: IUniswapV2Router02Calls, value: U256) -> SwapIntent {
+    use IUniswapV2Router02Calls::*;
+
+    let (function, amount, path, to, deadline, native_in, native_out) = match call {
+        swapExactTokensForTokens(c) => (
+            "swapExactTokensForTokens",
+            exact_in(c.amountIn, c.amountOutMin),
+            c.path,
+            c.to,
+            c.deadline,
+            false,
+            false,
+        ),
+        swapTokensForExactTokens(c) => (
+            "swapTokensForExactTokens",
+            exact_out(c.amountOut, c.amountInMax),
+            c.path,
+            c.to,
+            c.deadline,
+            false,
+            false,
+        ),
+        swapExactETHForTokens(c) => (
+            "swapExactETHForTokens",
+            exact_in(value, c.amountOutMin),
+            c.path,
+            c.to,
+            c.deadline,
+            true,
+            false,
+        ),
+        swapTokensForExactETH(c) => (
+            "swapTokensForExactETH",
+            exact_out(c.amountOut, c.amountInMax),
+            c.path,
+            c.to,
+            c.deadline,
+            false,
+            true,
+        ),
+        swapExactTokensForETH(c) => (
+            "swapExactTokensForETH",
+            exact_in(c.amountIn, c.amountOutMin),
+            c.path,
+            c.to,
+            c.deadline,
+            false,
+            true,
+        ),
+        swapETHForExactTokens(c) => (
+            "swapETHForExactTokens",
+            exact_out(c.amountOut, value),
+            c.path,
+            c.to,
+            c.deadline,
+            true,
+            false,
+        ),
+        swapExactTokensForTokensSupportingFeeOnTransferTokens(c) => (
+            "swapExactTokensForTokensSupportingFeeOnTransferTokens",
+            exact_in(c.amountIn, c.amountOutMin),
+            c.path,
+            c.to,
+            c.deadline,
+            false,
+            false,
+        ),
+        swapExactETHForTokensSupportingFeeOnTransferTokens(c) => (
+            "swapExactETHForTokensSupportingFeeOnTransferTokens",
+            exact_in(value, c.amountOutMin),
+            c.path,
+            c.to,
+            c.deadline,
+            true,
+            false,
+        ),
+        swapExactTokensForETHSupportingFeeOnTransferTokens(c) => (
+            "swapExactTokensForETHSupportingFeeOnTransferTokens",
+            exact_in(c.amountIn, c.amountOutMin),
+            c.path,
+            c.to,
+            c.deadline,
+            false,
+            true,
+        ),
+    };
+
+    SwapIntent {
+        router: RouterKind::UniswapV2Router02,
+        function: function.to_string(),
+        protocol: Protocol::UniswapV2,
+        path,
+        fees: vec![],
+        amount,
+        recipient: to,
+        deadline: Some(deadline),
+        native_in,
+        native_out,
+    }
+}
+
+/// SwapRouter02 copies of the V2 token swaps, without a deadline
+pub fn decode_router02_v2(
+    function: &str,
+    amount: SwapAmount,
+    path: Vec<Address>,
+    to: Address,
+) -> SwapIntent {
+    SwapIntent {
+        router: RouterKind::SwapRouter02,
+        function: function.to_string(),
+        protocol: Protocol::UniswapV2,
+        path,
+        fees: vec![],
+        amount,
+        recipient: to,
+        deadline: None,
+        native_in: false,
+        native_out: false,
+    }
+}
+
+pub(crate) fn amount(exact_input: bool, amount: U256, limit: U256) -> SwapAmount {
+    if exact_input {
+        exact_in(amount, limit)
+    } else {
+        exact_out(amount, limit)
+    }
+}
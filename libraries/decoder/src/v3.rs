@@ -0,0 +1,203 @@
+use super::*;
+
+/// Split a packed V3 path `token (20) | fee (3) | token (20) | ...` into tokens and fees
+pub fn decode_path<'a>(path: &[u8]) -> Result<(Vec<Address>, Vec<u32>), CustomError<'a>> {
+    if path.len() < 43 || !(path.len() - 20).is_multiple_of(23) {
+        return Err(CustomError::InvalidCalldata("v3 path length"));
+    }
+
+    let mut tokens = vec![Address::from_slice(&path[..20])];
+    let mut fees = vec![];
+
+    for hop in path[20..].chunks(23) {
+        fees.push(u32::from_be_bytes([0, hop[0], hop[1], hop[2]]));
+        tokens.push(Address::from_slice(&hop[3..]));
+    }
+
+    Ok((tokens, fees))
+}
+
+/// Exact output paths are encoded from the output token back to the input token
+pub fn decode_path_in_swap_order<'a>(
+    path: &[u8],
+    exact_output: bool,
+) -> Result<(Vec<Address>, Vec<u32>), CustomError<'a>> {
+    let (mut tokens, mut fees) = decode_path(path)?;
+
+    if exact_output {
+        tokens.reverse();
+        fees.reverse();
+    }
+
+    Ok((tokens, fees))
+}
+
+/// Fields shared by the V3 calls of SwapRouter and SwapRouter02
+pub struct V3Swap<'p> {
+    pub router: RouterKind,
+    pub function: &'static str,
+    pub exact_output: bool,
+    pub single: Option<(Address, Address, u32)>,
+    pub path: &'p [u8],
+    pub recipient: Address,
+    pub deadline: Option<U256>,
+    pub amount: U256,
+    pub limit: U256,
+}
+
+impl V3Swap<'_> {
+    /// The routers wrap any attached ETH into WETH for the input
+    pub fn into_intent<'a>(self, value: U256) -> Result<SwapIntent, CustomError<'a>> {
+        let (path, fees) = match self.single {
+            Some((token_in, token_out, fee)) => (vec![token_in, token_out], vec![fee]),
+            None => decode_path_in_swap_order(self.path, self.exact_output)?,
+        };
+
+        Ok(SwapIntent {
+            router: self.router,
+            function: self.function.to_string(),
+            protocol: Protocol::UniswapV3,
+            path,
+            fees,
+            amount: amount(!self.exact_output, self.amount, self.limit),
+            recipient: self.recipient,
+            deadline: self.deadline,
+            native_in: !value.is_zero(),
+            native_out: false,
+        })
+    }
+}
+
+pub fn decode_swap_router<'a>(
+    call: ISwapRouter::ISwapRouterCalls,
+    value: U256,
+) -> Result<Option<SwapIntent>, CustomError<'a>> {
+    use ISwapRouter::ISwapRouterCalls::*;
+
+    let swap = match call {
+        exactInputSingle(c) => V3Swap {
+            router: RouterKind::SwapRouter,
+            function: "exactInputSingle",
+            exact_output: false,
+            single: Some((c.params.tokenIn, c.params.tokenOut, c.params.fee.to())),
+            path: &[],
+            recipient: c.params.recipient,
+            deadline: Some(c.params.deadline),
+            amount: c.params.amountIn,
+            limit: c.params.amountOutMinimum,
+        }
+        .into_intent(value)?,
+        exactInput(c) => V3Swap {
+            router: RouterKind::SwapRouter,
+            function: "exactInput",
+            exact_output: false,
+            single: None,
+            path: &c.params.path,
+            recipient: c.params.recipient,
+            deadline: Some(c.params.deadline),
+            amount: c.params.amountIn,
+            limit: c.params.amountOutMinimum,
+        }
+        .into_intent(value)?,
+        exactOutputSingle(c) => V3Swap {
+            router: RouterKind::SwapRouter,
+            function: "exactOutputSingle",
+            exact_output: true,
+            single: Some((c.params.tokenIn, c.params.tokenOut, c.params.fee.to())),
+            path: &[],
+            recipient: c.params.recipient,
+            deadline: Some(c.params.deadline),
+            amount: c.params.amountOut,
+            limit: c.params.amountInMaximum,
+        }
+        .into_intent(value)?,
+        exactOutput(c) => V3Swap {
+            router: RouterKind::SwapRouter,
+            function: "exactOutput",
+            exact_output: true,
+            single: None,
+            path: &c.params.path,
+            recipient: c.params.recipient,
+            deadline: Some(c.params.deadline),
+            amount: c.params.amountOut,
+            limit: c.params.amountInMaximum,
+        }
+        .into_intent(value)?,
+        multicall(_) | unwrapWETH9(_) => return Ok(None),
+    };
+
+    Ok(Some(swap))
+}
+
+pub fn decode_swap_router02<'a>(
+    call: ISwapRouter02::ISwapRouter02Calls,
+    value: U256,
+) -> Result<Option<SwapIntent>, CustomError<'a>> {
+    use ISwapRouter02::ISwapRouter02Calls::*;
+
+    let swap = match call {
+        exactInputSingle(c) => V3Swap {
+            router: RouterKind::SwapRouter02,
+            function: "exactInputSingle",
+            exact_output: false,
+            single: Some((c.params.tokenIn, c.params.tokenOut, c.params.fee.to())),
+            path: &[],
+            recipient: c.params.recipient,
+            deadline: None,
+            amount: c.params.amountIn,
+            limit: c.params.amountOutMinimum,
+        }
+        .into_intent(value)?,
+        exactInput(c) => V3Swap {
+            router: RouterKind::SwapRouter02,
+            function: "exactInput",
+            exact_output: false,
+            single: None,
+            path: &c.params.path,
+            recipient: c.params.recipient,
+            deadline: None,
+            amount: c.params.amountIn,
+            limit: c.params.amountOutMinimum,
+        }
+        .into_intent(value)?,
+        exactOutputSingle(c) => V3Swap {
+            router: RouterKind::SwapRouter02,
+            function: "exactOutputSingle",
+            exact_output: true,
+            single: Some((c.params.tokenIn, c.params.tokenOut, c.params.fee.to())),
+            path: &[],
+            recipient: c.params.recipient,
+            deadline: None,
+            amount: c.params.amountOut,
+            limit: c.params.amountInMaximum,
+        }
+        .into_intent(value)?,
+        exactOutput(c) => V3Swap {
+            router: RouterKind::SwapRouter02,
+            function: "exactOutput",
+            exact_output: true,
+            single: None,
+            path: &c.params.path,
+            recipient: c.params.recipient,
+            deadline: None,
+            amount: c.params.amountOut,
+            limit: c.params.amountInMaximum,
+        }
+        .into_intent(value)?,
+        swapExactTokensForTokens(c) => decode_router02_v2(
+            "swapExactTokensForTokens",
+            amount(true, c.amountIn, c.amountOutMin),
+            c.path,
+            c.to,
+        ),
+        swapTokensForExactTokens(c) => decode_router02_v2(
+            "swapTokensForExactTokens",
+            amount(false, c.amountOut, c.amountInMax),
+            c.path,
+            c.to,
+        ),
+        multicall_0(_) | multicall_1(_) | unwrapWETH9(_) => return Ok(None),
+    };
+
+    Ok(Some(swap))
+}
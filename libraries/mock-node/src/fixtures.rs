@@ -0,0 +1,247 @@
+use super::*;
+
+/// Return data of an `eth_call`, matched on the target and the exact calldata
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallFixture {
+    pub to: Address,
+    pub input: Bytes,
+    pub output: Bytes,
+}
+
+/// Balance, nonce, code and storage of an account, served by the state queries
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountFixture {
+    #[serde(default)]
+    pub balance: U256,
+    #[serde(default)]
+    pub nonce: u64,
+    #[serde(default)]
+    pub code: Bytes,
+    #[serde(default)]
+    pub storage: HashMap<U256, U256>,
+}
+
+/// Everything the node knows about the chain. Subscriptions replay the matching entries
+/// as soon as they are opened, `eth_getLogs` filters `logs` by address, topics and block range.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Fixtures {
+    #[serde(default = "default_chain_id")]
+    pub chain_id: u64,
+    pub block_number: u64,
+    #[serde(default)]
+    pub logs: Vec<Log>,
+    #[serde(default)]
+    pub heads: Vec<Header>,
+    /// Served by `eth_getTransactionByHash` and announced to `newPendingTransactions`
+    #[serde(default)]
+    pub transactions: Vec<Transaction>,
+    #[serde(default)]
+    pub calls: Vec<CallFixture>,
+    /// Accounts missing here are empty, as on a real node
+    #[serde(default)]
+    pub accounts: HashMap<Address, AccountFixture>,
+}
+
+fn default_chain_id() -> u64 {
+    1
+}
+
+fn in_range(filter: &Filter, log: &Log) -> bool {
+    let block = log.block_number.unwrap_or_default();
+
+    filter.get_from_block().is_none_or(|from| block >= from)
+        && filter.get_to_block().is_none_or(|to| block <= to)
+}
+
+impl Fixtures {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    /// Logs matching the address and topics of `filter`, ignoring its block range
+    pub fn subscribed_logs(&self, filter: &Filter) -> Vec<&Log> {
+        self.logs
+            .iter()
+            .filter(|log| filter.matches(&log.inner))
+            .collect()
+    }
+
+    pub fn get_logs(&self, filter: &Filter) -> Vec<&Log> {
+        self.subscribed_logs(filter)
+            .into_iter()
+            .filter(|log| in_range(filter, log))
+            .collect()
+    }
+
+    pub fn account(&self, address: &Address) -> AccountFixture {
+        self.accounts.get(address).cloned().unwrap_or_default()
+    }
+
+    /// Header of a past block, hashed by its number like the log fixtures
+    pub fn header(&self, number: BlockNumberOrTag) -> Option<Header> {
+        let number = number.as_number().unwrap_or(self.block_number);
+        if number > self.block_number {
+            return None;
+        }
+
+        Some(Header {
+            hash: B256::from(U256::from(number)),
+            inner: alloy::consensus::Header {
+                number,
+                timestamp: number * 12,
+                ..Default::default()
+            },
+            ..Default::default()
+        })
+    }
+
+    pub fn transaction(&self, hash: &B256) -> Option<&Transaction> {
+        self.transactions
+            .iter()
+            .find(|tx| tx.inner.tx_hash() == hash)
+    }
+
+    /// Return data of a call, `None` where a node would revert.
+    /// Multicall3 batches are answered call by call from the same fixtures.
+    pub fn call(&self, to: Address, input: &[u8]) -> Option<Bytes> {
+        if to == MULTICALL3_ADDRESS {
+            return self.multicall(input);
+        }
+
+        self.calls
+            .iter()
+            .find(|call| call.to == to && *call.input == *input)
+            .map(|call| call.output.clone())
+    }
+
+    fn multicall(&self, input: &[u8]) -> Option<Bytes> {
+        use IMulticall3Calls::*;
+
+        let output = match IMulticall3Calls::abi_decode(input).ok()? {
+            aggregate(call) => {
+                let return_data = call
+                    .calls
+                    .iter()
+                    .map(|call| self.call(call.target, &call.callData))
+                    .collect::<Option<Vec<_>>>()?;
+
+                IMulticall3::aggregateCall::abi_encode_returns(&IMulticall3::aggregateReturn {
+                    blockNumber: U256::from(self.block_number),
+                    returnData: return_data,
+                })
+            }
+            tryAggregate(call) => {
+                let results = call
+                    .calls
+                    .iter()
+                    .map(|inner| self.try_call(inner.target, &inner.callData, !call.requireSuccess))
+                    .collect::<Option<Vec<_>>>()?;
+
+                IMulticall3::tryAggregateCall::abi_encode_returns(&results)
+            }
+            aggregate3(call) => {
+                let results = call
+                    .calls
+                    .iter()
+                    .map(|call| self.try_call(call.target, &call.callData, call.allowFailure))
+                    .collect::<Option<Vec<_>>>()?;
+
+                IMulticall3::aggregate3Call::abi_encode_returns(&results)
+            }
+            getBlockNumber(_) => {
+                IMulticall3::getBlockNumberCall::abi_encode_returns(&U256::from(self.block_number))
+            }
+            getChainId(_) => {
+                IMulticall3::getChainIdCall::abi_encode_returns(&U256::from(self.chain_id))
+            }
+            _ => return None,
+        };
+
+        Some(output.into())
+    }
+
+    /// A call inside a batch, which only reverts the batch when it may not fail
+    fn try_call(
+        &self,
+        target: Address,
+        input: &[u8],
+        allow_failure: bool,
+    ) -> Option<IMulticall3::Result> {
+        match self.call(target, input) {
+            Some(return_data) => Some(IMulticall3::Result {
+                success: true,
+                returnData: return_data,
+            }),
+            None if allow_failure => Some(IMulticall3::Result {
+                success: false,
+                returnData: Bytes::new(),
+            }),
+            None => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{address, bytes};
+
+    const PAIR: Address = address!("0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc");
+
+    fn fixtures() -> Fixtures {
+        Fixtures {
+            block_number: 100,
+            calls: vec![CallFixture {
+                to: PAIR,
+                input: bytes!("0902f1ac"),
+                output: bytes!("01"),
+            }],
+            ..Default::default()
+        }
+    }
+
+    fn call3(input: Bytes, allow_failure: bool) -> IMulticall3::Call3 {
+        IMulticall3::Call3 {
+            target: PAIR,
+            allowFailure: allow_failure,
+            callData: input,
+        }
+    }
+
+    #[test]
+    fn multicall_answers_each_call_from_fixtures() {
+        let fixtures = fixtures();
+        let input = IMulticall3::aggregate3Call {
+            calls: vec![call3(bytes!("0902f1ac"), false), call3(bytes!("ff"), true)],
+        }
+        .abi_encode();
+
+        let output = fixtures.call(MULTICALL3_ADDRESS, &input).unwrap();
+        let results = IMulticall3::aggregate3Call::abi_decode_returns(&output).unwrap();
+
+        assert!(results[0].success);
+        assert_eq!(results[0].returnData, bytes!("01"));
+        assert!(!results[1].success);
+    }
+
+    #[test]
+    fn multicall_reverts_on_required_failures() {
+        let fixtures = fixtures();
+        let aggregate3 = IMulticall3::aggregate3Call {
+            calls: vec![call3(bytes!("ff"), false)],
+        };
+        let aggregate = IMulticall3::aggregateCall {
+            calls: vec![IMulticall3::Call {
+                target: PAIR,
+                callData: bytes!("ff"),
+            }],
+        };
+
+        assert!(fixtures
+            .call(MULTICALL3_ADDRESS, &aggregate3.abi_encode())
+            .is_none());
+        assert!(fixtures
+            .call(MULTICALL3_ADDRESS, &aggregate.abi_encode())
+            .is_none());
+    }
+}
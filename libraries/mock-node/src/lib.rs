@@ -0,0 +1,23 @@
+pub use crate::{fixtures::*, node::*};
+use alloy::{
+    eips::BlockNumberOrTag,
+    primitives::{Address, Bytes, B256, U256},
+    providers::{
+        bindings::IMulticall3::{self, IMulticall3Calls},
+        MULTICALL3_ADDRESS,
+    },
+    rpc::types::{Filter, Header, Log, Transaction, TransactionRequest},
+    sol_types::{SolCall, SolInterface},
+};
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::{collections::HashMap, fs::File, io::BufReader, net::SocketAddr, path::Path, sync::Arc};
+use tokio::{
+    net::{TcpListener, TcpStream, ToSocketAddrs},
+    task::JoinHandle,
+};
+use tokio_tungstenite::tungstenite::Message;
+
+mod fixtures;
+mod node;
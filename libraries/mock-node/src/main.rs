@@ -0,0 +1,23 @@
+use mock_node::{Fixtures, MockNode};
+use std::env;
+
+/// Serve a fixtures file as a stand-in for `WEBSOCKET_ENDPOINT`:
+/// `mock-node <fixtures.json> [address]`, listening on 127.0.0.1:8546 by default
+#[tokio::main]
+async fn main() -> Result<(), anyhow::Error> {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let mut args = env::args().skip(1);
+    let Some(path) = args.next() else {
+        anyhow::bail!("usage: mock-node <fixtures.json> [address]");
+    };
+    let address = args.next().unwrap_or_else(|| "127.0.0.1:8546".to_string());
+
+    let fixtures = Fixtures::load(&path)?;
+    let mut node = MockNode::bind(address, fixtures).await?;
+    log::info!("Serving {path} on {}", node.ws_address());
+
+    node.run().await;
+
+    Ok(())
+}
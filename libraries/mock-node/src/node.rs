@@ -0,0 +1,234 @@
+use super::*;
+
+// Geth's error for calls that revert
+const EXECUTION_REVERTED: i64 = -32000;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+
+struct RpcError(i64, String);
+
+type RpcResult = Result<Value, RpcError>;
+
+fn invalid_params(e: impl std::fmt::Display) -> RpcError {
+    RpcError(INVALID_PARAMS, e.to_string())
+}
+
+fn param<T: serde::de::DeserializeOwned>(params: &Value, idx: usize) -> Result<T, RpcError> {
+    serde_json::from_value(params.get(idx).cloned().unwrap_or_default()).map_err(invalid_params)
+}
+
+fn to_value(value: impl Serialize) -> RpcResult {
+    serde_json::to_value(value).map_err(|e| RpcError(EXECUTION_REVERTED, e.to_string()))
+}
+
+/// State of a single websocket connection
+#[derive(Default)]
+struct Session {
+    next_subscription: u64,
+    /// Notifications owed to subscriptions opened by the last request
+    notifications: Vec<Value>,
+}
+
+impl Session {
+    fn subscribe(&mut self, fixtures: &Fixtures, params: &Value) -> RpcResult {
+        self.next_subscription += 1;
+        let id = format!("{:#x}", self.next_subscription);
+
+        let items: Vec<Value> = match param::<String>(params, 0)?.as_str() {
+            "logs" => {
+                let filter: Filter = match params.get(1) {
+                    Some(_) => param(params, 1)?,
+                    None => Filter::new(),
+                };
+                fixtures
+                    .subscribed_logs(&filter)
+                    .into_iter()
+                    .map(|log| json!(log))
+                    .collect()
+            }
+            "newHeads" => fixtures.heads.iter().map(|head| json!(head)).collect(),
+            "newPendingTransactions" => {
+                let full = params.get(1).and_then(Value::as_bool).unwrap_or_default();
+                fixtures
+                    .transactions
+                    .iter()
+                    .map(|tx| match full {
+                        true => json!(tx),
+                        false => json!(tx.inner.tx_hash()),
+                    })
+                    .collect()
+            }
+            kind => return Err(invalid_params(format!("unsupported subscription `{kind}`"))),
+        };
+
+        self.notifications.extend(items.into_iter().map(|result| {
+            json!({
+                "jsonrpc": "2.0",
+                "method": "eth_subscription",
+                "params": { "subscription": id, "result": result },
+            })
+        }));
+
+        Ok(json!(id))
+    }
+
+    fn dispatch(&mut self, fixtures: &Fixtures, method: &str, params: &Value) -> RpcResult {
+        match method {
+            "eth_blockNumber" => Ok(json!(format!("{:#x}", fixtures.block_number))),
+            "eth_chainId" => Ok(json!(format!("{:#x}", fixtures.chain_id))),
+            "net_version" => Ok(json!(fixtures.chain_id.to_string())),
+            "eth_getLogs" => to_value(fixtures.get_logs(&param(params, 0)?)),
+            "eth_call" => {
+                let tx: TransactionRequest = param(params, 0)?;
+                let to = tx.to.and_then(|to| to.to().copied()).unwrap_or_default();
+                let input = tx.input.input().cloned().unwrap_or_default();
+
+                match fixtures.call(to, &input) {
+                    Some(output) => Ok(json!(output)),
+                    None => Err(RpcError(
+                        EXECUTION_REVERTED,
+                        "execution reverted".to_string(),
+                    )),
+                }
+            }
+            "eth_getBalance" => Ok(json!(fixtures.account(&param(params, 0)?).balance)),
+            "eth_getTransactionCount" => {
+                let nonce = fixtures.account(&param(params, 0)?).nonce;
+                Ok(json!(format!("{nonce:#x}")))
+            }
+            "eth_getCode" => Ok(json!(fixtures.account(&param(params, 0)?).code)),
+            "eth_getStorageAt" => {
+                let account = fixtures.account(&param(params, 0)?);
+                let slot: U256 = param(params, 1)?;
+                let value = account.storage.get(&slot).copied().unwrap_or_default();
+                Ok(json!(B256::from(value)))
+            }
+            "eth_getBlockByNumber" => match fixtures.header(param(params, 0)?) {
+                Some(header) => {
+                    let mut block = json!(header);
+                    block["transactions"] = json!([]);
+                    block["uncles"] = json!([]);
+                    Ok(block)
+                }
+                None => Ok(Value::Null),
+            },
+            "eth_getTransactionByHash" => to_value(fixtures.transaction(&param(params, 0)?)),
+            "eth_subscribe" => self.subscribe(fixtures, params),
+            "eth_unsubscribe" => Ok(json!(true)),
+            _ => Err(RpcError(
+                METHOD_NOT_FOUND,
+                format!("the method {method} does not exist/is not available"),
+            )),
+        }
+    }
+
+    fn respond(&mut self, fixtures: &Fixtures, request: &Value) -> Value {
+        let id = request.get("id").cloned().unwrap_or_default();
+        let method = request
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let params = request.get("params").cloned().unwrap_or(json!([]));
+
+        log::debug!("{method} {params}");
+
+        match self.dispatch(fixtures, method, &params) {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err(RpcError(code, message)) => json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": code, "message": message },
+            }),
+        }
+    }
+
+    /// Answer a request or a batch of them
+    fn handle(&mut self, fixtures: &Fixtures, text: &str) -> Value {
+        match serde_json::from_str::<Value>(text) {
+            Ok(Value::Array(batch)) => Value::Array(
+                batch
+                    .iter()
+                    .map(|request| self.respond(fixtures, request))
+                    .collect(),
+            ),
+            Ok(request) => self.respond(fixtures, &request),
+            Err(e) => json!({
+                "jsonrpc": "2.0",
+                "id": null,
+                "error": { "code": -32700, "message": e.to_string() },
+            }),
+        }
+    }
+}
+
+async fn serve(stream: TcpStream, fixtures: Arc<Fixtures>) -> anyhow::Result<()> {
+    let mut ws = tokio_tungstenite::accept_async(stream).await?;
+    let mut session = Session::default();
+
+    while let Some(message) = ws.next().await {
+        match message? {
+            Message::Text(text) => {
+                let response = session.handle(&fixtures, &text);
+                ws.send(Message::text(response.to_string())).await?;
+
+                // Subscription items only follow the id they are delivered under
+                for notification in session.notifications.drain(..) {
+                    ws.send(Message::text(notification.to_string())).await?;
+                }
+            }
+            Message::Ping(data) => ws.send(Message::Pong(data)).await?,
+            Message::Close(_) => break,
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// A websocket JSON-RPC node serving [`Fixtures`] on a local port, stopped when dropped
+pub struct MockNode {
+    address: SocketAddr,
+    handle: JoinHandle<()>,
+}
+
+impl MockNode {
+    /// Serve `fixtures` on a free port of the loopback interface
+    pub async fn spawn(fixtures: Fixtures) -> anyhow::Result<Self> {
+        Self::bind("127.0.0.1:0", fixtures).await
+    }
+
+    pub async fn bind(address: impl ToSocketAddrs, fixtures: Fixtures) -> anyhow::Result<Self> {
+        let listener = TcpListener::bind(address).await?;
+        let address = listener.local_addr()?;
+        let fixtures = Arc::new(fixtures);
+
+        let handle = tokio::spawn(async move {
+            while let Ok((stream, peer)) = listener.accept().await {
+                let fixtures = Arc::clone(&fixtures);
+                tokio::spawn(async move {
+                    if let Err(e) = serve(stream, fixtures).await {
+                        log::debug!("Connection from {peer} closed: {e}");
+                    }
+                });
+            }
+        });
+
+        Ok(Self { address, handle })
+    }
+
+    /// Endpoint to use as `WEBSOCKET_ENDPOINT`
+    pub fn ws_address(&self) -> String {
+        format!("ws://{}", self.address)
+    }
+
+    /// Serve until the listener fails
+    pub async fn run(&mut self) {
+        let _ = (&mut self.handle).await;
+    }
+}
+
+impl Drop for MockNode {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
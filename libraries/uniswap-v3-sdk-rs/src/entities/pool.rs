@@ -283,6 +283,7 @@ impl<TP: TickDataProvider> Pool<TP> {
             self.fee.into(),
             self.sqrt_ratio_x96,
             self.liquidity,
+            self.tick_spacing().to_i24(),
             zero_for_one,
             amount_specified,
             sqrt_price_limit_x96,
@@ -876,4 +877,80 @@ mod tests {
             assert_eq!(input_amount.quotient(), 100.into());
         }
     }
+
+    mod sync_swaps {
+        use super::*;
+        use crate::currency_amount;
+        use proptest::prelude::*;
+
+        fn amount() -> impl Strategy<Value = u128> {
+            (1_u128..1000, 0_u32..31)
+                .prop_map(|(mantissa, exponent)| mantissa * 10_u128.pow(exponent))
+        }
+
+        fn same_result(
+            sync: Result<CurrencyAmount<Token>, Error>,
+            async_: Result<CurrencyAmount<Token>, Error>,
+        ) -> Result<(), TestCaseError> {
+            match (sync, async_) {
+                (Ok(sync), Ok(async_)) => {
+                    prop_assert!(sync.currency.equals(&async_.currency));
+                    prop_assert_eq!(sync.quotient(), async_.quotient());
+                }
+                (sync, async_) => {
+                    prop_assert_eq!(
+                        sync.unwrap_err().to_string(),
+                        async_.unwrap_err().to_string()
+                    );
+                }
+            }
+            Ok(())
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(256))]
+
+            #[test]
+            fn get_output_amount_sync_matches_async(
+                random in random_pool(),
+                zero_for_one in any::<bool>(),
+                amount in amount(),
+            ) {
+                let pool = random.pool();
+                let token: &Token = if zero_for_one { &TOKEN0 } else { &TOKEN1 };
+                let input_amount = currency_amount!(token, BigInt::from(amount));
+
+                same_result(
+                    pool.get_output_amount_sync(
+                        &input_amount,
+                        None,
+                        random.tick_current.to_i24(),
+                        &random.ticks_sync(),
+                    ),
+                    now(pool.get_output_amount(&input_amount, None)),
+                )?;
+            }
+
+            #[test]
+            fn get_input_amount_sync_matches_async(
+                random in random_pool(),
+                zero_for_one in any::<bool>(),
+                amount in amount(),
+            ) {
+                let pool = random.pool();
+                let token: &Token = if zero_for_one { &TOKEN1 } else { &TOKEN0 };
+                let output_amount = currency_amount!(token, BigInt::from(amount));
+
+                same_result(
+                    pool.get_input_amount_sync(
+                        &output_amount,
+                        None,
+                        random.tick_current.to_i24(),
+                        &random.ticks_sync(),
+                    ),
+                    now(pool.get_input_amount(&output_amount, None)),
+                )?;
+            }
+        }
+    }
 }
@@ -10,45 +10,106 @@ pub struct TickSync {
     pub is_init: bool,
 }
 
-/// Return the next tick that is initialized within a single word
+/// Return the next initialized tick within the same word as `tick`, the way the pool's tick
+/// bitmap does, or the word boundary when there is none
 ///
 /// ## Arguments
 ///
+/// * `ticks`: Ticks in ascending order of index, uninitialized entries are skipped
 /// * `tick`: The current tick
 /// * `lte`: Whether the next tick should be lte the current tick
+/// * `tick_spacing`: The tick spacing of the pool
 ///
-/// returns: Result<(Self::Index, bool), Error>
-pub fn get_next_initialized_tick(
-    current_tick: i32,
+/// returns: The next tick index and its liquidity net, `None` if the tick is not initialized
+#[inline]
+#[must_use]
+pub fn next_initialized_tick_within_one_word(
     ticks: &[TickSync],
-    zero_for_one: bool,
-) -> Result<TickSync, Error> {
-    if zero_for_one {
-        let idx = ticks
-            .iter()
-            .rev()
-            .position(|t| t.index.le(&current_tick))
-            .ok_or_else(|| Error::NoTickDataError)?;
-
-        for i in (0..=idx).rev() {
-            let tick = ticks[i];
-            if tick.is_init {
-                return Ok(tick);
-            }
+    tick: i32,
+    lte: bool,
+    tick_spacing: i32,
+) -> (i32, Option<i128>) {
+    let compressed = tick.compress(tick_spacing);
+    let split = ticks.partition_point(|t| t.index <= tick);
+
+    if lte {
+        let minimum = ((compressed >> 8) << 8) * tick_spacing;
+        match ticks[..split].iter().rev().find(|t| t.is_init) {
+            Some(t) if t.index >= minimum => (t.index, Some(t.liquidity_net)),
+            _ => (minimum, None),
         }
     } else {
-        let idx = ticks
-            .iter()
-            .position(|t| t.index.gt(&current_tick))
-            .ok_or_else(|| Error::NoTickDataError)?;
-
-        for i in idx..ticks.len() {
-            let tick = ticks[i];
-            if tick.is_init {
-                return Ok(tick);
-            }
+        let maximum = (((((compressed + 1) >> 8) + 1) << 8) - 1) * tick_spacing;
+        match ticks[split..].iter().find(|t| t.is_init) {
+            Some(t) if t.index <= maximum => (t.index, Some(t.liquidity_net)),
+            _ => (maximum, None),
         }
     }
+}
 
-    Err(Error::NoTickDataError)
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    fn ticks(indices: &[i32]) -> Vec<TickSync> {
+        indices
+            .iter()
+            .map(|&index| TickSync {
+                index,
+                liquidity_gross: 1,
+                liquidity_net: index as i128,
+                is_init: true,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_stops_at_word_boundaries() {
+        let ticks = ticks(&[-300, 10, 600]);
+
+        assert_eq!(
+            next_initialized_tick_within_one_word(&ticks, 5, true, 1),
+            (0, None)
+        );
+        assert_eq!(
+            next_initialized_tick_within_one_word(&ticks, -1, true, 1),
+            (-256, None)
+        );
+        assert_eq!(
+            next_initialized_tick_within_one_word(&ticks, 10, false, 1),
+            (255, None)
+        );
+        assert_eq!(
+            next_initialized_tick_within_one_word(&ticks, 255, false, 1),
+            (511, None)
+        );
+    }
+
+    #[test]
+    fn test_finds_initialized_ticks() {
+        let mut ticks = ticks(&[-300, 10, 20, 600]);
+        ticks[2].is_init = false;
+
+        assert_eq!(
+            next_initialized_tick_within_one_word(&ticks, 10, true, 1),
+            (10, Some(10))
+        );
+        assert_eq!(
+            next_initialized_tick_within_one_word(&ticks, 30, true, 1),
+            (10, Some(10))
+        );
+        assert_eq!(
+            next_initialized_tick_within_one_word(&ticks, 9, false, 1),
+            (10, Some(10))
+        );
+        assert_eq!(
+            next_initialized_tick_within_one_word(&ticks, -257, true, 1),
+            (-300, Some(-300))
+        );
+        assert_eq!(
+            next_initialized_tick_within_one_word(&ticks, 10, false, 5),
+            (600, Some(600))
+        );
+    }
 }
@@ -1,10 +1,18 @@
 use crate::prelude::{
     tick_math::{MAX_TICK, MIN_TICK},
+    tick_sync::TickSync,
     *,
 };
 pub(crate) use alloc::vec;
+use alloc::{collections::BTreeMap, vec::Vec};
 use alloy_primitives::U160;
+use core::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll, Waker},
+};
 use once_cell::sync::Lazy;
+use proptest::prelude::*;
 use uniswap_sdk_core::{prelude::*, token};
 
 pub(crate) static ETHER: Lazy<Ether> = Lazy::new(|| Ether::on_chain(1));
@@ -167,6 +175,105 @@ pub(crate) fn make_pool(token0: Token, token1: Token) -> Pool<TickListDataProvid
     .unwrap()
 }
 
+/// Resolve a future that never waits, such as a swap over an in-memory tick list
+pub(crate) fn now<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("in-memory tick data is never pending"),
+    }
+}
+
+/// A pool built from random positions on the tick spacing of its fee tier, spanning several
+/// words of the tick bitmap around the current price
+#[derive(Clone, Debug)]
+pub(crate) struct RandomPool {
+    pub(crate) fee: FeeAmount,
+    pub(crate) sqrt_price_x96: U160,
+    pub(crate) tick_current: i32,
+    pub(crate) liquidity: u128,
+    /// Initialized ticks in ascending order
+    pub(crate) ticks: Vec<Tick>,
+}
+
+impl RandomPool {
+    pub(crate) fn tick_spacing(&self) -> i32 {
+        self.fee.tick_spacing().as_i32()
+    }
+
+    pub(crate) fn ticks_sync(&self) -> Vec<TickSync> {
+        self.ticks
+            .iter()
+            .map(|tick| TickSync {
+                index: tick.index,
+                liquidity_gross: tick.liquidity_gross,
+                liquidity_net: tick.liquidity_net,
+                is_init: true,
+            })
+            .collect()
+    }
+
+    pub(crate) fn pool(&self) -> Pool<TickListDataProvider> {
+        Pool::new_with_tick_data_provider(
+            TOKEN0.clone(),
+            TOKEN1.clone(),
+            self.fee,
+            self.sqrt_price_x96,
+            self.liquidity,
+            TickListDataProvider::new(self.ticks.clone(), self.tick_spacing()).unwrap(),
+        )
+        .unwrap()
+    }
+}
+
+/// Positions as `(lower, width, liquidity)` in compressed ticks, and a price as a compressed
+/// tick, an offset within the spacing and a fraction in thousandths of the next tick
+pub(crate) fn random_pool() -> impl Strategy<Value = RandomPool> {
+    let fee = prop_oneof![
+        Just(FeeAmount::LOWEST),
+        Just(FeeAmount::LOW),
+        Just(FeeAmount::MEDIUM),
+        Just(FeeAmount::HIGH),
+    ];
+    let position = (
+        -1500_i32..1500,
+        1_i32..1500,
+        10_u128.pow(9)..10_u128.pow(24),
+    );
+    let price = (-1500_i32..1500, 0_i32..200, 0_u64..1000);
+
+    (fee, prop::collection::vec(position, 1..8), price).prop_map(
+        |(fee, positions, (compressed, offset, fraction))| {
+            let tick_spacing = fee.tick_spacing().as_i32();
+            let tick_current = compressed * tick_spacing + offset % tick_spacing;
+            let lower = get_sqrt_ratio_at_tick(tick_current.to_i24()).unwrap();
+            let upper = get_sqrt_ratio_at_tick((tick_current + 1).to_i24()).unwrap();
+            let sqrt_price_x96 = lower + (upper - lower) * U160::from(fraction) / U160::from(1000);
+
+            let mut ticks = BTreeMap::<i32, Tick>::new();
+            let mut liquidity = 0;
+            for (lower, width, amount) in positions {
+                let (lower, upper) = (lower * tick_spacing, (lower + width) * tick_spacing);
+                if (lower..upper).contains(&tick_current) {
+                    liquidity += amount;
+                }
+                for (index, net) in [(lower, amount as i128), (upper, -(amount as i128))] {
+                    let tick = ticks.entry(index).or_insert(Tick::new(index, 0, 0));
+                    tick.liquidity_gross += amount;
+                    tick.liquidity_net += net;
+                }
+            }
+
+            RandomPool {
+                fee,
+                sqrt_price_x96,
+                tick_current,
+                liquidity,
+                ticks: ticks.into_values().collect(),
+            }
+        },
+    )
+}
+
 #[cfg(feature = "extensions")]
 pub(crate) use extensions::*;
 
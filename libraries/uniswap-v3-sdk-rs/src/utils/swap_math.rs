@@ -1,5 +1,5 @@
 use crate::prelude::{
-    tick_sync::{get_next_initialized_tick, TickSync},
+    tick_sync::{next_initialized_tick_within_one_word, TickSync},
     *,
 };
 use alloy_primitives::{
@@ -291,6 +291,7 @@ pub fn v3_swap_simulation(
     fee: U24,
     sqrt_price_x96: U160,
     liquidity: u128,
+    tick_spacing: I24,
     zero_for_one: bool,
     amount_specified: I256,
     sqrt_price_limit_x96: Option<U160>,
@@ -319,6 +320,11 @@ pub fn v3_swap_simulation(
         }
     }
 
+    // fee tiers without a known spacing have no tick bitmap to walk
+    if tick_spacing <= I24::ZERO {
+        return Err(TickListError::TickSpacing.into());
+    }
+
     let exact_input = amount_specified >= I256::ZERO;
 
     // keep track of swap state
@@ -330,12 +336,16 @@ pub fn v3_swap_simulation(
         liquidity,
     };
 
+    // every step ends on an initialized tick or a word boundary, so the walk can't take more
+    // steps than there are of both between the price limits
+    let max_runs =
+        ticks.len() + ((MAX_TICK_I32 - MIN_TICK_I32) / (tick_spacing.as_i32() << 8)) as usize + 2;
     let mut runs = 0;
 
     // start swap while loop
     while !state.amount_specified_remaining.is_zero()
         && state.sqrt_price_x96 != sqrt_price_limit_x96
-        && runs <= ticks.len()
+        && runs <= max_runs
     {
         let mut step = StepComputations {
             sqrt_price_start_x96: state.sqrt_price_x96,
@@ -345,9 +355,14 @@ pub fn v3_swap_simulation(
         // because each iteration of the while loop rounds, we can't optimize this code
         // (relative to the smart contract) by simply traversing to the next available tick, we
         // instead need to exactly replicate
-        let tick = get_next_initialized_tick(current_tick.as_i32(), ticks, zero_for_one)?;
-        step.tick_next = tick.index.to_i24();
-        step.initialized = tick.is_init;
+        let (tick_next, liquidity_net) = next_initialized_tick_within_one_word(
+            ticks,
+            state.tick_current.as_i32(),
+            zero_for_one,
+            tick_spacing.as_i32(),
+        );
+        step.tick_next = tick_next.to_i24();
+        step.initialized = liquidity_net.is_some();
 
         step.tick_next = I24::from_i24(step.tick_next.to_i24().clamp(MIN_TICK, MAX_TICK));
         step.sqrt_price_next_x96 = get_sqrt_ratio_at_tick(step.tick_next.to_i24())?;
@@ -385,9 +400,7 @@ pub fn v3_swap_simulation(
 
         if state.sqrt_price_x96 == step.sqrt_price_next_x96 {
             // if the tick is initialized, run the tick transition
-            if step.initialized {
-                let mut liquidity_net = tick.liquidity_net;
-
+            if let Some(mut liquidity_net) = liquidity_net {
                 // if we're moving leftward, we interpret liquidityNet as the opposite sign
                 // safe because liquidityNet cannot be type(int128).min
                 if zero_for_one {
@@ -441,4 +454,272 @@ mod tests {
         assert_eq!(amount_out, U256::from_limbs([4846, 0, 0, 0]));
         assert_eq!(fee_amount, U256::from_limbs([14, 0, 0, 0]));
     }
+
+    mod simulation {
+        use super::*;
+        use crate::tests::*;
+        use proptest::prelude::*;
+        use std::collections::HashMap;
+        use uniswap_v3_math::{
+            error::UniswapV3MathError, liquidity_math, swap_math, tick_bitmap, tick_math,
+        };
+
+        /// The swap loop of the pool contract, over the tick bitmap and math of `uniswap_v3_math`
+        fn reference_swap(
+            pool: &RandomPool,
+            zero_for_one: bool,
+            amount_specified: I256,
+            sqrt_price_limit_x96: U160,
+        ) -> Result<SwapState, UniswapV3MathError> {
+            let tick_spacing = pool.tick_spacing();
+            let mut bitmap = HashMap::new();
+            let mut liquidity_net = HashMap::new();
+            for tick in &pool.ticks {
+                tick_bitmap::flip_tick(&mut bitmap, tick.index, tick_spacing)?;
+                liquidity_net.insert(tick.index, tick.liquidity_net);
+            }
+
+            let sqrt_price_limit_x96 = U256::from(sqrt_price_limit_x96);
+            let exact_input = amount_specified >= I256::ZERO;
+            let mut amount_specified_remaining = amount_specified;
+            let mut amount_calculated = I256::ZERO;
+            let mut sqrt_price_x96 = U256::from(pool.sqrt_price_x96);
+            let mut tick = pool.tick_current;
+            let mut liquidity = pool.liquidity;
+
+            while !amount_specified_remaining.is_zero() && sqrt_price_x96 != sqrt_price_limit_x96 {
+                let sqrt_price_start_x96 = sqrt_price_x96;
+                let (tick_next, initialized) = tick_bitmap::next_initialized_tick_within_one_word(
+                    &bitmap,
+                    tick,
+                    tick_spacing,
+                    zero_for_one,
+                )?;
+                let tick_next = tick_next.clamp(tick_math::MIN_TICK, tick_math::MAX_TICK);
+                let sqrt_price_next_x96 = tick_math::get_sqrt_ratio_at_tick(tick_next)?;
+
+                let (sqrt_price, amount_in, amount_out, fee_amount) = swap_math::compute_swap_step(
+                    sqrt_price_x96,
+                    if zero_for_one {
+                        sqrt_price_next_x96.max(sqrt_price_limit_x96)
+                    } else {
+                        sqrt_price_next_x96.min(sqrt_price_limit_x96)
+                    },
+                    liquidity,
+                    amount_specified_remaining,
+                    <U24 as From<FeeAmount>>::from(pool.fee).to(),
+                )?;
+                sqrt_price_x96 = sqrt_price;
+
+                if exact_input {
+                    amount_specified_remaining -= I256::from_raw(amount_in + fee_amount);
+                    amount_calculated -= I256::from_raw(amount_out);
+                } else {
+                    amount_specified_remaining += I256::from_raw(amount_out);
+                    amount_calculated += I256::from_raw(amount_in + fee_amount);
+                }
+
+                if sqrt_price_x96 == sqrt_price_next_x96 {
+                    if initialized {
+                        let net = liquidity_net[&tick_next];
+                        liquidity = liquidity_math::add_delta(
+                            liquidity,
+                            if zero_for_one { -net } else { net },
+                        )?;
+                    }
+                    tick = if zero_for_one {
+                        tick_next - 1
+                    } else {
+                        tick_next
+                    };
+                } else if sqrt_price_x96 != sqrt_price_start_x96 {
+                    tick = tick_math::get_tick_at_sqrt_ratio(sqrt_price_x96)?;
+                }
+            }
+
+            Ok(SwapState {
+                amount_specified_remaining,
+                amount_calculated,
+                sqrt_price_x96: U160::from(sqrt_price_x96),
+                tick_current: tick,
+                liquidity,
+            })
+        }
+
+        fn sync_swap(
+            pool: &RandomPool,
+            zero_for_one: bool,
+            amount_specified: I256,
+            sqrt_price_limit_x96: Option<U160>,
+        ) -> Result<SwapState<I24>, Error> {
+            v3_swap_simulation(
+                pool.fee.into(),
+                pool.sqrt_price_x96,
+                pool.liquidity,
+                pool.fee.tick_spacing(),
+                zero_for_one,
+                amount_specified,
+                sqrt_price_limit_x96,
+                pool.tick_current.to_i24(),
+                &pool.ticks_sync(),
+            )
+        }
+
+        fn async_swap(
+            pool: &RandomPool,
+            zero_for_one: bool,
+            amount_specified: I256,
+            sqrt_price_limit_x96: Option<U160>,
+        ) -> Result<SwapState, Error> {
+            now(v3_swap(
+                pool.fee.into(),
+                pool.sqrt_price_x96,
+                pool.tick_current,
+                pool.liquidity,
+                pool.tick_spacing(),
+                &pool.pool().tick_data_provider,
+                zero_for_one,
+                amount_specified,
+                sqrt_price_limit_x96,
+            ))
+        }
+
+        fn assert_same_state(sync: &SwapState<I24>, other: &SwapState) {
+            assert_eq!(
+                sync.amount_specified_remaining,
+                other.amount_specified_remaining
+            );
+            assert_eq!(sync.amount_calculated, other.amount_calculated);
+            assert_eq!(sync.sqrt_price_x96, other.sqrt_price_x96);
+            assert_eq!(sync.tick_current.as_i32(), other.tick_current);
+            assert_eq!(sync.liquidity, other.liquidity);
+        }
+
+        /// A signed amount from 1 wei up to 10^33, negative for exact output
+        fn amount_specified() -> impl Strategy<Value = I256> {
+            (1_u64..1000, 0_usize..31, any::<bool>()).prop_map(|(mantissa, exponent, exact_in)| {
+                let amount = I256::try_from(mantissa).unwrap()
+                    * I256::try_from(10_u64).unwrap().pow(U256::from(exponent));
+                if exact_in {
+                    amount
+                } else {
+                    -amount
+                }
+            })
+        }
+
+        /// No limit, or a limit a number of tick spacings away in the direction of the swap
+        fn sqrt_price_limit(
+            pool: &RandomPool,
+            zero_for_one: bool,
+            spacings: Option<i32>,
+        ) -> Option<U160> {
+            let distance = spacings? * pool.tick_spacing();
+            let tick = if zero_for_one {
+                (pool.tick_current - distance).max(MIN_TICK_I32 + 1)
+            } else {
+                (pool.tick_current + distance).min(MAX_TICK_I32 - 1)
+            };
+            Some(get_sqrt_ratio_at_tick(tick.to_i24()).unwrap())
+        }
+
+        proptest! {
+            #![proptest_config(ProptestConfig::with_cases(512))]
+
+            #[test]
+            fn matches_async_and_reference_swaps(
+                pool in random_pool(),
+                zero_for_one in any::<bool>(),
+                amount_specified in amount_specified(),
+                spacings in prop::option::of(1_i32..3000),
+            ) {
+                let limit = sqrt_price_limit(&pool, zero_for_one, spacings);
+                let sync = sync_swap(&pool, zero_for_one, amount_specified, limit);
+                let async_ = async_swap(&pool, zero_for_one, amount_specified, limit);
+                let reference = reference_swap(
+                    &pool,
+                    zero_for_one,
+                    amount_specified,
+                    limit.unwrap_or(if zero_for_one {
+                        MIN_SQRT_RATIO + ONE
+                    } else {
+                        MAX_SQRT_RATIO - ONE
+                    }),
+                );
+
+                match sync {
+                    Ok(sync) => {
+                        assert_same_state(&sync, &async_.unwrap());
+                        assert_same_state(&sync, &reference.unwrap());
+                    }
+                    Err(_) => {
+                        prop_assert!(async_.is_err());
+                        prop_assert!(reference.is_err());
+                    }
+                }
+            }
+
+            #[test]
+            fn stops_at_price_limit(
+                pool in random_pool(),
+                zero_for_one in any::<bool>(),
+                spacings in 1_i32..3000,
+            ) {
+                let limit = sqrt_price_limit(&pool, zero_for_one, Some(spacings));
+                let amount = if zero_for_one { I256::MAX } else { I256::MIN + I256::ONE };
+                let state = sync_swap(&pool, zero_for_one, amount, limit).unwrap();
+
+                // A swap stopping on a tick going down is left just below it, like the contract
+                let tick = state.tick_current;
+                prop_assert_eq!(Some(state.sqrt_price_x96), limit);
+                prop_assert!(get_sqrt_ratio_at_tick(tick).unwrap() <= state.sqrt_price_x96);
+                prop_assert!(get_sqrt_ratio_at_tick(tick + I24::ONE).unwrap() >= state.sqrt_price_x96);
+            }
+        }
+
+        #[test]
+        fn crosses_every_initialized_tick() {
+            let ticks = [
+                Tick::new(-600, 100_u128.pow(7), 100_i128.pow(7)),
+                Tick::new(-60, 100_u128.pow(6), 100_i128.pow(6)),
+                Tick::new(60, 100_u128.pow(6), -(100_i128.pow(6))),
+                Tick::new(600, 100_u128.pow(7), -(100_i128.pow(7))),
+            ];
+            let pool = RandomPool {
+                fee: FeeAmount::MEDIUM,
+                sqrt_price_x96: get_sqrt_ratio_at_tick(I24::ZERO).unwrap(),
+                tick_current: 0,
+                liquidity: 100_u128.pow(7) + 100_u128.pow(6),
+                ticks: ticks.to_vec(),
+            };
+
+            for zero_for_one in [true, false] {
+                let state = sync_swap(&pool, zero_for_one, I256::MAX, None).unwrap();
+                assert_same_state(
+                    &state,
+                    &async_swap(&pool, zero_for_one, I256::MAX, None).unwrap(),
+                );
+                // Past the last tick the pool has no liquidity left to swap against
+                assert_eq!(state.liquidity, 0);
+                if zero_for_one {
+                    assert_eq!(state.sqrt_price_x96, MIN_SQRT_RATIO + ONE);
+                } else {
+                    assert_eq!(state.sqrt_price_x96, MAX_SQRT_RATIO - ONE);
+                }
+            }
+        }
+
+        #[test]
+        fn rejects_zero_tick_spacing() {
+            let pool = RandomPool {
+                fee: FeeAmount::CUSTOM(0),
+                sqrt_price_x96: get_sqrt_ratio_at_tick(I24::ZERO).unwrap(),
+                tick_current: 0,
+                liquidity: 1,
+                ticks: Vec::new(),
+            };
+
+            assert!(sync_swap(&pool, true, I256::ONE, None).is_err());
+        }
+    }
 }
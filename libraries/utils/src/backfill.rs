@@ -0,0 +1,263 @@
+use super::*;
+use crate::metrics::record_rpc_error;
+use alloy::{
+    providers::Provider,
+    rpc::types::{Filter, Log},
+    sol_types::SolEvent,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    io::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+// Fragments of the errors nodes return when a range holds too many logs
+const RANGE_ERRORS: [&str; 6] = [
+    "query returned more than",
+    "too many",
+    "response size",
+    "limit exceeded",
+    "block range",
+    "too large",
+];
+
+fn is_range_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    RANGE_ERRORS.iter().any(|fragment| message.contains(fragment))
+}
+
+/// Next block to fetch, persisted after every range the consumer accepted
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Cursor {
+    pub next_block: u64,
+}
+
+impl Cursor {
+    fn load<'a>(path: &Path) -> Result<Option<Self>, CustomError<'a>> {
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        Ok(Some(from_reader(BufReader::new(File::open(path)?))?))
+    }
+
+    /// Write through a temporary file, so a crash never leaves a truncated cursor behind
+    fn save<'a>(&self, path: &Path) -> Result<(), CustomError<'a>> {
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+
+        let mut file = File::create(&tmp)?;
+        file.write_all(serde_json::to_string(self)?.as_bytes())?;
+        file.sync_all()?;
+        std::fs::rename(&tmp, path)?;
+
+        Ok(())
+    }
+}
+
+/// `eth_getLogs` walker that adapts its block range to what the node accepts
+#[derive(Debug, Clone)]
+pub struct Backfill {
+    /// Addresses and topics to fetch; the block range is set per request
+    pub filter: Filter,
+    pub initial_chunk: u64,
+    pub max_chunk: u64,
+    /// Ranges returning fewer logs than this double the next range
+    pub sparse_logs: usize,
+    /// Retries of a failing range before giving up, with doubling delays
+    pub max_retries: u32,
+    pub backoff: Duration,
+    /// Where the cursor is kept between runs, if anywhere
+    pub cursor_path: Option<PathBuf>,
+}
+
+impl Backfill {
+    pub fn new(filter: Filter) -> Self {
+        Self {
+            filter,
+            initial_chunk: 10_000,
+            max_chunk: 500_000,
+            sparse_logs: 2_000,
+            max_retries: 5,
+            backoff: Duration::from_millis(500),
+            cursor_path: None,
+        }
+    }
+
+    /// Fetch the logs of `from_block..=to_block`, resuming from the cursor when there is one.
+    /// The consumer gets each range's logs in chain order with the range's last block,
+    /// and the cursor only moves once it returned `Ok`. Returns the next block to fetch.
+    pub async fn run<'a, P, F, E>(
+        &self,
+        provider: &P,
+        from_block: u64,
+        to_block: u64,
+        mut consumer: F,
+    ) -> Result<u64, E>
+    where
+        P: Provider,
+        F: FnMut(Vec<Log>, u64) -> Result<(), E>,
+        E: From<CustomError<'a>>,
+    {
+        let mut from = match &self.cursor_path {
+            Some(path) => Cursor::load(path)?
+                .map_or(from_block, |cursor| cursor.next_block.max(from_block)),
+            None => from_block,
+        };
+        let mut chunk = self.initial_chunk.max(1);
+        let mut retries = 0;
+
+        while from <= to_block {
+            let to = (from + chunk - 1).min(to_block);
+            let filter = self.filter.clone().from_block(from).to_block(to);
+
+            match provider.get_logs(&filter).await {
+                Ok(logs) => {
+                    let sparse = logs.len() < self.sparse_logs;
+                    consumer(logs, to)?;
+
+                    from = to + 1;
+                    retries = 0;
+                    if let Some(path) = &self.cursor_path {
+                        Cursor { next_block: from }.save(path)?;
+                    }
+                    if sparse {
+                        chunk = (chunk * 2).min(self.max_chunk);
+                    }
+                }
+                Err(e) if chunk > 1 && is_range_error(&e.to_string()) => {
+                    chunk /= 2;
+                    log::debug!("Blocks {from}-{to} too large ({e}), retrying {chunk} blocks");
+                }
+                Err(e) if retries < self.max_retries => {
+                    record_rpc_error("eth_getLogs");
+                    let delay = self.backoff * 2u32.pow(retries);
+                    retries += 1;
+                    log::warn!("Blocks {from}-{to} failed ({e}), retry {retries} in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                }
+                Err(e) => {
+                    record_rpc_error("eth_getLogs");
+                    return Err(CustomError::from(e).into());
+                }
+            }
+        }
+
+        Ok(from)
+    }
+
+    /// Like [`Backfill::run`], handing the consumer every log that decodes as `T`, one at a time
+    pub async fn run_decoded<'a, T, P, F, E>(
+        &self,
+        provider: &P,
+        from_block: u64,
+        to_block: u64,
+        mut consumer: F,
+    ) -> Result<u64, E>
+    where
+        T: SolEvent,
+        P: Provider,
+        F: FnMut(alloy::rpc::types::Log<T>) -> Result<(), E>,
+        E: From<CustomError<'a>>,
+    {
+        self.run(provider, from_block, to_block, |logs, _| {
+            logs.iter()
+                .filter_map(|log| log.log_decode::<T>().ok())
+                .try_for_each(&mut consumer)
+        })
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        primitives::{Log as PrimitiveLog, LogData},
+        providers::ProviderBuilder,
+        transports::mock::Asserter,
+    };
+
+    fn log(block: u64) -> Log {
+        Log {
+            inner: PrimitiveLog {
+                address: Address::ZERO,
+                data: LogData::empty(),
+            },
+            block_number: Some(block),
+            ..Default::default()
+        }
+    }
+
+    fn backfill(initial_chunk: u64) -> Backfill {
+        Backfill {
+            initial_chunk,
+            sparse_logs: 1,
+            backoff: Duration::from_millis(1),
+            ..Backfill::new(Filter::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn narrows_on_range_errors_and_widens_on_sparse_ranges() {
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        // 0-99 fails, 0-49 holds a log, 50-99 is empty so 100-199 is tried whole
+        asserter.push_failure_msg("query returned more than 10000 results");
+        asserter.push_success(&vec![log(10)]);
+        asserter.push_success(&Vec::<Log>::new());
+        asserter.push_success(&Vec::<Log>::new());
+
+        let mut ranges = Vec::new();
+        let next = backfill(100)
+            .run(&provider, 0, 199, |logs, to| {
+                ranges.push((logs.len(), to));
+                Ok::<_, CustomError>(())
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(next, 200);
+        assert_eq!(ranges, vec![(1, 49), (0, 99), (0, 199)]);
+        assert!(asserter.read_q().is_empty());
+    }
+
+    #[tokio::test]
+    async fn retries_transport_errors_then_resumes_from_cursor() {
+        let cursor_path =
+            std::env::temp_dir().join(format!("backfill_cursor_test-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&cursor_path);
+        let asserter = Asserter::new();
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter.clone());
+        let backfill = Backfill {
+            cursor_path: Some(cursor_path.clone()),
+            max_retries: 1,
+            ..backfill(10)
+        };
+
+        // A failure beyond the retry budget stops the run after the first range
+        asserter.push_success(&Vec::<Log>::new());
+        asserter.push_failure_msg("connection reset");
+        asserter.push_failure_msg("connection reset");
+        let result = backfill
+            .run(&provider, 0, 19, |_, _| Ok::<_, CustomError>(()))
+            .await;
+        assert!(result.is_err());
+
+        // The next run starts where the cursor was left
+        asserter.push_success(&Vec::<Log>::new());
+        let mut ranges = Vec::new();
+        let next = backfill
+            .run(&provider, 0, 19, |_, to| {
+                ranges.push(to);
+                Ok::<_, CustomError>(())
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(next, 20);
+        assert_eq!(ranges, vec![19]);
+        std::fs::remove_file(cursor_path).unwrap();
+    }
+}
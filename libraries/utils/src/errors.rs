@@ -26,21 +26,39 @@ pub enum CustomError<'a> {
     #[error("Uniswap sdk core error: `{0}`!")]
     UniswapSdkCoreError(#[from] uniswap_sdk_core::error::Error),
 
+    #[error("Abi decoding error: `{0}`!")]
+    SolTypesError(#[from] alloy::sol_types::Error),
+
     #[error("Json serialisation failed: `{0}`!")]
     JsonParseError(#[from] serde_json::Error),
 
     #[error("IO error: `{0}`!")]
     IoError(#[from] io::Error),
 
-    #[error("IO error: `{0}`!")]
-    EthAbiError(#[from] web3::ethabi::Error),
-
     #[error("Error while getting `{0}`!")]
     NotFound(&'a str),
 
     #[error("Error while getting address: `{0}`!")]
     AddressNotFound(Address),
 
+    #[error("Invalid path: `{0}`!")]
+    InvalidPath(&'a str),
+
+    #[error("Invalid calldata: `{0}`!")]
+    InvalidCalldata(&'a str),
+
+    #[error("Implausible decimals for token `{0}`: {1}!")]
+    InvalidDecimals(Address, u8),
+
+    #[error("Simulation error: `{0}`!")]
+    SimulationError(String),
+
     #[error("Error while parsing bigInt!")]
     ParseBigIntError(#[from] ParseBigIntError),
+
+    #[error("Error while parsing float: `{0}`!")]
+    ParseFloatError(#[from] ParseFloatError),
+
+    #[error("Multicall error: `{0}`!")]
+    MulticallError(#[from] MulticallError),
 }
@@ -1,22 +1,38 @@
 pub use crate::{errors::*, parser::*, util::*};
 use alloy::{
     contract,
-    primitives::Address,
+    primitives::{Address, U256},
+    providers::MulticallError,
     transports::{RpcError, TransportErrorKind},
 };
 use dotenv::dotenv;
 use num_bigint::ParseBigIntError;
+use once_cell::sync::Lazy;
+use prometheus::{
+    register_gauge_vec, register_histogram, register_histogram_vec, register_int_counter,
+    register_int_counter_vec, register_int_gauge, Encoder, GaugeVec, Histogram, HistogramVec,
+    IntCounter, IntCounterVec, IntGauge, TextEncoder,
+};
 use serde_json::from_reader;
 use std::{
+    collections::HashMap,
     env::{self, VarError},
     fs::File,
     io::{self, BufReader},
+    num::ParseFloatError,
+    sync::Mutex,
+    time::Instant,
 };
 use thiserror::Error;
-use web3::types::H160;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
 
 mod errors;
 mod parser;
 mod util;
+pub mod backfill;
 #[macro_use]
 pub mod logger;
+pub mod metrics;
@@ -1,20 +1,27 @@
+/// Time a block into the `span_duration_seconds` histogram and log it at debug level
 #[macro_export]
 macro_rules! debug_time {
     ($label:expr, $block:block) => {{
-        use std::time::Instant;
-        let start = Instant::now();
+        let timer = $crate::metrics::SPAN_SECONDS
+            .with_label_values(&[$label])
+            .start_timer();
         let result = $block;
-        log::debug!("{} took {:?}", $label, start.elapsed());
+        let elapsed = std::time::Duration::from_secs_f64(timer.stop_and_record());
+        log::debug!("{} took {:?}", $label, elapsed);
         result
     }};
 }
+
+/// Time a block into the `span_duration_seconds` histogram and log it at info level
 #[macro_export]
 macro_rules! info_time {
     ($label:expr, $block:block) => {{
-        use std::time::Instant;
-        let start = Instant::now();
+        let timer = $crate::metrics::SPAN_SECONDS
+            .with_label_values(&[$label])
+            .start_timer();
         let result = $block;
-        log::info!("{} took {:?}", $label, start.elapsed());
+        let elapsed = std::time::Duration::from_secs_f64(timer.stop_and_record());
+        log::info!("{} took {:?}", $label, elapsed);
         result
     }};
 }
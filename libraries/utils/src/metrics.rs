@@ -0,0 +1,175 @@
+use super::*;
+
+/// Buckets from 10µs to ~10s, wide enough for both event handling and startup work
+const LATENCY_BUCKETS: &[f64] = &[
+    0.00001, 0.00005, 0.0001, 0.0005, 0.001, 0.005, 0.01, 0.05, 0.1, 0.5, 1.0, 5.0, 10.0,
+];
+
+pub static SPAN_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "span_duration_seconds",
+        "Duration of spans timed with debug_time! and info_time!",
+        &["span"],
+        LATENCY_BUCKETS.to_vec()
+    )
+    .unwrap()
+});
+
+pub static EVENTS_PROCESSED: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "events_processed_total",
+        "Pool events applied to state, by kind and protocol",
+        &["kind", "protocol"]
+    )
+    .unwrap()
+});
+
+pub static EVENT_APPLY_SECONDS: Lazy<HistogramVec> = Lazy::new(|| {
+    register_histogram_vec!(
+        "event_apply_seconds",
+        "Latency from receiving a pool event to its state being applied",
+        &["protocol"],
+        LATENCY_BUCKETS.to_vec()
+    )
+    .unwrap()
+});
+
+pub static PATH_QUERY_SECONDS: Lazy<Histogram> = Lazy::new(|| {
+    register_histogram!(
+        "path_query_seconds",
+        "Latency of a path query from graph build to best path",
+        LATENCY_BUCKETS.to_vec()
+    )
+    .unwrap()
+});
+
+pub static GRAPH_NODES: Lazy<IntGauge> =
+    Lazy::new(|| register_int_gauge!("swap_graph_nodes", "Tokens in the last swap graph").unwrap());
+
+pub static GRAPH_EDGES: Lazy<IntGauge> = Lazy::new(|| {
+    register_int_gauge!("swap_graph_edges", "Pool edges in the last swap graph").unwrap()
+});
+
+pub static POOL_STATE_AGE: Lazy<GaugeVec> = Lazy::new(|| {
+    register_gauge_vec!(
+        "pool_state_age_seconds",
+        "Seconds since pool state of a protocol was last updated",
+        &["protocol"]
+    )
+    .unwrap()
+});
+
+pub static RPC_ERRORS: Lazy<IntCounterVec> = Lazy::new(|| {
+    register_int_counter_vec!(
+        "rpc_errors_total",
+        "Failed RPC calls, by method",
+        &["method"]
+    )
+    .unwrap()
+});
+
+pub static WS_RECONNECTS: Lazy<IntCounter> = Lazy::new(|| {
+    register_int_counter!(
+        "websocket_reconnects_total",
+        "Websocket subscriptions re-established after ending"
+    )
+    .unwrap()
+});
+
+/// Last state update per protocol, turned into `POOL_STATE_AGE` on every scrape
+static POOL_STATE_UPDATED: Lazy<Mutex<HashMap<String, Instant>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Count an applied event and observe how long it took since `received`
+pub fn record_event(kind: &str, protocol: &str, received: Instant) {
+    EVENTS_PROCESSED.with_label_values(&[kind, protocol]).inc();
+    EVENT_APPLY_SECONDS
+        .with_label_values(&[protocol])
+        .observe(received.elapsed().as_secs_f64());
+
+    if let Ok(mut updated) = POOL_STATE_UPDATED.lock() {
+        updated.insert(protocol.to_string(), Instant::now());
+    }
+}
+
+pub fn record_rpc_error(method: &str) {
+    RPC_ERRORS.with_label_values(&[method]).inc();
+}
+
+/// Text exposition of every registered metric
+pub fn render() -> String {
+    if let Ok(updated) = POOL_STATE_UPDATED.lock() {
+        for (protocol, instant) in updated.iter() {
+            POOL_STATE_AGE
+                .with_label_values(&[protocol])
+                .set(instant.elapsed().as_secs_f64());
+        }
+    }
+
+    let mut buffer = Vec::new();
+    let _ = TextEncoder::new().encode(&prometheus::gather(), &mut buffer);
+
+    String::from_utf8(buffer).unwrap_or_default()
+}
+
+/// Serve `GET /metrics` on `address` until the listener fails
+pub async fn serve<'a>(address: &str) -> Result<(), CustomError<'a>> {
+    let listener = TcpListener::bind(address).await?;
+
+    loop {
+        let (mut socket, _) = listener.accept().await?;
+
+        tokio::spawn(async move {
+            let mut request = [0u8; 1024];
+            let Ok(n) = socket.read(&mut request).await else {
+                return;
+            };
+
+            let response = if request[..n].starts_with(b"GET /metrics") {
+                let body = render();
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\n\r\n{body}",
+                    body.len()
+                )
+            } else {
+                "HTTP/1.1 404 Not Found\r\nContent-Length: 0\r\n\r\n".to_string()
+            };
+
+            let _ = socket.write_all(response.as_bytes()).await;
+        });
+    }
+}
+
+/// Start the exporter in the background when `METRICS_ADDRESS` is set
+pub fn spawn_exporter() {
+    let Ok(address) = env::var("METRICS_ADDRESS") else {
+        return;
+    };
+
+    log::info!("Serving metrics on http://{address}/metrics");
+
+    tokio::spawn(async move {
+        if let Err(e) = serve(&address).await {
+            log::error!("Metrics exporter stopped: {e}");
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_includes_recorded_metrics() {
+        record_event("swap", "uniswap_v3", Instant::now());
+        record_rpc_error("eth_getBlockReceipts");
+        debug_time!("render_test", {});
+
+        let text = render();
+
+        assert!(text.contains(r#"events_processed_total{kind="swap",protocol="uniswap_v3"} 1"#));
+        assert!(text.contains(r#"rpc_errors_total{method="eth_getBlockReceipts"} 1"#));
+        assert!(text.contains(r#"pool_state_age_seconds{protocol="uniswap_v3"}"#));
+        assert!(text.contains(r#"span_duration_seconds_count{span="render_test"} 1"#));
+    }
+}
@@ -2,8 +2,7 @@ use super::*;
 
 pub struct EnvParser {
     pub ws_address: String,
-    pub pools: Vec<H160>,
-    pub pools_addrs: Vec<Address>,
+    pub pools: Vec<Address>,
 }
 
 impl<'a> EnvParser {
@@ -17,20 +16,14 @@ impl<'a> EnvParser {
         // Parse and decode addresses
         let addresses: Vec<String> = from_reader(reader)?;
 
-        let pools_addrs: Vec<Address> = addresses
+        let pools: Vec<Address> = addresses
             .iter()
             .map(|s| s.parse())
             .collect::<Result<_, _>>()?;
 
-        let pools: Vec<H160> = pools_addrs
-            .iter()
-            .map(|addr| H160::from(addr.into_array()))
-            .collect();
-
         Ok(Self {
             ws_address: env::var("WEBSOCKET_ENDPOINT")?,
             pools,
-            pools_addrs,
         })
     }
 }
@@ -1,6 +1,30 @@
-pub fn format_with_decimals(value: u128, decimals: u32) -> String {
-    let tens = 10u128.pow(decimals);
+use super::*;
+
+/// Render a raw token amount with exactly `decimals` fractional digits
+pub fn format_with_decimals(value: U256, decimals: u32) -> String {
+    if decimals == 0 {
+        return value.to_string();
+    }
+
+    let tens = U256::from(10).pow(U256::from(decimals));
     let int_part = value / tens;
-    let frac_part = value % tens;
-    format!("{}.{:018}", int_part, frac_part)
+    let frac_part = (value % tens).to_string();
+
+    format!("{int_part}.{frac_part:0>width$}", width = decimals as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_with_decimals() {
+        assert_eq!(format_with_decimals(U256::from(1_500_000), 6), "1.500000");
+        assert_eq!(format_with_decimals(U256::from(42), 6), "0.000042");
+        assert_eq!(
+            format_with_decimals(U256::from(10).pow(U256::from(18)), 18),
+            "1.000000000000000000"
+        );
+        assert_eq!(format_with_decimals(U256::from(7), 0), "7");
+    }
 }
@@ -0,0 +1,20 @@
+use super::*;
+
+/// Merge the per-protocol pool lists into the file the solver reads from `POOL_PATH`
+pub fn combine(ctx: &mut Context) -> Result<()> {
+    let pools = PoolAddress {
+        v2: ctx.read(POOLS_V2)?,
+        v3: ctx.read(POOLS_V3)?,
+        curve: ctx.read(CURVE_POOLS)?,
+    };
+    ctx.write(POOLS_COMBINED, &pools)?;
+
+    log::info!(
+        "Combined {} v2, {} v3 and {} curve pools",
+        pools.v2.len(),
+        pools.v3.len(),
+        pools.curve.len()
+    );
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use super::*;
+
+pub const POOLS_V2: &str = "pools_v2.json";
+pub const POOLS_V3: &str = "pools_v3.json";
+pub const TOKENS_TO_POOL_V2: &str = "uniswapv2_tokens_to_pool.json";
+pub const TOKENS_TO_POOL_V3: &str = "uniswapv3_tokens_to_pool.json";
+pub const CURVE_POOLS: &str = "curve_pools.json";
+pub const CURVE_TOKENS_TO_POOL: &str = "curve_tokens_to_pool.json";
+pub const CURVE_TOKENS: &str = "curve_tokens.json";
+pub const CURVE_FIXTURES: &str = "curve_fixtures.json";
+pub const TOKEN_METADATA: &str = "token_metadata_combined.json";
+pub const TICKS: &str = "ticks.json";
+pub const TICK_REPLAY: &str = "tick_replay.json";
+pub const SKIPPED: &str = "skipped.json";
+pub const POOLS_COMBINED: &str = "pools_combined.json";
+pub const DISCOVERY_CHECKPOINT: &str = "discovery_checkpoint.json";
+
+/// Resource directories, the RPC connection and the block shared by every step
+pub struct Context {
+    pub input: PathBuf,
+    pub output: PathBuf,
+    block: Option<u64>,
+    provider: Option<SolverProvider>,
+}
+
+impl Context {
+    pub fn new(input: PathBuf, output: PathBuf, block: Option<u64>) -> Self {
+        Self {
+            input,
+            output,
+            block,
+            provider: None,
+        }
+    }
+
+    /// Connect on first use, so offline steps never need `WEBSOCKET_ENDPOINT`
+    pub async fn provider(&mut self) -> Result<SolverProvider> {
+        if let Some(provider) = &self.provider {
+            return Ok(provider.clone());
+        }
+
+        dotenv().ok();
+        let ws = WsConnect::new(env::var("WEBSOCKET_ENDPOINT")?);
+        let provider = ProviderBuilder::new().connect_ws(ws).await?;
+        self.provider = Some(provider.clone());
+
+        Ok(provider)
+    }
+
+    /// The pinned block, resolved to the chain head once when none was given
+    pub async fn block(&mut self) -> Result<u64> {
+        if let Some(block) = self.block {
+            return Ok(block);
+        }
+
+        let block = self.provider().await?.get_block_number().await?;
+        log::info!("Pinned to block {block}");
+        self.block = Some(block);
+
+        Ok(block)
+    }
+
+    pub fn exists(&self, name: &str) -> bool {
+        self.input.join(name).exists()
+    }
+
+    pub fn read<T: DeserializeOwned>(&self, name: &str) -> Result<T> {
+        let file = File::open(self.input.join(name))?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+
+    /// Read a file that the step producing it may not have run for yet
+    pub fn read_or_default<T: DeserializeOwned + Default>(&self, name: &str) -> Result<T> {
+        if self.exists(name) {
+            self.read(name)
+        } else {
+            Ok(T::default())
+        }
+    }
+
+    /// Write through a temporary file, so a crash never leaves a truncated resource behind
+    pub fn write<T: Serialize>(&self, name: &str, value: &T) -> Result<()> {
+        let path = self.output.join(name);
+        let tmp = self.output.join(format!("{name}.tmp"));
+
+        let mut file = File::create(&tmp)?;
+        file.write_all(serde_json::to_string_pretty(value)?.as_bytes())?;
+        file.sync_all()?;
+        fs::rename(tmp, path)?;
+
+        Ok(())
+    }
+}
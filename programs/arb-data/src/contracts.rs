@@ -0,0 +1,8 @@
+use super::*;
+
+sol!(
+    #[sol(rpc)]
+    #[derive(Debug)]
+    CurveAddressProvider,
+    "../../resources/contracts/curve_address_provider.json"
+);
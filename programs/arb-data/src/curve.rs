@@ -0,0 +1,194 @@
+use super::*;
+
+const CURVE_ADDRESS_PROVIDER: Address = address!("0x0000000022D53366457F9d5E68Ec105046FC4383");
+
+// Ids the address provider hands out registries and factories under
+const MAX_REGISTRY_ID: u64 = 15;
+
+const MAX_CONCURRENT_POOLS: usize = 10;
+
+/// Plain pools of both families recorded for the parity tests
+const FIXTURE_POOLS: [Address; 3] = [
+    address!("0xbebc44782c7db0a1a60cb6fe97d0b483032ff1c7"), // 3pool
+    address!("0xdc24316b9ae028f1497c275eb9192a3ea0f67022"), // steth
+    address!("0x4dece678ceceb27446b35c672dc7d61f30bad69e"), // crvUSD/USDC
+];
+
+/// Lending pools scale their coins by `rates` the solver doesn't read
+const LENDING_POOLS: [Address; 3] = [
+    address!("0xa2b47e3d5c44877cca798226b7b8118f9bfb7a56"), // compound
+    address!("0x45f783cce6b7ff23b2ab2d70e416cdb7d6055f51"), // y
+    address!("0xdebf20617708857ebe4f679508e7b7863a8a8eee"), // aave
+];
+
+/// Metapools quote against a base pool's LP token, priced by the solver as a plain coin
+const METAPOOLS: [Address; 2] = [
+    address!("0xd632f22692fac7611d2aa1c0d552930d43caed3b"), // FRAX/3CRV
+    address!("0xed279fdd11ca84beef15af5d39bb4d4bee23f0ca"), // LUSD/3CRV
+];
+
+/// Every pool listed by the registries known to the address provider
+async fn registry_pools(provider: &SolverProvider, block: BlockId) -> Result<BTreeSet<Address>> {
+    let address_provider = CurveAddressProvider::new(CURVE_ADDRESS_PROVIDER, provider);
+    let mut pools = BTreeSet::new();
+
+    for id in 0..MAX_REGISTRY_ID {
+        let Ok(registry) = address_provider
+            .get_address(U256::from(id))
+            .block(block)
+            .call()
+            .await
+        else {
+            continue;
+        };
+        if registry.is_zero() {
+            continue;
+        }
+
+        let contract = CurveRegistry::new(registry, provider);
+        let Ok(count) = contract.pool_count().block(block).call().await else {
+            log::error!("registry: {registry} -> pool count");
+            continue;
+        };
+
+        for i in 0..count.to::<u64>() {
+            match contract.pool_list(U256::from(i)).block(block).call().await {
+                Ok(pool) if !pool.is_zero() => {
+                    pools.insert(pool);
+                }
+                Ok(_) => {}
+                Err(e) => log::error!("registry: {registry}, i: {i} -> pool list: {e}"),
+            }
+        }
+    }
+
+    Ok(pools)
+}
+
+/// Coins, A, A_precise and fee of a pool, trying both `coins` signatures Curve pools come with
+async fn pool_data(provider: SolverProvider, pool: Address, block: BlockId) -> CurvePools {
+    let contract = CurvePool::new(pool, provider.clone());
+    let contract_1 = CurvePool1::new(pool, provider.clone());
+
+    let a = contract.A().block(block).call().await.unwrap_or_else(|e| {
+        log::error!("pool: {pool}, A(): {e}");
+        U256::ZERO
+    });
+    let fee = contract
+        .fee()
+        .block(block)
+        .call()
+        .await
+        .unwrap_or_else(|e| {
+            log::error!("pool: {pool}, fee(): {e}");
+            U256::ZERO
+        });
+
+    // Only pools scaling their amplification by `A_PRECISION` have it
+    let a_precise = CurveMetaPool::new(pool, provider)
+        .A_precise()
+        .block(block)
+        .call()
+        .await
+        .ok();
+
+    let mut tokens = Vec::new();
+    loop {
+        let i = tokens.len();
+        if let Ok(token) = contract.coins(U256::from(i)).block(block).call().await {
+            tokens.push(token);
+        } else if let Ok(token) = contract_1.coins(i as i128).block(block).call().await {
+            tokens.push(token);
+        } else {
+            break;
+        }
+    }
+
+    CurvePools {
+        balances: vec![U256::ZERO; tokens.len()],
+        tokens,
+        fee,
+        a,
+        a_precise,
+        address: pool,
+    }
+}
+
+/// List the registry pools and fetch their coins, parameters and balances at the pinned block
+pub async fn curve(ctx: &mut Context) -> Result<()> {
+    let provider = ctx.provider().await?;
+    let block = BlockId::number(ctx.block().await?);
+
+    let pools = registry_pools(&provider, block).await?;
+    log::info!("{} curve pools found", pools.len());
+    ctx.write(CURVE_POOLS, &pools)?;
+
+    let mut curve_pools: Vec<CurvePools> = stream::iter(pools)
+        .map(|pool| pool_data(provider.clone(), pool, block))
+        .buffer_unordered(MAX_CONCURRENT_POOLS)
+        .collect()
+        .await;
+    curve_pools.sort_by_key(|pool| pool.address);
+
+    CurvePools::fetch_balances(&provider, &mut curve_pools, block).await;
+
+    let tokens: BTreeSet<Address> = curve_pools
+        .iter()
+        .flat_map(|pool| pool.tokens.iter().copied())
+        .collect();
+
+    ctx.write(CURVE_TOKENS_TO_POOL, &curve_pools)?;
+    ctx.write(CURVE_TOKENS, &tokens)
+}
+
+/// Record the fixture pools with their `get_dy` quotes at the pinned block
+pub async fn curve_fixtures(ctx: &mut Context) -> Result<()> {
+    let provider = ctx.provider().await?;
+    let block = ctx.block().await?;
+
+    let curve_pools: Vec<CurvePools> = ctx.read(CURVE_TOKENS_TO_POOL)?;
+    let token_metadata: Vec<TokenMetadata> = ctx.read(TOKEN_METADATA)?;
+    let decimals: HashMap<Address, u8> = token_metadata
+        .iter()
+        .map(|metadata| (metadata.address, metadata.decimals))
+        .collect();
+
+    // Recorded too, so their mismatches stay visible, but known not to match
+    let pools = FIXTURE_POOLS
+        .into_iter()
+        .map(|pool| (pool, None))
+        .chain(
+            LENDING_POOLS
+                .into_iter()
+                .map(|pool| (pool, Some("lending pool"))),
+        )
+        .chain(METAPOOLS.into_iter().map(|pool| (pool, Some("metapool"))));
+
+    let mut fixtures = Vec::new();
+    for (address, unsupported) in pools {
+        let Some(pool) = curve_pools.iter().find(|pool| pool.address == address) else {
+            log::warn!("pool: {address} -> not in {CURVE_TOKENS_TO_POOL}");
+            continue;
+        };
+        let Some(pool_decimals) = pool
+            .tokens
+            .iter()
+            .map(|token| decimals.get(token).copied())
+            .collect::<Option<Vec<u8>>>()
+        else {
+            log::warn!("pool: {address} -> missing token metadata");
+            continue;
+        };
+
+        match CurveFixture::record(&provider, pool, pool_decimals, block).await {
+            Ok(fixture) => fixtures.push(CurveFixture {
+                unsupported: unsupported.map(str::to_string),
+                ..fixture
+            }),
+            Err(e) => log::error!("pool: {address} -> {e}"),
+        }
+    }
+
+    log::info!("{} curve pools recorded", fixtures.len());
+    ctx.write(CURVE_FIXTURES, &fixtures)
+}
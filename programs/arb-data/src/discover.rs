@@ -0,0 +1,209 @@
+use super::*;
+
+// Outputs are rewritten whole, so they are flushed at most this often
+const FLUSH_INTERVAL: Duration = Duration::from_secs(30);
+
+/// No v3 pool has events before its factory was deployed
+pub const UNISWAP_V3_DEPLOYMENT_BLOCK: u64 = 12_369_621;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    V2,
+    V3,
+}
+
+struct Factory {
+    name: &'static str,
+    address: Address,
+    deployment_block: u64,
+    kind: Kind,
+}
+
+const FACTORIES: [Factory; 3] = [
+    Factory {
+        name: "UniswapV2",
+        address: UNISWAP_V2_FACTORY,
+        deployment_block: 10_000_835,
+        kind: Kind::V2,
+    },
+    Factory {
+        name: "SushiswapV2",
+        address: SUSHI_V2_FACTORY,
+        deployment_block: 10_794_229,
+        kind: Kind::V2,
+    },
+    Factory {
+        name: "UniswapV3",
+        address: UNISWAP_V3_FACTORY,
+        deployment_block: UNISWAP_V3_DEPLOYMENT_BLOCK,
+        kind: Kind::V3,
+    },
+];
+
+impl Kind {
+    fn signature(&self) -> B256 {
+        match self {
+            Kind::V2 => IUniswapV2Factory::PairCreated::SIGNATURE_HASH,
+            Kind::V3 => IUniswapV3Factory::PoolCreated::SIGNATURE_HASH,
+        }
+    }
+
+    fn decode(&self, log: &Log) -> Option<Pools> {
+        match self {
+            Kind::V2 => {
+                let event = IUniswapV2Factory::PairCreated::decode_log(&log.inner).ok()?;
+                Some(Pools {
+                    token0: event.token0,
+                    token1: event.token1,
+                    fee: 3000,
+                    address: event.pair,
+                })
+            }
+            Kind::V3 => {
+                let event = IUniswapV3Factory::PoolCreated::decode_log(&log.inner).ok()?;
+                Some(Pools {
+                    token0: event.token0,
+                    token1: event.token1,
+                    fee: event.fee.to(),
+                    address: event.pool,
+                })
+            }
+        }
+    }
+}
+
+/// Next block to scan per factory, only advanced together with a flush of the outputs
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Checkpoint {
+    next_block: HashMap<Address, u64>,
+}
+
+struct Discovery {
+    checkpoint: Checkpoint,
+    pools_v2: Vec<Pools>,
+    pools_v3: Vec<Pools>,
+    known: HashSet<Address>,
+    last_flush: Instant,
+}
+
+impl Discovery {
+    /// Resume from the checkpoint when there is one, otherwise start from every factory's deployment
+    fn load(ctx: &Context) -> Result<Self> {
+        let (checkpoint, pools_v2, pools_v3) = if ctx.exists(DISCOVERY_CHECKPOINT) {
+            (
+                ctx.read(DISCOVERY_CHECKPOINT)?,
+                ctx.read(TOKENS_TO_POOL_V2)?,
+                ctx.read(TOKENS_TO_POOL_V3)?,
+            )
+        } else {
+            Default::default()
+        };
+
+        let mut discovery = Self {
+            checkpoint,
+            pools_v2: Vec::new(),
+            pools_v3: Vec::new(),
+            known: HashSet::new(),
+            last_flush: Instant::now(),
+        };
+        // Pools written by a flush the checkpoint did not make it into are found again on resume
+        discovery.extend(Kind::V2, pools_v2);
+        discovery.extend(Kind::V3, pools_v3);
+
+        Ok(discovery)
+    }
+
+    /// Append the pools not seen yet, keeping the order they were created in
+    fn extend(&mut self, kind: Kind, pools: Vec<Pools>) {
+        let found: Vec<Pools> = pools
+            .into_iter()
+            .filter(|pool| self.known.insert(pool.address))
+            .collect();
+
+        match kind {
+            Kind::V2 => self.pools_v2.extend(found),
+            Kind::V3 => self.pools_v3.extend(found),
+        }
+    }
+
+    fn flush(&mut self, ctx: &Context) -> Result<()> {
+        for (pools, addresses_path, tokens_to_pool_path) in [
+            (&self.pools_v2, POOLS_V2, TOKENS_TO_POOL_V2),
+            (&self.pools_v3, POOLS_V3, TOKENS_TO_POOL_V3),
+        ] {
+            let addresses: Vec<Address> = pools.iter().map(|pool| pool.address).collect();
+            ctx.write(addresses_path, &addresses)?;
+            ctx.write(tokens_to_pool_path, pools)?;
+        }
+
+        // The checkpoint goes last so it never runs ahead of the pools on disk
+        ctx.write(DISCOVERY_CHECKPOINT, &self.checkpoint)?;
+        self.last_flush = Instant::now();
+
+        log::info!(
+            "Flushed {} v2 and {} v3 pools",
+            self.pools_v2.len(),
+            self.pools_v3.len()
+        );
+
+        Ok(())
+    }
+
+    /// Walk a factory's creation events from its checkpoint to `head`
+    async fn scan(
+        &mut self,
+        ctx: &Context,
+        provider: &SolverProvider,
+        factory: &Factory,
+        head: u64,
+    ) -> Result<()> {
+        let mut from = self
+            .checkpoint
+            .next_block
+            .get(&factory.address)
+            .copied()
+            .unwrap_or(factory.deployment_block);
+        let filter = Filter::new()
+            .address(factory.address)
+            .event_signature(factory.kind.signature());
+
+        Backfill::new(filter)
+            .run(provider, from, head, |logs, to| {
+                let found: Vec<Pools> = logs
+                    .iter()
+                    .filter_map(|log| factory.kind.decode(log))
+                    .collect();
+                log::info!(
+                    "{}: blocks {from}-{to}, {} pools",
+                    factory.name,
+                    found.len()
+                );
+
+                self.extend(factory.kind, found);
+                from = to + 1;
+                self.checkpoint.next_block.insert(factory.address, from);
+
+                if self.last_flush.elapsed() >= FLUSH_INTERVAL {
+                    self.flush(ctx)?;
+                }
+
+                Ok::<_, anyhow::Error>(())
+            })
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Scan every factory up to the pinned block, resuming from the last checkpoint
+pub async fn discover(ctx: &mut Context) -> Result<()> {
+    let provider = ctx.provider().await?;
+    let head = ctx.block().await?;
+    let mut discovery = Discovery::load(ctx)?;
+
+    for factory in &FACTORIES {
+        discovery.scan(ctx, &provider, factory, head).await?;
+    }
+
+    discovery.flush(ctx)
+}
@@ -0,0 +1,132 @@
+use crate::{
+    combine::*, context::*, contracts::*, curve::*, discover::*, metadata::*, ticks::*, validate::*,
+};
+use alloy::{
+    eips::BlockId,
+    primitives::{address, Address, B256, U256},
+    providers::{Provider, ProviderBuilder, WsConnect},
+    rpc::types::{Filter, Log},
+    sol,
+    sol_types::SolEvent,
+};
+use anyhow::Result;
+use clap::{Parser, Subcommand};
+use dotenv::dotenv;
+use futures::{stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use solver::{
+    constants::{SUSHI_V2_FACTORY, UNISWAP_V2_FACTORY, UNISWAP_V3_FACTORY},
+    contracts::{
+        CurveMetaPool, CurvePool, CurvePool1, CurveRegistry, IUniswapV2Factory, IUniswapV3Factory,
+        ERC20,
+    },
+    parser::PoolAddress,
+    pools::{curve::parity::CurveFixture, CurvePools, Pools, TokenMetadata},
+    snapshot::snapshot_ticks,
+    tick_replay::TickReplay,
+    validate::{Resources, Skipped, TickPool},
+    SolverProvider,
+};
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    env,
+    fs::{self, File},
+    io::{BufReader, Write},
+    path::PathBuf,
+    time::{Duration, Instant},
+};
+use utils::backfill::Backfill;
+
+mod combine;
+mod context;
+mod contracts;
+mod curve;
+mod discover;
+mod metadata;
+mod ticks;
+mod validate;
+
+/// Refresh the resource files the solver loads at startup
+#[derive(Parser)]
+#[command(name = "arb-data")]
+struct Args {
+    /// Directory resources are read from
+    #[arg(long, global = true, default_value = "resources")]
+    input: PathBuf,
+
+    /// Directory resources are written to
+    #[arg(long, global = true, default_value = "resources")]
+    output: PathBuf,
+
+    /// Block every RPC read is pinned to; defaults to the latest block
+    #[arg(long, global = true)]
+    block: Option<u64>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Clone, Copy)]
+enum Command {
+    /// Collect v2 and v3 pools from their factories' creation events
+    Discover,
+    /// Collect Curve pools from the registries, with their coins, parameters and balances
+    Curve,
+    /// Fetch ERC20 metadata for every pool token that has none yet
+    Metadata,
+    /// Snapshot price, liquidity and initialized ticks of every v3 pool
+    Ticks,
+    /// Rebuild the v3 tick snapshot from pool events alone, resuming from the last replay
+    ReplayTicks,
+    /// Record Curve pool state with on-chain `get_dy` quotes for the solver's parity tests
+    CurveFixtures,
+    /// Merge the pool lists into the single file the solver watches
+    Combine,
+    /// Cross-check the resource files against each other without an RPC
+    Validate,
+    /// Run every step above in dependency order
+    Pipeline,
+}
+
+impl Command {
+    async fn run(self, ctx: &mut Context) -> Result<()> {
+        match self {
+            Command::Discover => discover(ctx).await,
+            Command::Curve => curve(ctx).await,
+            Command::Metadata => metadata(ctx).await,
+            Command::Ticks => ticks(ctx).await,
+            Command::ReplayTicks => replay_ticks(ctx).await,
+            Command::CurveFixtures => curve_fixtures(ctx).await,
+            Command::Combine => combine(ctx),
+            Command::Validate => validate(ctx),
+            Command::Pipeline => {
+                for step in [
+                    Command::Discover,
+                    Command::Curve,
+                    Command::Metadata,
+                    Command::Ticks,
+                    Command::Combine,
+                    Command::Validate,
+                ] {
+                    Box::pin(step.run(ctx)).await?;
+                    // Later steps build on what the earlier ones wrote
+                    ctx.input = ctx.output.clone();
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    // Initialize the logger
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("info")).init();
+
+    let args = Args::parse();
+    fs::create_dir_all(&args.output)?;
+
+    let mut ctx = Context::new(args.input, args.output, args.block);
+    args.command.run(&mut ctx).await
+}
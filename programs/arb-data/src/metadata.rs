@@ -0,0 +1,87 @@
+use super::*;
+
+const MAX_CONCURRENT_TOKENS: usize = 10;
+
+/// Name, symbol and decimals of an ERC20 as of `block`, `None` if it has no decimals
+async fn fetch_metadata(
+    provider: SolverProvider,
+    address: Address,
+    block: BlockId,
+) -> Option<TokenMetadata> {
+    let contract = ERC20::new(address, provider);
+    let decimals = contract.decimals().block(block).call().await.ok()?;
+    // Some tokens return bytes32 names, which are kept blank rather than dropping the token
+    let symbol = contract
+        .symbol()
+        .block(block)
+        .call()
+        .await
+        .unwrap_or_default();
+    let name = contract
+        .name()
+        .block(block)
+        .call()
+        .await
+        .unwrap_or_default();
+
+    Some(TokenMetadata {
+        address,
+        name,
+        symbol,
+        decimals,
+    })
+}
+
+/// Fetch metadata for every v2, v3 and Curve pool token missing from the combined metadata
+pub async fn metadata(ctx: &mut Context) -> Result<()> {
+    let provider = ctx.provider().await?;
+    let block_number = ctx.block().await?;
+    let block = BlockId::number(block_number);
+
+    let mut token_metadata: Vec<TokenMetadata> = ctx.read_or_default(TOKEN_METADATA)?;
+    let known: BTreeSet<Address> = token_metadata.iter().map(|m| m.address).collect();
+
+    let pools_v2: Vec<Pools> = ctx.read(TOKENS_TO_POOL_V2)?;
+    let pools_v3: Vec<Pools> = ctx.read(TOKENS_TO_POOL_V3)?;
+    let curve_pools: Vec<CurvePools> = ctx.read(CURVE_TOKENS_TO_POOL)?;
+    let missing: BTreeSet<Address> = pools_v2
+        .iter()
+        .chain(&pools_v3)
+        .flat_map(|pool| [pool.token0, pool.token1])
+        .chain(curve_pools.iter().flat_map(|pool| pool.tokens.clone()))
+        .filter(|token| !known.contains(token))
+        .collect();
+
+    log::info!("Fetching metadata of {} tokens", missing.len());
+
+    let fetched: Vec<TokenMetadata> = stream::iter(missing.clone())
+        .map(|address| fetch_metadata(provider.clone(), address, block))
+        .buffer_unordered(MAX_CONCURRENT_TOKENS)
+        .filter_map(|metadata| async move { metadata })
+        .collect()
+        .await;
+
+    log::info!("{} tokens processed!", fetched.len());
+
+    // Recorded so validation knows these tokens are left out on purpose
+    let mut skipped: Skipped = ctx.read_or_default(SKIPPED)?;
+    let processed: BTreeSet<Address> = fetched.iter().map(|m| m.address).collect();
+    skipped.tokens = missing
+        .difference(&processed)
+        .map(|token| {
+            (
+                *token,
+                format!("decimals() unreadable at block {block_number}"),
+            )
+        })
+        .collect();
+    if !skipped.tokens.is_empty() {
+        log::warn!("{} tokens skipped without decimals", skipped.tokens.len());
+    }
+    ctx.write(SKIPPED, &skipped)?;
+
+    token_metadata.extend(fetched);
+    token_metadata.sort_by_key(|m| m.address);
+    token_metadata.dedup_by_key(|m| m.address);
+    ctx.write(TOKEN_METADATA, &token_metadata)
+}
@@ -0,0 +1,104 @@
+use super::*;
+
+// Pools sharing one `eth_getLogs` filter, the replay checkpoint is saved after each batch
+const REPLAY_BATCH_SIZE: usize = 200;
+
+/// Snapshot every v3 pool in batched multicalls pinned to one block
+pub async fn ticks(ctx: &mut Context) -> Result<()> {
+    let provider = ctx.provider().await?;
+    let block = ctx.block().await?;
+    let pools: Vec<Address> = ctx.read(POOLS_V3)?;
+
+    let started = Instant::now();
+    let mut snapshots = snapshot_ticks(&provider, &pools, block).await?;
+    snapshots.sort_by_key(|snapshot| snapshot.pool);
+
+    log::info!(
+        "{} of {} pools snapshotted at block {block} in {:?}",
+        snapshots.len(),
+        pools.len(),
+        started.elapsed()
+    );
+
+    // Recorded so validation knows these pools are left out on purpose
+    let mut skipped: Skipped = ctx.read_or_default(SKIPPED)?;
+    let snapshotted: HashSet<Address> = snapshots.iter().map(|snapshot| snapshot.pool).collect();
+    skipped.ticks = pools
+        .iter()
+        .filter(|pool| !snapshotted.contains(*pool))
+        .map(|pool| {
+            (
+                *pool,
+                format!("state or tick reads failed at block {block}"),
+            )
+        })
+        .collect();
+    ctx.write(SKIPPED, &skipped)?;
+
+    ctx.write(TICKS, &snapshots)
+}
+
+/// Replay every v3 pool's `Initialize`, `Swap`, `Mint` and `Burn` events up to the pinned block
+/// and write the result as the tick snapshot, without reading any pool state
+pub async fn replay_ticks(ctx: &mut Context) -> Result<()> {
+    let provider = ctx.provider().await?;
+    let block = ctx.block().await?;
+    let pools: Vec<Address> = ctx.read(POOLS_V3)?;
+
+    let mut checkpoints: HashMap<Address, TickReplay> = ctx
+        .read_or_default::<Vec<TickReplay>>(TICK_REPLAY)?
+        .into_iter()
+        .map(|replay| (replay.pool, replay))
+        .collect();
+    let mut replays: Vec<TickReplay> = pools
+        .iter()
+        .map(|pool| {
+            checkpoints
+                .remove(pool)
+                .unwrap_or_else(|| TickReplay::new(*pool, UNISWAP_V3_DEPLOYMENT_BLOCK))
+        })
+        .collect();
+    // Replays only move forward, so a snapshot can't be taken before a checkpoint
+    if let Some(replay) = replays.iter().find(|replay| replay.next_block > block + 1) {
+        anyhow::bail!(
+            "{TICK_REPLAY} has {} replayed past block {block}, pin a later block or remove it",
+            replay.pool
+        );
+    }
+
+    let started = Instant::now();
+    for start in (0..replays.len()).step_by(REPLAY_BATCH_SIZE) {
+        let end = (start + REPLAY_BATCH_SIZE).min(replays.len());
+        TickReplay::sync_all(&provider, &mut replays[start..end], block).await?;
+        ctx.write(TICK_REPLAY, &replays)?;
+
+        log::info!("{end} of {} pools replayed", replays.len());
+    }
+
+    // Pools never initialized have no price to snapshot
+    let mut skipped: Skipped = ctx.read_or_default(SKIPPED)?;
+    skipped.ticks.clear();
+    let mut snapshots = Vec::new();
+    for replay in &replays {
+        match replay.snapshot() {
+            Some(snapshot) => snapshots.push(snapshot),
+            None => {
+                skipped
+                    .ticks
+                    .insert(replay.pool, format!("not initialized by block {block}"));
+            }
+        }
+    }
+    snapshots.sort_by_key(|snapshot| snapshot.pool);
+
+    log::info!(
+        "{} of {} pools replayed to block {block} in {:?}",
+        snapshots.len(),
+        pools.len(),
+        started.elapsed()
+    );
+
+    ctx.write(SKIPPED, &skipped)?;
+
+    ctx.write(TICKS, &snapshots)
+}
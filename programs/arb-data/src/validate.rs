@@ -0,0 +1,37 @@
+use super::*;
+
+/// Report every inconsistency between the resource files, failing on any a step didn't record skipping
+pub fn validate(ctx: &mut Context) -> Result<()> {
+    let resources = Resources {
+        pools_v2: ctx.read(POOLS_V2)?,
+        pools_v3: ctx.read(POOLS_V3)?,
+        tokens_to_pool_v2: ctx.read(TOKENS_TO_POOL_V2)?,
+        tokens_to_pool_v3: ctx.read(TOKENS_TO_POOL_V3)?,
+        token_metadata: ctx.read(TOKEN_METADATA)?,
+        curve_pools: ctx.read(CURVE_TOKENS_TO_POOL)?,
+        ticks: ctx.read::<Vec<TickPool>>(TICKS)?,
+        skipped: ctx.read_or_default(SKIPPED)?,
+    };
+
+    let (skipped, issues): (Vec<_>, Vec<_>) = resources
+        .validate()
+        .into_iter()
+        .partition(|issue| issue.skipped.is_some());
+    for issue in &skipped {
+        log::warn!("{issue}");
+    }
+    for issue in &issues {
+        println!("{issue}");
+    }
+
+    if !issues.is_empty() {
+        anyhow::bail!("{} inconsistencies found", issues.len());
+    }
+    if !skipped.is_empty() {
+        log::warn!("{} entries missing as recorded in {SKIPPED}", skipped.len());
+    }
+
+    log::info!("Resources are consistent");
+
+    Ok(())
+}
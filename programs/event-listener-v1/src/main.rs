@@ -1,71 +0,0 @@
-use crate::{enums::*, helper::*, process::*, structs::*, util::*};
-use colored::Colorize;
-use futures::future::join_all;
-use futures::StreamExt;
-use std::{collections::HashMap, fmt::Display, sync::Arc};
-use utils::{CustomError, EnvParser};
-use web3::{
-    contract::Contract,
-    ethabi::{Address, Event, Log, RawLog},
-    transports::WebSocket,
-    types::{H160, H256},
-    Web3,
-};
-
-mod enums;
-mod helper;
-mod process;
-mod structs;
-mod util;
-
-#[tokio::main]
-async fn main() -> Result<(), anyhow::Error> {
-    let env_parser = EnvParser::new()?;
-
-    // Shared WebSocket connection
-    let web3 = Arc::new(web3::Web3::new(
-        web3::transports::ws::WebSocket::new(&env_parser.ws_address).await?,
-    ));
-
-    // Shared block stream
-    let mut block_stream = web3.eth_subscribe().subscribe_new_heads().await?;
-
-    while let Some(Ok(block)) = block_stream.next().await {
-        let block_number = block.number.ok_or(CustomError::NotFound("block number"))?;
-        let block_hash = block.hash.ok_or(CustomError::NotFound("block hash"))?;
-
-        println!("{}", format!("current block: {}", block_number).blue());
-
-        // Spawn a task for each contract address
-        let mut tasks = vec![];
-        for address in &env_parser.pools {
-            let web3 = web3.clone();
-            let address = *address;
-
-            let task = tokio::spawn(async move {
-                let contract = Contract::from_json(
-                    web3.eth(),
-                    address,
-                    include_bytes!("contracts/uniswap_pool_abi.json"),
-                )?;
-
-                let (events, signatures) =
-                    get_events(&contract, &["Swap", "Sync", "Mint", "Burn"])?;
-
-                scan(web3.clone(), address, &events, signatures, block_hash).await
-            });
-
-            tasks.push(task);
-        }
-
-        // Wait for all tasks to complete
-        let results = join_all(tasks).await;
-        for res in results {
-            if let Err(e) = res {
-                eprintln!("{}", format!("Error in contract task: {:?}", e).red());
-            }
-        }
-    }
-
-    Ok(())
-}
@@ -0,0 +1,229 @@
+use super::*;
+
+#[derive(Debug, Serialize)]
+pub struct Arg {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub value: String,
+}
+
+impl Arg {
+    pub fn new(idx: usize, name: &str, ty: String, value: &DynSolValue) -> Self {
+        Self {
+            name: match name {
+                "" => format!("arg{idx}"),
+                name => name.to_string(),
+            },
+            ty,
+            value: format_value(value),
+        }
+    }
+}
+
+/// A decoded call, with the calls nested in its multicall or execute payload
+#[derive(Debug, Serialize)]
+pub struct Call {
+    pub contract: String,
+    pub function: String,
+    pub selector: String,
+    pub args: Vec<Arg>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<Call>,
+}
+
+impl Call {
+    /// Decode calldata against every function with a matching selector
+    pub fn decode(registry: &AbiRegistry, input: &[u8]) -> Result<Self> {
+        let selector = Selector::try_from(input.get(..4).ok_or(anyhow!("calldata too short"))?)?;
+
+        for (contract, function) in registry.get(&selector) {
+            let Ok(values) = function.abi_decode_input(&input[4..]) else {
+                continue;
+            };
+
+            let args = function
+                .inputs
+                .iter()
+                .zip(&values)
+                .enumerate()
+                .map(|(idx, (param, value))| {
+                    Arg::new(idx, &param.name, param.selector_type().into(), value)
+                })
+                .collect();
+
+            return Ok(Self {
+                contract: contract.clone(),
+                function: function.signature(),
+                selector: selector.to_string(),
+                args,
+                calls: nested_calls(registry, function, &values),
+            });
+        }
+
+        bail!("no loaded ABI decodes selector {selector}")
+    }
+
+    /// Payload nothing decodes, kept as raw bytes
+    pub fn unknown(contract: &str, selector: String, data: &[u8]) -> Self {
+        Self {
+            contract: contract.to_string(),
+            function: "unknown".to_string(),
+            selector,
+            args: vec![Arg::new(
+                0,
+                "data",
+                "bytes".to_string(),
+                &DynSolValue::Bytes(data.to_vec()),
+            )],
+            calls: vec![],
+        }
+    }
+
+    fn write_tree(&self, f: &mut std::fmt::Formatter<'_>, depth: usize) -> std::fmt::Result {
+        let indent = "  ".repeat(depth);
+        writeln!(
+            f,
+            "{indent}{}::{} [{}]",
+            self.contract, self.function, self.selector
+        )?;
+
+        for arg in &self.args {
+            // The payloads are printed decoded below instead
+            let value = match self.calls.is_empty() || arg.ty != "bytes[]" {
+                true => arg.value.as_str(),
+                false => "<nested calls>",
+            };
+            writeln!(f, "{indent}  {}: {} = {value}", arg.name, arg.ty)?;
+        }
+
+        for call in &self.calls {
+            call.write_tree(f, depth + 2)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Display for Call {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.write_tree(f, 0)
+    }
+}
+
+/// Calls batched by `multicall(.., bytes[])` and commands of `execute(bytes, bytes[], ..)`
+fn nested_calls(registry: &AbiRegistry, function: &Function, values: &[DynSolValue]) -> Vec<Call> {
+    match (function.name.as_str(), values) {
+        ("execute", [DynSolValue::Bytes(commands), DynSolValue::Array(inputs), ..]) => {
+            decode_commands(commands, inputs)
+        }
+        ("multicall", [.., DynSolValue::Array(calls)]) => calls
+            .iter()
+            .filter_map(DynSolValue::as_bytes)
+            .map(|data| {
+                Call::decode(registry, data).unwrap_or_else(|_| {
+                    let selector = data.get(..4).map(hex::encode_prefixed).unwrap_or_default();
+                    Call::unknown("unknown", selector, data)
+                })
+            })
+            .collect(),
+        _ => vec![],
+    }
+}
+
+pub fn format_value(value: &DynSolValue) -> String {
+    let join = |values: &[DynSolValue]| {
+        values
+            .iter()
+            .map(format_value)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match value {
+        DynSolValue::Address(address) => address.to_checksum(None),
+        DynSolValue::Bool(value) => value.to_string(),
+        DynSolValue::Int(value, _) => value.to_string(),
+        DynSolValue::Uint(value, _) => value.to_string(),
+        DynSolValue::FixedBytes(word, size) => hex::encode_prefixed(&word[..*size]),
+        DynSolValue::Bytes(bytes) => hex::encode_prefixed(bytes),
+        DynSolValue::Function(function) => hex::encode_prefixed(function),
+        DynSolValue::String(value) => format!("{value:?}"),
+        DynSolValue::Array(values) | DynSolValue::FixedArray(values) => {
+            format!("[{}]", join(values))
+        }
+        DynSolValue::Tuple(values) => format!("({})", join(values)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        primitives::{Address, Bytes, U256},
+        sol_types::{SolCall, SolValue},
+    };
+    use decoder::{ISwapRouter02, IUniversalRouter};
+
+    #[test]
+    fn test_decode_multicall() {
+        let swap = ISwapRouter02::exactInputSingleCall {
+            params: ISwapRouter02::ExactInputSingleParams {
+                tokenIn: Address::repeat_byte(1),
+                tokenOut: Address::repeat_byte(2),
+                fee: 500.try_into().unwrap(),
+                recipient: Address::repeat_byte(3),
+                amountIn: U256::from(7),
+                amountOutMinimum: U256::from(6),
+                sqrtPriceLimitX96: Default::default(),
+            },
+        };
+        let input = ISwapRouter02::multicall_0Call {
+            deadline: U256::from(100),
+            data: vec![swap.abi_encode().into(), Bytes::from_static(&[0xde, 0xad])],
+        }
+        .abi_encode();
+
+        let call = Call::decode(&AbiRegistry::builtin(), &input).unwrap();
+        assert_eq!(call.function, "multicall(uint256,bytes[])");
+        assert_eq!(call.calls.len(), 2);
+        assert_eq!(
+            call.calls[0].function,
+            "exactInputSingle((address,address,uint24,address,uint256,uint256,uint160))"
+        );
+        assert_eq!(call.calls[0].args[0].name, "params");
+        assert_eq!(call.calls[1].function, "unknown");
+        assert_eq!(call.calls[1].args[0].value, "0xdead");
+
+        assert!(Call::decode(&AbiRegistry::builtin(), &[0; 4]).is_err());
+    }
+
+    #[test]
+    fn test_decode_execute() {
+        let path = vec![Address::repeat_byte(1), Address::repeat_byte(2)];
+        let v2_swap = (
+            Address::repeat_byte(3),
+            U256::from(7),
+            U256::from(6),
+            path,
+            true,
+        );
+        let sub_plan = (
+            Bytes::from_static(&[0x08]),
+            vec![Bytes::from(v2_swap.abi_encode_params())],
+        );
+        let input = IUniversalRouter::execute_1Call {
+            commands: Bytes::from_static(&[0xa1, 0x3f]),
+            inputs: vec![sub_plan.abi_encode_params().into(), Bytes::new()],
+        }
+        .abi_encode();
+
+        let call = Call::decode(&AbiRegistry::builtin(), &input).unwrap();
+        assert_eq!(call.calls.len(), 2);
+        assert_eq!(call.calls[0].function, "EXECUTE_SUB_PLAN (allow revert)");
+        assert_eq!(call.calls[0].calls[0].function, "V2_SWAP_EXACT_IN");
+        assert_eq!(call.calls[0].calls[0].args[1].value, "7");
+        assert_eq!(call.calls[1].function, "unknown");
+        assert_eq!(call.calls[1].selector, "0x3f");
+    }
+}
@@ -0,0 +1,77 @@
+use super::*;
+
+/// The top bits flag whether the command may revert
+const COMMAND_TYPE_MASK: u8 = 0x3f;
+const ALLOW_REVERT: u8 = 0x80;
+
+/// Name and type of every param of a command input
+type Params = &'static [(&'static str, &'static str)];
+
+/// Universal Router commands and the params of their input, see `Dispatcher.sol`
+#[rustfmt::skip]
+const COMMANDS: &[(u8, &str, Params)] = &[
+    (0x00, "V3_SWAP_EXACT_IN", &[("recipient", "address"), ("amountIn", "uint256"), ("amountOutMin", "uint256"), ("path", "bytes"), ("payerIsUser", "bool")]),
+    (0x01, "V3_SWAP_EXACT_OUT", &[("recipient", "address"), ("amountOut", "uint256"), ("amountInMax", "uint256"), ("path", "bytes"), ("payerIsUser", "bool")]),
+    (0x02, "PERMIT2_TRANSFER_FROM", &[("token", "address"), ("recipient", "address"), ("amount", "uint160")]),
+    (0x04, "SWEEP", &[("token", "address"), ("recipient", "address"), ("amountMin", "uint256")]),
+    (0x05, "TRANSFER", &[("token", "address"), ("recipient", "address"), ("value", "uint256")]),
+    (0x06, "PAY_PORTION", &[("token", "address"), ("recipient", "address"), ("bips", "uint256")]),
+    (0x08, "V2_SWAP_EXACT_IN", &[("recipient", "address"), ("amountIn", "uint256"), ("amountOutMin", "uint256"), ("path", "address[]"), ("payerIsUser", "bool")]),
+    (0x09, "V2_SWAP_EXACT_OUT", &[("recipient", "address"), ("amountOut", "uint256"), ("amountInMax", "uint256"), ("path", "address[]"), ("payerIsUser", "bool")]),
+    (0x0a, "PERMIT2_PERMIT", &[("permitSingle", "((address,uint160,uint48,uint48),address,uint256)"), ("signature", "bytes")]),
+    (0x0b, "WRAP_ETH", &[("recipient", "address"), ("amount", "uint256")]),
+    (0x0c, "UNWRAP_WETH", &[("recipient", "address"), ("amountMin", "uint256")]),
+    (0x0d, "PERMIT2_TRANSFER_FROM_BATCH", &[("batchDetails", "(address,address,uint160,address)[]")]),
+    (0x0e, "BALANCE_CHECK_ERC20", &[("owner", "address"), ("token", "address"), ("minBalance", "uint256")]),
+    (0x21, "EXECUTE_SUB_PLAN", &[("commands", "bytes"), ("inputs", "bytes[]")]),
+];
+
+/// Decode Universal Router commands, recursing into sub plans
+pub fn decode_commands(commands: &[u8], inputs: &[DynSolValue]) -> Vec<Call> {
+    commands
+        .iter()
+        .zip(inputs.iter().filter_map(DynSolValue::as_bytes))
+        .map(|(&command, input)| decode_command(command, input))
+        .collect()
+}
+
+fn decode_command(command: u8, input: &[u8]) -> Call {
+    let selector = hex::encode_prefixed([command]);
+    let known = COMMANDS
+        .iter()
+        .find(|(byte, ..)| *byte == command & COMMAND_TYPE_MASK);
+    let Some((_, name, params)) = known else {
+        return Call::unknown("UniversalRouter", selector, input);
+    };
+
+    let types = params
+        .iter()
+        .map(|(_, ty)| DynSolType::parse(ty).expect("command types are valid"))
+        .collect();
+    let Ok(DynSolValue::Tuple(values)) = DynSolType::Tuple(types).abi_decode_params(input) else {
+        return Call::unknown("UniversalRouter", selector, input);
+    };
+
+    let calls = match (*name, values.as_slice()) {
+        ("EXECUTE_SUB_PLAN", [DynSolValue::Bytes(commands), DynSolValue::Array(inputs)]) => {
+            decode_commands(commands, inputs)
+        }
+        _ => vec![],
+    };
+
+    Call {
+        contract: "UniversalRouter".to_string(),
+        function: match command & ALLOW_REVERT {
+            0 => name.to_string(),
+            _ => format!("{name} (allow revert)"),
+        },
+        selector,
+        args: params
+            .iter()
+            .zip(&values)
+            .enumerate()
+            .map(|(idx, ((name, ty), value))| Arg::new(idx, name, ty.to_string(), value))
+            .collect(),
+        calls,
+    }
+}
@@ -0,0 +1,66 @@
+use super::*;
+use decoder::{ISwapRouter, ISwapRouter02, IUniswapV2Router02, IUniversalRouter};
+
+/// Every function of the loaded ABIs, keyed by selector
+#[derive(Default)]
+pub struct AbiRegistry {
+    functions: HashMap<Selector, Vec<(String, Function)>>,
+}
+
+impl AbiRegistry {
+    /// The router interfaces the decoder crate knows about
+    pub fn builtin() -> Self {
+        let mut registry = Self::default();
+        registry.insert("IUniswapV2Router02", &IUniswapV2Router02::abi::contract());
+        registry.insert("ISwapRouter", &ISwapRouter::abi::contract());
+        registry.insert("ISwapRouter02", &ISwapRouter02::abi::contract());
+        registry.insert("IUniversalRouter", &IUniversalRouter::abi::contract());
+        registry
+    }
+
+    /// The built-in routers plus every `*.json` ABI in `dir`; files that are not ABIs are skipped
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut registry = Self::builtin();
+
+        let mut paths: Vec<PathBuf> = fs::read_dir(dir)?
+            .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+            .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+            .collect();
+        paths.sort();
+
+        for path in paths {
+            let reader = BufReader::new(File::open(&path)?);
+            match serde_json::from_reader::<_, JsonAbi>(reader) {
+                Ok(abi) => {
+                    let name = path.file_stem().unwrap_or_default().to_string_lossy();
+                    registry.insert(&name, &abi);
+                }
+                Err(e) => eprintln!("Skipping {}: {e}", path.display()),
+            }
+        }
+
+        Ok(registry)
+    }
+
+    fn insert(&mut self, contract: &str, abi: &JsonAbi) {
+        for function in abi.functions() {
+            let candidates = self.functions.entry(function.selector()).or_default();
+
+            // Shared functions such as ERC20 ones appear in many ABIs, the first one wins
+            if !candidates
+                .iter()
+                .any(|(_, known)| known.signature() == function.signature())
+            {
+                candidates.push((contract.to_string(), function.clone()));
+            }
+        }
+    }
+
+    /// Functions with this selector, more than one only on a selector collision
+    pub fn get(&self, selector: &Selector) -> &[(String, Function)] {
+        self.functions
+            .get(selector)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
@@ -0,0 +1,77 @@
+use super::*;
+use alloy::{
+    consensus::{transaction::SignerRecoverable, Transaction, TxEnvelope},
+    eips::Decodable2718,
+    primitives::{Address, TxHash, U256},
+};
+
+#[derive(Debug, Serialize)]
+pub struct TxSummary {
+    pub hash: TxHash,
+    pub from: Address,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub nonce: u64,
+    pub chain_id: Option<u64>,
+}
+
+impl TxSummary {
+    /// Decode an EIP-2718 signed transaction into its summary and calldata
+    pub fn decode(raw: &[u8]) -> Result<(Self, Vec<u8>)> {
+        let envelope = TxEnvelope::decode_2718(&mut &raw[..])?;
+
+        let summary = Self {
+            hash: *envelope.tx_hash(),
+            from: envelope.recover_signer()?,
+            to: envelope.to(),
+            value: envelope.value(),
+            nonce: envelope.nonce(),
+            chain_id: envelope.chain_id(),
+        };
+
+        Ok((summary, envelope.input().to_vec()))
+    }
+}
+
+impl Display for TxSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "hash: {}", self.hash)?;
+        writeln!(f, "from: {}", self.from)?;
+        writeln!(f, "to: {:?}", self.to)?;
+        writeln!(f, "value: {}", self.value)?;
+        writeln!(f, "nonce: {}", self.nonce)?;
+        write!(f, "chain_id: {:?}", self.chain_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{
+        consensus::{SignableTransaction, TxEip1559},
+        eips::Encodable2718,
+        primitives::{Bytes, TxKind},
+        signers::{local::PrivateKeySigner, SignerSync},
+    };
+
+    #[test]
+    fn test_decode_raw_tx() {
+        let signer = PrivateKeySigner::random();
+        let tx = TxEip1559 {
+            chain_id: 1,
+            nonce: 3,
+            to: TxKind::Call(Address::repeat_byte(1)),
+            value: U256::from(5),
+            input: Bytes::from_static(&[1, 2, 3, 4]),
+            ..Default::default()
+        };
+        let signature = signer.sign_hash_sync(&tx.signature_hash()).unwrap();
+        let raw = TxEnvelope::from(tx.into_signed(signature)).encoded_2718();
+
+        let (summary, input) = TxSummary::decode(&raw).unwrap();
+        assert_eq!(summary.from, signer.address());
+        assert_eq!(summary.to, Some(Address::repeat_byte(1)));
+        assert_eq!(summary.nonce, 3);
+        assert_eq!(input, vec![1, 2, 3, 4]);
+    }
+}
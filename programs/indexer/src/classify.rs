@@ -0,0 +1,489 @@
+use super::*;
+
+/// Net amount of one token an actor gained, negative for a loss
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Profit {
+    pub token: Address,
+    pub amount: I256,
+}
+
+impl Profit {
+    fn new(token: Address, gained: U256, spent: U256) -> Self {
+        Self {
+            token,
+            amount: I256::from_raw(gained) - I256::from_raw(spent),
+        }
+    }
+}
+
+impl Display for Profit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount, self.token)
+    }
+}
+
+/// A trade that ends in the token it started with, for more than it spent
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Arbitrage {
+    pub block_number: u64,
+    pub tx_hash: TxHash,
+    pub pools: Vec<Address>,
+    pub profit: Profit,
+}
+
+/// Swaps of one actor around swaps of others in the same direction, in one pool and block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Sandwich {
+    pub block_number: u64,
+    pub pool: Address,
+    /// Account that signed both legs
+    pub attacker: Address,
+    pub front_run: TxHash,
+    pub victims: Vec<TxHash>,
+    pub back_run: TxHash,
+    /// Net of both legs, in the token the front-run sold and the one it bought
+    pub profit: Vec<Profit>,
+}
+
+/// Liquidity minted right before swaps of others and burned right after, in one v3 pool
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct JitLiquidity {
+    pub block_number: u64,
+    pub pool: Address,
+    /// Position owner, or the signer for positions held through the position manager
+    pub provider: Address,
+    pub mint: TxHash,
+    pub victims: Vec<TxHash>,
+    pub burn: TxHash,
+    /// Burned minus minted amounts; fees are collected separately and not included
+    pub profit: Vec<Profit>,
+}
+
+pub fn arbitrages(trades: &[Trade]) -> Vec<Arbitrage> {
+    trades
+        .iter()
+        .filter(|trade| trade.token_in == trade.token_out && trade.amount_out > trade.amount_in)
+        .map(|trade| Arbitrage {
+            block_number: trade.block_number,
+            tx_hash: trade.tx_hash,
+            pools: trade.hops.iter().map(|hop| hop.pool).collect(),
+            profit: Profit::new(trade.token_in, trade.amount_out, trade.amount_in),
+        })
+        .collect()
+}
+
+/// Events of every pool in every block, in log order
+fn by_pool(events: &[PoolEvent]) -> BTreeMap<(u64, Address), Vec<&PoolEvent>> {
+    let mut pools: BTreeMap<(u64, Address), Vec<&PoolEvent>> = BTreeMap::new();
+    for event in events {
+        pools
+            .entry((event.block_number, event.pool))
+            .or_default()
+            .push(event);
+    }
+    pools
+        .values_mut()
+        .for_each(|events| events.sort_by_key(|event| event.log_index));
+    pools
+}
+
+/// Distinct transactions of `events`, other than the attacker's own
+fn victims<'a>(events: impl Iterator<Item = &'a PoolEvent>, own: &[TxHash]) -> Vec<TxHash> {
+    let mut victims: Vec<TxHash> = vec![];
+    for event in events {
+        if !own.contains(&event.tx_hash) && !victims.contains(&event.tx_hash) {
+            victims.push(event.tx_hash);
+        }
+    }
+    victims
+}
+
+pub fn sandwiches(events: &[PoolEvent], pool_tokens: &PoolTokens) -> Vec<Sandwich> {
+    let mut sandwiches = vec![];
+
+    for ((block_number, pool), events) in by_pool(events) {
+        let swaps: Vec<(&PoolEvent, Hop)> = events
+            .into_iter()
+            .filter_map(|event| Some((event, Hop::new(event, pool_tokens, None)?)))
+            .collect();
+        let mut back_runs: Vec<TxHash> = vec![];
+
+        for (idx, (front, front_hop)) in swaps.iter().enumerate() {
+            // Routers send and receive for everyone, only the signer ties both legs to one attacker
+            let Some(attacker) = front.tx_from else {
+                continue;
+            };
+            if back_runs.contains(&front.tx_hash) {
+                continue;
+            }
+
+            // The attacker's next swap in the other direction closes the position
+            let back = swaps[idx + 1..].iter().position(|(event, hop)| {
+                event.tx_hash != front.tx_hash
+                    && hop.token_in == front_hop.token_out
+                    && event.tx_from == Some(attacker)
+            });
+            let Some(back_idx) = back.map(|offset| idx + 1 + offset) else {
+                continue;
+            };
+            let (back, back_hop) = &swaps[back_idx];
+
+            let victims = victims(
+                swaps[idx + 1..back_idx]
+                    .iter()
+                    .filter(|(event, hop)| {
+                        hop.token_in == front_hop.token_in && event.tx_from != Some(attacker)
+                    })
+                    .map(|(event, _)| *event),
+                &[front.tx_hash, back.tx_hash],
+            );
+            if victims.is_empty() {
+                continue;
+            }
+
+            back_runs.push(back.tx_hash);
+            sandwiches.push(Sandwich {
+                block_number,
+                pool,
+                attacker,
+                front_run: front.tx_hash,
+                victims,
+                back_run: back.tx_hash,
+                profit: vec![
+                    Profit::new(front_hop.token_in, back_hop.amount_out, front_hop.amount_in),
+                    Profit::new(
+                        front_hop.token_out,
+                        front_hop.amount_out,
+                        back_hop.amount_in,
+                    ),
+                ],
+            });
+        }
+    }
+
+    sandwiches
+}
+
+pub fn jit_liquidity(events: &[PoolEvent], pool_tokens: &PoolTokens) -> Vec<JitLiquidity> {
+    let mut jit = vec![];
+
+    for ((block_number, pool), events) in by_pool(events) {
+        let Some(tokens) = pool_tokens.get(&pool) else {
+            continue;
+        };
+
+        for (idx, mint) in events.iter().enumerate() {
+            if (mint.protocol, mint.kind) != (Protocol::V3, EventKind::Mint) {
+                continue;
+            }
+            let (Some(position), Some((minted0, minted1))) = (&mint.position, mint.amounts) else {
+                continue;
+            };
+            // Every position minted through the manager is owned by it, the signer tells them apart
+            let managed = position.owner == NONFUNGIBLE_POSITION_MANAGER;
+            let provider = match (managed, mint.tx_from) {
+                (false, _) => position.owner,
+                (true, Some(tx_from)) => tx_from,
+                (true, None) => continue,
+            };
+
+            // The same position is burned: same owner, range and liquidity
+            let burn = events[idx + 1..].iter().position(|event| {
+                event.kind == EventKind::Burn
+                    && event.position.as_ref() == Some(position)
+                    && (!managed || event.tx_from == Some(provider))
+            });
+            let Some(burn_idx) = burn.map(|offset| idx + 1 + offset) else {
+                continue;
+            };
+            let burn = events[burn_idx];
+            let Some((burned0, burned1)) = burn.amounts else {
+                continue;
+            };
+
+            let victims = victims(
+                events[idx + 1..burn_idx]
+                    .iter()
+                    .filter(|event| event.kind == EventKind::Swap)
+                    .copied(),
+                &[mint.tx_hash, burn.tx_hash],
+            );
+            if victims.is_empty() {
+                continue;
+            }
+
+            jit.push(JitLiquidity {
+                block_number,
+                pool,
+                provider,
+                mint: mint.tx_hash,
+                victims,
+                burn: burn.tx_hash,
+                profit: vec![
+                    Profit::new(tokens[0], burned0, minted0),
+                    Profit::new(tokens[1], burned1, minted1),
+                ],
+            });
+        }
+    }
+
+    jit
+}
+
+/// Everything classified in a block range
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub from_block: u64,
+    pub to_block: u64,
+    pub arbitrages: Vec<Arbitrage>,
+    pub sandwiches: Vec<Sandwich>,
+    pub jit_liquidity: Vec<JitLiquidity>,
+}
+
+impl Report {
+    pub fn new(
+        from_block: u64,
+        to_block: u64,
+        events: &[PoolEvent],
+        pool_tokens: &PoolTokens,
+    ) -> Self {
+        Self {
+            from_block,
+            to_block,
+            arbitrages: arbitrages(&group_trades(events, pool_tokens)),
+            sandwiches: sandwiches(events, pool_tokens),
+            jit_liquidity: jit_liquidity(events, pool_tokens),
+        }
+    }
+}
+
+impl Display for Report {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "blocks {}-{}: {} arbitrages, {} sandwiches, {} jit liquidity",
+            self.from_block,
+            self.to_block,
+            self.arbitrages.len(),
+            self.sandwiches.len(),
+            self.jit_liquidity.len()
+        )?;
+
+        for arb in &self.arbitrages {
+            write!(
+                f,
+                "\narbitrage {} {}: {} via {:?}",
+                arb.block_number, arb.tx_hash, arb.profit, arb.pools
+            )?;
+        }
+        for sandwich in &self.sandwiches {
+            write!(
+                f,
+                "\nsandwich {} {} by {}: {} -> {:?} -> {}, profit {}, {}",
+                sandwich.block_number,
+                sandwich.pool,
+                sandwich.attacker,
+                sandwich.front_run,
+                sandwich.victims,
+                sandwich.back_run,
+                sandwich.profit[0],
+                sandwich.profit[1]
+            )?;
+        }
+        for jit in &self.jit_liquidity {
+            write!(
+                f,
+                "\njit {} {} by {}: {} -> {:?} -> {}, profit {}, {}",
+                jit.block_number,
+                jit.pool,
+                jit.provider,
+                jit.mint,
+                jit.victims,
+                jit.burn,
+                jit.profit[0],
+                jit.profit[1]
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WETH: Address = Address::repeat_byte(1);
+    const USDC: Address = Address::repeat_byte(2);
+    const POOL: Address = Address::repeat_byte(10);
+    const ATTACKER: Address = Address::repeat_byte(20);
+    const USER: Address = Address::repeat_byte(21);
+    const ROUTER: Address = Address::repeat_byte(30);
+
+    fn pool_tokens() -> PoolTokens {
+        PoolTokens::from([(POOL, vec![WETH, USDC])])
+    }
+
+    fn event(tx: u8, log_index: u64, kind: EventKind, actor: Address) -> PoolEvent {
+        PoolEvent {
+            block_number: 100,
+            block_hash: Some(B256::repeat_byte(100)),
+            tx_hash: TxHash::repeat_byte(tx),
+            log_index,
+            pool: POOL,
+            protocol: Protocol::V3,
+            kind,
+            tx_from: Some(actor),
+            sender: Some(ROUTER),
+            recipient: Some(ROUTER),
+            swap: None,
+            amounts: None,
+            state: None,
+            position: None,
+        }
+    }
+
+    fn swap(
+        tx: u8,
+        log_index: u64,
+        actor: Address,
+        token_in: u64,
+        amounts: (u64, u64),
+    ) -> PoolEvent {
+        PoolEvent {
+            swap: Some(SwapAmounts {
+                token_in,
+                token_out: 1 - token_in,
+                amount_in: U256::from(amounts.0),
+                amount_out: U256::from(amounts.1),
+            }),
+            ..event(tx, log_index, EventKind::Swap, actor)
+        }
+    }
+
+    fn liquidity(tx: u8, log_index: u64, kind: EventKind, amounts: (u64, u64)) -> PoolEvent {
+        PoolEvent {
+            amounts: Some((U256::from(amounts.0), U256::from(amounts.1))),
+            position: Some(Position {
+                owner: ATTACKER,
+                tick_lower: -60,
+                tick_upper: 60,
+                liquidity: 1000,
+            }),
+            ..event(tx, log_index, kind, ATTACKER)
+        }
+    }
+
+    fn managed(event: PoolEvent, tx_from: Address) -> PoolEvent {
+        PoolEvent {
+            tx_from: Some(tx_from),
+            position: event.position.clone().map(|position| Position {
+                owner: NONFUNGIBLE_POSITION_MANAGER,
+                ..position
+            }),
+            ..event
+        }
+    }
+
+    #[test]
+    fn test_arbitrage() {
+        let other = Address::repeat_byte(11);
+        let pool_tokens = PoolTokens::from([(POOL, vec![WETH, USDC]), (other, vec![USDC, WETH])]);
+        let events = vec![
+            swap(1, 0, ATTACKER, 0, (100, 3000)),
+            PoolEvent {
+                pool: other,
+                ..swap(1, 1, ATTACKER, 0, (3000, 103))
+            },
+        ];
+
+        let arbs = arbitrages(&group_trades(&events, &pool_tokens));
+        assert_eq!(arbs.len(), 1);
+        assert_eq!(arbs[0].pools, vec![POOL, other]);
+        assert_eq!(
+            arbs[0].profit,
+            Profit {
+                token: WETH,
+                amount: I256::try_from(3).unwrap()
+            }
+        );
+    }
+
+    #[test]
+    fn test_sandwich() {
+        let events = vec![
+            swap(1, 0, ATTACKER, 0, (100, 3000)),
+            swap(2, 1, USER, 0, (50, 1400)),
+            swap(3, 2, USER, 1, (10, 1)),
+            swap(4, 3, ATTACKER, 1, (3000, 104)),
+        ];
+
+        let sandwiches = sandwiches(&events, &pool_tokens());
+        assert_eq!(sandwiches.len(), 1);
+        assert_eq!(sandwiches[0].front_run, TxHash::repeat_byte(1));
+        assert_eq!(sandwiches[0].victims, vec![TxHash::repeat_byte(2)]);
+        assert_eq!(sandwiches[0].back_run, TxHash::repeat_byte(4));
+        assert_eq!(sandwiches[0].profit[0].amount, I256::try_from(4).unwrap());
+        assert_eq!(sandwiches[0].profit[1].amount, I256::ZERO);
+
+        // Without a victim in between it is just a round trip
+        assert!(
+            super::sandwiches(&[events[0].clone(), events[3].clone()], &pool_tokens()).is_empty()
+        );
+
+        // Swaps through the same router signed by someone else don't close the position
+        let other = Address::repeat_byte(22);
+        let events = vec![
+            events[0].clone(),
+            events[1].clone(),
+            swap(4, 3, other, 1, (3000, 104)),
+        ];
+        assert!(super::sandwiches(&events, &pool_tokens()).is_empty());
+    }
+
+    #[test]
+    fn test_jit_liquidity() {
+        let events = vec![
+            liquidity(1, 0, EventKind::Mint, (1000, 1000)),
+            swap(2, 1, USER, 0, (50, 49)),
+            liquidity(3, 2, EventKind::Burn, (1050, 951)),
+        ];
+
+        let jit = jit_liquidity(&events, &pool_tokens());
+        assert_eq!(jit.len(), 1);
+        assert_eq!(jit[0].provider, ATTACKER);
+        assert_eq!(jit[0].victims, vec![TxHash::repeat_byte(2)]);
+        assert_eq!(jit[0].profit[0].amount, I256::try_from(50).unwrap());
+        assert_eq!(jit[0].profit[1].amount, I256::try_from(-49).unwrap());
+
+        // Burning another range of the same owner is not the minted position
+        let mut other_range = events.clone();
+        if let Some(position) = other_range[2].position.as_mut() {
+            position.tick_upper = 120;
+        }
+        assert!(jit_liquidity(&other_range, &pool_tokens()).is_empty());
+    }
+
+    #[test]
+    fn test_jit_liquidity_through_position_manager() {
+        let mint = managed(liquidity(1, 0, EventKind::Mint, (1000, 1000)), ATTACKER);
+        let victim = swap(2, 1, USER, 0, (50, 49));
+
+        // Someone else's identical position burned in between is not the provider's
+        let events = vec![
+            mint.clone(),
+            victim.clone(),
+            managed(liquidity(3, 2, EventKind::Burn, (1050, 951)), USER),
+        ];
+        assert!(jit_liquidity(&events, &pool_tokens()).is_empty());
+
+        let events = vec![
+            mint,
+            victim,
+            managed(liquidity(3, 2, EventKind::Burn, (1050, 951)), ATTACKER),
+        ];
+        let jit = jit_liquidity(&events, &pool_tokens());
+        assert_eq!(jit.len(), 1);
+        assert_eq!(jit[0].provider, ATTACKER);
+    }
+}
@@ -0,0 +1,52 @@
+#![allow(clippy::too_many_arguments)]
+
+use super::*;
+
+sol!(
+    #[derive(Debug)]
+    IUniswapV2Pool,
+    "../../resources/contracts/uniswapv2_pool_abi.json"
+);
+
+sol!(
+    #[derive(Debug)]
+    IUniswapV3Pool,
+    "../../resources/contracts/uniswapv3_pool_abi.json"
+);
+
+// Stable pools index coins with `int128`, crypto pools with `uint256`
+sol!(
+    #[derive(Debug)]
+    CurvePool,
+    "../../resources/contracts/curve_pool.json"
+);
+
+sol!(
+    #[derive(Debug)]
+    CurveCryptoPool,
+    "../../resources/contracts/curve_crypto_contract.json"
+);
+
+sol!(
+    #[derive(Debug)]
+    CurveMetaPool,
+    "../../resources/contracts/curve_meta_contract.json"
+);
+
+/// Topic0 of every event the indexer stores
+pub const EVENT_SIGNATURES: [B256; 10] = [
+    IUniswapV2Pool::Swap::SIGNATURE_HASH,
+    IUniswapV2Pool::Sync::SIGNATURE_HASH,
+    IUniswapV2Pool::Mint::SIGNATURE_HASH,
+    IUniswapV2Pool::Burn::SIGNATURE_HASH,
+    IUniswapV3Pool::Swap::SIGNATURE_HASH,
+    IUniswapV3Pool::Mint::SIGNATURE_HASH,
+    IUniswapV3Pool::Burn::SIGNATURE_HASH,
+    CurvePool::TokenExchange::SIGNATURE_HASH,
+    CurveCryptoPool::TokenExchange::SIGNATURE_HASH,
+    CurveMetaPool::TokenExchangeUnderlying::SIGNATURE_HASH,
+];
+
+/// Owner of every position minted through the periphery, whatever account holds its NFT
+pub const NONFUNGIBLE_POSITION_MANAGER: Address =
+    address!("0xC36442b4a4522E871399CD717aBDD847Ab11FE88");
@@ -0,0 +1,455 @@
+use super::*;
+
+const SCHEMA: &str = "
+CREATE TABLE IF NOT EXISTS events (
+    block_number INTEGER NOT NULL,
+    tx_hash TEXT NOT NULL,
+    log_index INTEGER NOT NULL,
+    pool TEXT NOT NULL,
+    protocol TEXT NOT NULL,
+    kind TEXT NOT NULL,
+    sender TEXT,
+    recipient TEXT,
+    token_in INTEGER,
+    token_out INTEGER,
+    amount_in TEXT,
+    amount_out TEXT,
+    amount0 TEXT,
+    amount1 TEXT,
+    sqrt_price_x96 TEXT,
+    liquidity TEXT,
+    tick INTEGER,
+    tx_from TEXT,
+    owner TEXT,
+    tick_lower INTEGER,
+    tick_upper INTEGER,
+    position_liquidity TEXT,
+    block_hash TEXT,
+    PRIMARY KEY (tx_hash, log_index)
+);
+CREATE TABLE IF NOT EXISTS blocks (
+    block_number INTEGER PRIMARY KEY,
+    block_hash TEXT NOT NULL
+);
+CREATE INDEX IF NOT EXISTS events_pool ON events (pool, block_number);
+CREATE INDEX IF NOT EXISTS events_block ON events (block_number, log_index);
+";
+
+// Created after the columns are, on databases that predate them
+const ADDED_INDEXES: &str = "
+CREATE INDEX IF NOT EXISTS events_block_hash ON events (block_hash);
+";
+
+const COLUMNS: &str = "block_number, tx_hash, log_index, pool, protocol, kind, sender, recipient, \
+    token_in, token_out, amount_in, amount_out, amount0, amount1, sqrt_price_x96, liquidity, tick, \
+    tx_from, owner, tick_lower, tick_upper, position_liquidity, block_hash";
+
+/// Columns added after the first schema, created on databases that predate them
+const ADDED_COLUMNS: [(&str, &str); 6] = [
+    ("tx_from", "TEXT"),
+    ("owner", "TEXT"),
+    ("tick_lower", "INTEGER"),
+    ("tick_upper", "INTEGER"),
+    ("position_liquidity", "TEXT"),
+    ("block_hash", "TEXT"),
+];
+
+/// Filters of the `query` subcommand, all optional
+#[derive(Debug, Default, clap::Args)]
+pub struct EventFilter {
+    #[arg(long)]
+    pub pool: Option<Address>,
+    #[arg(long)]
+    pub protocol: Option<Protocol>,
+    #[arg(long)]
+    pub kind: Option<EventKind>,
+    /// First block, inclusive
+    #[arg(long)]
+    pub from_block: Option<u64>,
+    /// Last block, inclusive
+    #[arg(long)]
+    pub to_block: Option<u64>,
+    /// Maximum number of events printed
+    #[arg(long, default_value_t = 50)]
+    pub limit: u64,
+}
+
+/// Embedded SQLite store of normalised pool events
+pub struct Database {
+    conn: Connection,
+}
+
+impl Database {
+    pub fn open(path: &Path) -> Result<Self> {
+        Self::new(Connection::open(path)?)
+    }
+
+    pub fn new(conn: Connection) -> Result<Self> {
+        conn.execute_batch(SCHEMA)?;
+
+        let existing: Vec<String> = conn
+            .prepare("SELECT name FROM pragma_table_info('events')")?
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        for (column, kind) in ADDED_COLUMNS {
+            if !existing.iter().any(|name| name == column) {
+                conn.execute(
+                    &format!("ALTER TABLE events ADD COLUMN {column} {kind}"),
+                    [],
+                )?;
+            }
+        }
+        conn.execute_batch(ADDED_INDEXES)?;
+
+        Ok(Self { conn })
+    }
+
+    /// Store events in one transaction, along with the hash of every block they came from;
+    /// logs seen before, e.g. on a restart, are ignored
+    pub fn insert(&mut self, events: &[PoolEvent]) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut inserted = 0;
+
+        {
+            let mut stmt = tx.prepare_cached(&format!(
+                "INSERT OR IGNORE INTO events ({COLUMNS}) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17, \
+                         ?18, ?19, ?20, ?21, ?22, ?23)"
+            ))?;
+
+            for event in events {
+                let swap = event.swap.as_ref();
+                let state = event.state.as_ref();
+                let position = event.position.as_ref();
+
+                inserted += stmt.execute(params![
+                    event.block_number,
+                    event.tx_hash.to_string(),
+                    event.log_index,
+                    event.pool.to_string(),
+                    event.protocol.as_str(),
+                    event.kind.as_str(),
+                    event.sender.map(|sender| sender.to_string()),
+                    event.recipient.map(|recipient| recipient.to_string()),
+                    swap.map(|swap| swap.token_in),
+                    swap.map(|swap| swap.token_out),
+                    swap.map(|swap| swap.amount_in.to_string()),
+                    swap.map(|swap| swap.amount_out.to_string()),
+                    event.amounts.map(|(amount0, _)| amount0.to_string()),
+                    event.amounts.map(|(_, amount1)| amount1.to_string()),
+                    state.map(|state| state.sqrt_price_x96.to_string()),
+                    state.map(|state| state.liquidity.to_string()),
+                    state.map(|state| state.tick),
+                    event.tx_from.map(|tx_from| tx_from.to_string()),
+                    position.map(|position| position.owner.to_string()),
+                    position.map(|position| position.tick_lower),
+                    position.map(|position| position.tick_upper),
+                    position.map(|position| position.liquidity.to_string()),
+                    event.block_hash.map(|hash| hash.to_string()),
+                ])?;
+
+                if let Some(hash) = event.block_hash {
+                    record_block(&tx, event.block_number, hash)?;
+                }
+            }
+        }
+
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Events matching the query, most recent first
+    pub fn query(&self, filter: &EventFilter) -> Result<Vec<PoolEvent>> {
+        let mut stmt = self.conn.prepare_cached(&format!(
+            "SELECT {COLUMNS} FROM events
+             WHERE (?1 IS NULL OR pool = ?1)
+               AND (?2 IS NULL OR protocol = ?2)
+               AND (?3 IS NULL OR kind = ?3)
+               AND (?4 IS NULL OR block_number >= ?4)
+               AND (?5 IS NULL OR block_number <= ?5)
+             ORDER BY block_number DESC, log_index DESC
+             LIMIT ?6"
+        ))?;
+
+        let rows = stmt.query_map(
+            params![
+                filter.pool.map(|pool| pool.to_string()),
+                filter.protocol.map(|protocol| protocol.as_str()),
+                filter.kind.map(|kind| kind.as_str()),
+                filter.from_block,
+                filter.to_block,
+                filter.limit,
+            ],
+            read_event,
+        )?;
+
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Every event of a block range in log order
+    pub fn block_events(&self, from_block: u64, to_block: u64) -> Result<Vec<PoolEvent>> {
+        let mut stmt = self.conn.prepare_cached(&format!(
+            "SELECT {COLUMNS} FROM events
+             WHERE block_number BETWEEN ?1 AND ?2
+             ORDER BY block_number, log_index"
+        ))?;
+
+        let rows = stmt.query_map(params![from_block, to_block], read_event)?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Remember a block as indexed, events or not
+    pub fn record_block(&mut self, block_number: u64, block_hash: B256) -> Result<()> {
+        record_block(&self.conn, block_number, block_hash)
+    }
+
+    /// Highest block indexed, where indexing resumes from
+    pub fn last_indexed_block(&self) -> Result<Option<u64>> {
+        Ok(self
+            .conn
+            .query_row("SELECT MAX(block_number) FROM blocks", [], |row| row.get(0))?)
+    }
+
+    /// Number and hash of the latest indexed blocks, newest first
+    pub fn recent_blocks(&self, limit: u64) -> Result<Vec<(u64, B256)>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT block_number, block_hash FROM blocks ORDER BY block_number DESC LIMIT ?1",
+        )?;
+
+        let rows = stmt.query_map(params![limit], |row| Ok((row.get(0)?, parse(row, 1)?)))?;
+        Ok(rows.collect::<Result<_, _>>()?)
+    }
+
+    /// Delete the events of blocks a reorg replaced, and forget those blocks were indexed
+    pub fn remove_blocks(&mut self, block_hashes: &[B256]) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut removed = 0;
+
+        for hash in block_hashes {
+            removed += tx.execute(
+                "DELETE FROM events WHERE block_hash = ?1",
+                params![hash.to_string()],
+            )?;
+            tx.execute(
+                "DELETE FROM blocks WHERE block_hash = ?1",
+                params![hash.to_string()],
+            )?;
+        }
+
+        tx.commit()?;
+        Ok(removed)
+    }
+
+    /// Highest block with a stored event
+    pub fn last_block(&self) -> Result<Option<u64>> {
+        Ok(self
+            .conn
+            .query_row("SELECT MAX(block_number) FROM events", [], |row| row.get(0))?)
+    }
+}
+
+fn record_block(conn: &Connection, block_number: u64, block_hash: B256) -> Result<()> {
+    conn.prepare_cached(
+        "INSERT OR REPLACE INTO blocks (block_number, block_hash) VALUES (?1, ?2)",
+    )?
+    .execute(params![block_number, block_hash.to_string()])?;
+    Ok(())
+}
+
+fn parse<T: FromStr>(row: &Row, idx: usize) -> rusqlite::Result<T>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    let value: String = row.get(idx)?;
+    value
+        .parse()
+        .map_err(|e| rusqlite::Error::FromSqlConversionFailure(idx, Type::Text, Box::new(e)))
+}
+
+fn parse_opt<T: FromStr>(row: &Row, idx: usize) -> rusqlite::Result<Option<T>>
+where
+    T::Err: std::error::Error + Send + Sync + 'static,
+{
+    match row.get_ref(idx)? {
+        ValueRef::Null => Ok(None),
+        _ => parse(row, idx).map(Some),
+    }
+}
+
+fn read_event(row: &Row) -> rusqlite::Result<PoolEvent> {
+    let swap = match (row.get(8)?, row.get(9)?) {
+        (Some(token_in), Some(token_out)) => Some(SwapAmounts {
+            token_in,
+            token_out,
+            amount_in: parse(row, 10)?,
+            amount_out: parse(row, 11)?,
+        }),
+        _ => None,
+    };
+    let amounts = match (parse_opt(row, 12)?, parse_opt(row, 13)?) {
+        (Some(amount0), Some(amount1)) => Some((amount0, amount1)),
+        _ => None,
+    };
+    let state = match row.get::<_, Option<i32>>(16)? {
+        Some(tick) => Some(PoolState {
+            sqrt_price_x96: parse(row, 14)?,
+            liquidity: parse(row, 15)?,
+            tick,
+        }),
+        None => None,
+    };
+    let position = match parse_opt(row, 18)? {
+        Some(owner) => Some(Position {
+            owner,
+            tick_lower: row.get(19)?,
+            tick_upper: row.get(20)?,
+            liquidity: parse(row, 21)?,
+        }),
+        None => None,
+    };
+
+    Ok(PoolEvent {
+        block_number: row.get(0)?,
+        block_hash: parse_opt(row, 22)?,
+        tx_hash: parse(row, 1)?,
+        log_index: row.get(2)?,
+        pool: parse(row, 3)?,
+        protocol: parse(row, 4)?,
+        kind: parse(row, 5)?,
+        tx_from: parse_opt(row, 17)?,
+        sender: parse_opt(row, 6)?,
+        recipient: parse_opt(row, 7)?,
+        swap,
+        amounts,
+        state,
+        position,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(block_number: u64, log_index: u64, kind: EventKind) -> PoolEvent {
+        PoolEvent {
+            block_number,
+            block_hash: Some(B256::repeat_byte(block_number as u8)),
+            tx_hash: TxHash::repeat_byte(block_number as u8),
+            log_index,
+            pool: Address::repeat_byte(9),
+            protocol: Protocol::V3,
+            kind,
+            tx_from: Some(Address::repeat_byte(1)),
+            sender: Some(Address::repeat_byte(2)),
+            recipient: None,
+            swap: (kind == EventKind::Swap).then(|| SwapAmounts {
+                token_in: 1,
+                token_out: 0,
+                amount_in: U256::MAX,
+                amount_out: U256::from(3),
+            }),
+            amounts: (kind == EventKind::Mint).then(|| (U256::from(1), U256::from(2))),
+            state: (kind == EventKind::Swap).then(|| PoolState {
+                sqrt_price_x96: U256::from(1) << 96,
+                liquidity: u128::MAX,
+                tick: -887272,
+            }),
+            position: (kind == EventKind::Mint).then(|| Position {
+                owner: Address::repeat_byte(3),
+                tick_lower: -887220,
+                tick_upper: 887220,
+                liquidity: u128::MAX,
+            }),
+        }
+    }
+
+    #[test]
+    fn test_roundtrip() {
+        let mut db = Database::new(Connection::open_in_memory().unwrap()).unwrap();
+        let events = vec![
+            event(1, 0, EventKind::Mint),
+            event(1, 1, EventKind::Swap),
+            event(2, 0, EventKind::Swap),
+        ];
+
+        assert_eq!(db.insert(&events).unwrap(), 3);
+        // Reprocessing a block stores nothing new
+        assert_eq!(db.insert(&events[..1]).unwrap(), 0);
+
+        let all = db.query(&EventFilter {
+            limit: 10,
+            ..Default::default()
+        });
+        assert_eq!(
+            all.unwrap(),
+            vec![events[2].clone(), events[1].clone(), events[0].clone()]
+        );
+
+        let swaps = db.query(&EventFilter {
+            kind: Some(EventKind::Swap),
+            to_block: Some(1),
+            limit: 10,
+            ..Default::default()
+        });
+        assert_eq!(swaps.unwrap(), vec![events[1].clone()]);
+    }
+
+    #[test]
+    fn test_adds_new_columns() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE events (
+                block_number INTEGER NOT NULL,
+                tx_hash TEXT NOT NULL,
+                log_index INTEGER NOT NULL,
+                pool TEXT NOT NULL,
+                protocol TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                sender TEXT,
+                recipient TEXT,
+                token_in INTEGER,
+                token_out INTEGER,
+                amount_in TEXT,
+                amount_out TEXT,
+                amount0 TEXT,
+                amount1 TEXT,
+                sqrt_price_x96 TEXT,
+                liquidity TEXT,
+                tick INTEGER,
+                PRIMARY KEY (tx_hash, log_index)
+            );",
+        )
+        .unwrap();
+
+        let mut db = Database::new(conn).unwrap();
+        let events = vec![event(1, 0, EventKind::Mint)];
+        db.insert(&events).unwrap();
+        assert_eq!(db.block_events(1, 1).unwrap(), events);
+    }
+
+    #[test]
+    fn test_remove_reorged_blocks() {
+        let mut db = Database::new(Connection::open_in_memory().unwrap()).unwrap();
+        let events = vec![
+            event(1, 0, EventKind::Swap),
+            event(2, 0, EventKind::Swap),
+            event(2, 1, EventKind::Mint),
+        ];
+        db.insert(&events).unwrap();
+        db.record_block(3, B256::repeat_byte(3)).unwrap();
+
+        assert_eq!(db.last_indexed_block().unwrap(), Some(3));
+        assert_eq!(
+            db.recent_blocks(2).unwrap(),
+            vec![(3, B256::repeat_byte(3)), (2, B256::repeat_byte(2))]
+        );
+
+        // Only the replaced blocks lose their events
+        let removed = db
+            .remove_blocks(&[B256::repeat_byte(3), B256::repeat_byte(2)])
+            .unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(db.last_indexed_block().unwrap(), Some(1));
+        assert_eq!(db.block_events(1, 3).unwrap(), events[..1]);
+    }
+}
@@ -0,0 +1,82 @@
+use super::*;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum Protocol {
+    V2,
+    V3,
+    Curve,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, ValueEnum)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Swap,
+    /// Curve meta pool swap between underlying coins
+    SwapUnderlying,
+    Sync,
+    Mint,
+    Burn,
+}
+
+impl Protocol {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::V2 => "v2",
+            Self::V3 => "v3",
+            Self::Curve => "curve",
+        }
+    }
+}
+
+impl EventKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Swap => "swap",
+            Self::SwapUnderlying => "swap_underlying",
+            Self::Sync => "sync",
+            Self::Mint => "mint",
+            Self::Burn => "burn",
+        }
+    }
+}
+
+impl FromStr for Protocol {
+    type Err = FromSqlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [Self::V2, Self::V3, Self::Curve]
+            .into_iter()
+            .find(|protocol| protocol.as_str() == s)
+            .ok_or(FromSqlError::InvalidType)
+    }
+}
+
+impl FromStr for EventKind {
+    type Err = FromSqlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        [
+            Self::Swap,
+            Self::SwapUnderlying,
+            Self::Sync,
+            Self::Mint,
+            Self::Burn,
+        ]
+        .into_iter()
+        .find(|kind| kind.as_str() == s)
+        .ok_or(FromSqlError::InvalidType)
+    }
+}
+
+impl Display for Protocol {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl Display for EventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -0,0 +1,228 @@
+use super::*;
+
+/// Deepest reorg looked for when a new head doesn't extend the indexed chain
+const MAX_REORG_DEPTH: u64 = 64;
+
+/// Pause before subscribing again after the subscription failed
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+fn pool_filter(pools: &[Address]) -> Filter {
+    Filter::new()
+        .address(pools.to_vec())
+        .event_signature(EVENT_SIGNATURES.to_vec())
+}
+
+/// Decode logs, set the signer of their transactions and store them with their block hashes
+async fn store(
+    provider: &impl Provider,
+    db: &mut Database,
+    logs: &[Log],
+) -> Result<Vec<PoolEvent>> {
+    let received = Instant::now();
+    let mut events: Vec<PoolEvent> = logs.iter().filter_map(PoolEvent::decode).collect();
+
+    // Logs only name contracts, the account behind each transaction comes from its receipt
+    let mut blocks: Vec<B256> = events.iter().filter_map(|event| event.block_hash).collect();
+    blocks.dedup();
+    let mut senders: HashMap<TxHash, Address> = HashMap::new();
+    for block_hash in blocks {
+        let receipts = provider
+            .get_block_receipts(BlockId::hash(block_hash))
+            .await
+            .inspect_err(|_| record_rpc_error("eth_getBlockReceipts"))?
+            .unwrap_or_default();
+        senders.extend(
+            receipts
+                .iter()
+                .map(|receipt| (receipt.transaction_hash, receipt.from)),
+        );
+    }
+    for event in events.iter_mut() {
+        event.tx_from = senders.get(&event.tx_hash).copied();
+    }
+
+    db.insert(&events)?;
+
+    for event in &events {
+        record_event(event.kind.as_str(), event.protocol.as_str(), received);
+    }
+
+    Ok(events)
+}
+
+/// Fetch every pool event of one block with a single `eth_getLogs` and store it
+pub async fn index_block(
+    provider: &impl Provider,
+    db: &mut Database,
+    pools: &[Address],
+    block_number: u64,
+    block_hash: B256,
+) -> Result<Vec<PoolEvent>> {
+    let filter = pool_filter(pools).at_block_hash(block_hash);
+
+    let logs = provider
+        .get_logs(&filter)
+        .await
+        .inspect_err(|_| record_rpc_error("eth_getLogs"))?;
+    let events = store(provider, db, &logs).await?;
+    db.record_block(block_number, block_hash)?;
+
+    Ok(events)
+}
+
+/// Drop the indexed blocks the chain no longer has, newest first, down to the first one it kept
+async fn rewind(provider: &impl Provider, db: &mut Database, head: &Header) -> Result<()> {
+    let mut orphaned = vec![];
+
+    for (number, hash) in db.recent_blocks(MAX_REORG_DEPTH)? {
+        let canonical = if number > head.number {
+            None
+        } else if number == head.number {
+            Some(head.hash)
+        } else if number + 1 == head.number {
+            Some(head.parent_hash)
+        } else {
+            provider
+                .get_block_by_number(number.into())
+                .await
+                .inspect_err(|_| record_rpc_error("eth_getBlockByNumber"))?
+                .map(|block| block.header.hash)
+        };
+
+        if canonical == Some(hash) {
+            break;
+        }
+        orphaned.push(hash);
+    }
+
+    if !orphaned.is_empty() {
+        let removed = db.remove_blocks(&orphaned)?;
+        eprintln!(
+            "{}",
+            format!(
+                "Reorg at block {}: {} blocks and {removed} events dropped",
+                head.number,
+                orphaned.len()
+            )
+            .red()
+        );
+    }
+
+    Ok(())
+}
+
+/// Index the blocks missed between the last indexed block and `head`, then `head` itself
+async fn catch_up(
+    provider: &impl Provider,
+    db: &mut Database,
+    pools: &[Address],
+    pool_tokens: &PoolTokens,
+    head: &Header,
+) -> Result<()> {
+    rewind(provider, db, head).await?;
+
+    let next = db
+        .last_indexed_block()?
+        .map_or(head.number, |last| last + 1);
+    if next < head.number {
+        let mut logs = vec![];
+        Backfill::new(pool_filter(pools))
+            .run(provider, next, head.number - 1, |range, _| {
+                logs.extend(range);
+                Ok::<_, anyhow::Error>(())
+            })
+            .await?;
+
+        let events = store(provider, db, &logs).await?;
+        println!(
+            "{}",
+            format!(
+                "blocks {next}-{}: {} events filled in",
+                head.number - 1,
+                events.len()
+            )
+            .blue()
+        );
+    }
+
+    // Retried like the backfill is, a block that still fails is filled in after the next one
+    let backfill = Backfill::new(Filter::new());
+    let mut retries = 0;
+    let events = loop {
+        match index_block(provider, db, pools, head.number, head.hash).await {
+            Ok(events) => break events,
+            Err(e) if retries < backfill.max_retries => {
+                let delay = backfill.backoff * 2u32.pow(retries);
+                retries += 1;
+                eprintln!(
+                    "Block {} failed ({e}), retry {retries} in {delay:?}",
+                    head.number
+                );
+                tokio::time::sleep(delay).await;
+            }
+            Err(e) => return Err(e),
+        }
+    };
+
+    println!(
+        "{}",
+        format!("block {}: {} events", head.number, events.len()).blue()
+    );
+    for event in &events {
+        println!("{event}");
+    }
+    for trade in group_trades(&events, pool_tokens) {
+        println!("{}", trade.to_string().yellow());
+    }
+
+    Ok(())
+}
+
+/// Index every new block, resubscribing whenever the subscription ends or fails
+pub async fn run(
+    provider: &impl Provider,
+    db: &mut Database,
+    pools: &[Address],
+    pool_tokens: &PoolTokens,
+) -> Result<()> {
+    println!("Indexing {} pools...", pools.len());
+
+    loop {
+        match index_blocks(provider, db, pools, pool_tokens).await {
+            Ok(()) => eprintln!("{}", "Block subscription ended, resubscribing...".red()),
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    format!("Block subscription failed ({e}), resubscribing...").red()
+                );
+                tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+            }
+        }
+        WS_RECONNECTS.inc();
+    }
+}
+
+async fn index_blocks(
+    provider: &impl Provider,
+    db: &mut Database,
+    pools: &[Address],
+    pool_tokens: &PoolTokens,
+) -> Result<()> {
+    let mut stream = provider
+        .subscribe_blocks()
+        .await
+        .inspect_err(|_| record_rpc_error("eth_subscribe"))?
+        .into_stream();
+
+    while let Some(header) = stream.next().await {
+        // A block that can't be indexed is left for the gap fill of the next one
+        if let Err(e) = catch_up(provider, db, pools, pool_tokens, &header).await {
+            eprintln!(
+                "{}",
+                format!("Block {} not indexed: {e}", header.number).red()
+            );
+        }
+    }
+
+    Ok(())
+}
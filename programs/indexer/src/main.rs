@@ -0,0 +1,184 @@
+use crate::{
+    classify::*, contracts::*, db::*, enums::*, indexer::*, parser::*, structs::*, trades::*,
+};
+use alloy::{
+    eips::BlockId,
+    primitives::{address, Address, TxHash, B256, I256, U256},
+    providers::{Provider, ProviderBuilder, WsConnect},
+    rpc::types::{Filter, Header, Log},
+    sol,
+    sol_types::SolEvent,
+};
+use anyhow::Result;
+use clap::{Parser, Subcommand, ValueEnum};
+use colored::Colorize;
+use dotenv::dotenv;
+use futures_util::StreamExt;
+use rusqlite::{
+    params,
+    types::{FromSqlError, Type, ValueRef},
+    Connection, Row,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    env,
+    fmt::Display,
+    fs::{self, File},
+    io::BufReader,
+    path::{Path, PathBuf},
+    str::FromStr,
+    time::{Duration, Instant},
+};
+use utils::backfill::Backfill;
+use utils::metrics::{record_event, record_rpc_error, spawn_exporter, WS_RECONNECTS};
+
+mod classify;
+mod contracts;
+mod db;
+mod enums;
+mod indexer;
+mod parser;
+mod structs;
+mod trades;
+
+/// Index Uniswap and Curve pool events into SQLite and query them back
+#[derive(Parser)]
+struct Args {
+    /// SQLite database file, created on first use
+    #[arg(long, default_value = "indexer.db")]
+    db: PathBuf,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Follow new blocks and store the events of every pool in `POOL_PATH`,
+    /// first filling in the blocks missed since the last indexed one
+    Run,
+    /// Print stored events, most recent first
+    Query {
+        #[command(flatten)]
+        filter: EventFilter,
+
+        /// Print JSON instead of one line per event
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the trades reconstructed from stored swaps, one per chained route
+    Trades {
+        /// First block, inclusive; defaults to the last block
+        #[arg(long)]
+        from_block: Option<u64>,
+        /// Last block, inclusive; defaults to the last indexed block
+        #[arg(long)]
+        to_block: Option<u64>,
+
+        /// Print JSON instead of one record per trade
+        #[arg(long)]
+        json: bool,
+    },
+    /// Classify arbitrages, sandwiches and JIT liquidity into one report per block range
+    Report {
+        #[arg(long)]
+        from_block: u64,
+        #[arg(long)]
+        to_block: u64,
+
+        /// Blocks covered by each report
+        #[arg(long, default_value_t = 1000)]
+        step: u64,
+
+        /// Directory to write `<from>-<to>.json` reports to instead of printing them
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let args = Args::parse();
+    let mut db = Database::open(&args.db)?;
+
+    match args.command {
+        Command::Run => {
+            let env_parser = EnvParser::new()?;
+            let ws = WsConnect::new(env_parser.ws_address);
+            let provider = ProviderBuilder::new().connect_ws(ws).await?;
+
+            // Serve /metrics when METRICS_ADDRESS is set
+            spawn_exporter();
+
+            run(
+                &provider,
+                &mut db,
+                &env_parser.pools,
+                &env_parser.pool_tokens,
+            )
+            .await
+        }
+        Command::Query { filter, json } => {
+            let events = db.query(&filter)?;
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&events)?);
+            } else {
+                events.iter().for_each(|event| println!("{event}"));
+            }
+
+            Ok(())
+        }
+        Command::Trades {
+            from_block,
+            to_block,
+            json,
+        } => {
+            let Some(to_block) = to_block.or(db.last_block()?) else {
+                return Ok(());
+            };
+            let from_block = from_block.unwrap_or(to_block);
+
+            let env_parser = EnvParser::new()?;
+            let events = db.block_events(from_block, to_block)?;
+            let trades = group_trades(&events, &env_parser.pool_tokens);
+
+            if json {
+                println!("{}", serde_json::to_string_pretty(&trades)?);
+            } else {
+                trades.iter().for_each(|trade| println!("{trade}"));
+            }
+
+            Ok(())
+        }
+        Command::Report {
+            from_block,
+            to_block,
+            step,
+            out,
+        } => {
+            let env_parser = EnvParser::new()?;
+            if let Some(out) = &out {
+                fs::create_dir_all(out)?;
+            }
+
+            for start in (from_block..=to_block).step_by(step.max(1) as usize) {
+                let end = (start + step.max(1) - 1).min(to_block);
+                let events = db.block_events(start, end)?;
+                let report = Report::new(start, end, &events, &env_parser.pool_tokens);
+
+                match &out {
+                    Some(out) => {
+                        let path = out.join(format!("{start}-{end}.json"));
+                        serde_json::to_writer_pretty(File::create(&path)?, &report)?;
+                        println!("{}", path.display());
+                    }
+                    None => println!("{report}"),
+                }
+            }
+
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use super::*;
+
+#[derive(Debug, Deserialize)]
+pub struct PoolAddress {
+    pub v2: Vec<Address>,
+    pub v3: Vec<Address>,
+    pub curve: Vec<Address>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UniswapPoolTokens {
+    token0: Address,
+    token1: Address,
+    address: Address,
+}
+
+#[derive(Debug, Deserialize)]
+struct CurvePoolTokens {
+    tokens: Vec<Address>,
+    address: Address,
+}
+
+/// Coins of every pool, in the order its events index them
+pub type PoolTokens = HashMap<Address, Vec<Address>>;
+
+pub struct EnvParser {
+    pub ws_address: String,
+    pub pools: Vec<Address>,
+    pub pool_tokens: PoolTokens,
+}
+
+impl EnvParser {
+    pub fn new() -> Result<Self> {
+        dotenv().ok();
+
+        // Open the file with pool addresses
+        let pool_file = File::open(env::var("POOL_PATH")?)?;
+        let pool_address: PoolAddress = serde_json::from_reader(BufReader::new(pool_file))?;
+
+        let mut pool_tokens = PoolTokens::new();
+        for var in ["POOLS_V2_PATH", "POOLS_V3_PATH"] {
+            let file = File::open(env::var(var)?)?;
+            let pools: Vec<UniswapPoolTokens> = serde_json::from_reader(BufReader::new(file))?;
+            pool_tokens.extend(
+                pools
+                    .into_iter()
+                    .map(|pool| (pool.address, vec![pool.token0, pool.token1])),
+            );
+        }
+
+        let curve_file = File::open(env::var("CURVE_TOKENS_PATH")?)?;
+        let curve_pools: Vec<CurvePoolTokens> =
+            serde_json::from_reader(BufReader::new(curve_file))?;
+        pool_tokens.extend(
+            curve_pools
+                .into_iter()
+                .map(|pool| (pool.address, pool.tokens)),
+        );
+
+        Ok(Self {
+            ws_address: env::var("WEBSOCKET_ENDPOINT")?,
+            pools: [pool_address.v2, pool_address.v3, pool_address.curve].concat(),
+            pool_tokens,
+        })
+    }
+}
@@ -0,0 +1,386 @@
+use super::*;
+
+/// Coin indices and amounts of a swap; 0 and 1 are token0 and token1 on Uniswap
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SwapAmounts {
+    pub token_in: u64,
+    pub token_out: u64,
+    pub amount_in: U256,
+    pub amount_out: U256,
+}
+
+impl SwapAmounts {
+    fn from_v2(swap: &IUniswapV2Pool::Swap) -> Self {
+        if swap.amount0In > U256::ZERO {
+            Self {
+                token_in: 0,
+                token_out: 1,
+                amount_in: swap.amount0In,
+                amount_out: swap.amount1Out,
+            }
+        } else {
+            Self {
+                token_in: 1,
+                token_out: 0,
+                amount_in: swap.amount1In,
+                amount_out: swap.amount0Out,
+            }
+        }
+    }
+
+    /// V3 amounts are pool balance deltas, positive for the token the pool received
+    fn from_v3(swap: &IUniswapV3Pool::Swap) -> Self {
+        let (amount0, amount1) = (swap.amount0.unsigned_abs(), swap.amount1.unsigned_abs());
+        if swap.amount0.is_positive() {
+            Self {
+                token_in: 0,
+                token_out: 1,
+                amount_in: amount0,
+                amount_out: amount1,
+            }
+        } else {
+            Self {
+                token_in: 1,
+                token_out: 0,
+                amount_in: amount1,
+                amount_out: amount0,
+            }
+        }
+    }
+}
+
+/// Pool state a v3 swap left behind
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PoolState {
+    pub sqrt_price_x96: U256,
+    pub liquidity: u128,
+    pub tick: i32,
+}
+
+/// Range and liquidity of the v3 position a mint or burn changed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Position {
+    pub owner: Address,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub liquidity: u128,
+}
+
+/// One pool log, normalised across protocols
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PoolEvent {
+    pub block_number: u64,
+    /// Lets the events of a block be dropped when a reorg replaces it; unset on rows older than it
+    pub block_hash: Option<B256>,
+    pub tx_hash: TxHash,
+    pub log_index: u64,
+    pub pool: Address,
+    pub protocol: Protocol,
+    pub kind: EventKind,
+    /// Account that signed the transaction, which logs don't carry; set by the indexer
+    pub tx_from: Option<Address>,
+    pub sender: Option<Address>,
+    pub recipient: Option<Address>,
+    pub swap: Option<SwapAmounts>,
+    /// Reserves of a v2 `Sync`, or the token amounts of a mint or burn
+    pub amounts: Option<(U256, U256)>,
+    pub state: Option<PoolState>,
+    pub position: Option<Position>,
+}
+
+impl PoolEvent {
+    fn new(log: &Log, protocol: Protocol, kind: EventKind) -> Option<Self> {
+        Some(Self {
+            block_number: log.block_number?,
+            block_hash: Some(log.block_hash?),
+            tx_hash: log.transaction_hash?,
+            log_index: log.log_index?,
+            pool: log.address(),
+            protocol,
+            kind,
+            tx_from: None,
+            sender: None,
+            recipient: None,
+            swap: None,
+            amounts: None,
+            state: None,
+            position: None,
+        })
+    }
+
+    /// Decode a log of any supported event; pending logs and other events yield `None`
+    pub fn decode(log: &Log) -> Option<Self> {
+        use EventKind::*;
+        use Protocol::*;
+
+        match *log.topic0()? {
+            IUniswapV2Pool::Swap::SIGNATURE_HASH => {
+                let swap = log.log_decode::<IUniswapV2Pool::Swap>().ok()?.inner.data;
+                Some(Self {
+                    sender: Some(swap.sender),
+                    recipient: Some(swap.to),
+                    swap: Some(SwapAmounts::from_v2(&swap)),
+                    ..Self::new(log, V2, Swap)?
+                })
+            }
+            IUniswapV2Pool::Sync::SIGNATURE_HASH => {
+                let sync = log.log_decode::<IUniswapV2Pool::Sync>().ok()?.inner.data;
+                Some(Self {
+                    amounts: Some((U256::from(sync.reserve0), U256::from(sync.reserve1))),
+                    ..Self::new(log, V2, Sync)?
+                })
+            }
+            IUniswapV2Pool::Mint::SIGNATURE_HASH => {
+                let mint = log.log_decode::<IUniswapV2Pool::Mint>().ok()?.inner.data;
+                Some(Self {
+                    sender: Some(mint.sender),
+                    amounts: Some((mint.amount0, mint.amount1)),
+                    ..Self::new(log, V2, Mint)?
+                })
+            }
+            IUniswapV2Pool::Burn::SIGNATURE_HASH => {
+                let burn = log.log_decode::<IUniswapV2Pool::Burn>().ok()?.inner.data;
+                Some(Self {
+                    sender: Some(burn.sender),
+                    recipient: Some(burn.to),
+                    amounts: Some((burn.amount0, burn.amount1)),
+                    ..Self::new(log, V2, Burn)?
+                })
+            }
+            IUniswapV3Pool::Swap::SIGNATURE_HASH => {
+                let swap = log.log_decode::<IUniswapV3Pool::Swap>().ok()?.inner.data;
+                Some(Self {
+                    sender: Some(swap.sender),
+                    recipient: Some(swap.recipient),
+                    swap: Some(SwapAmounts::from_v3(&swap)),
+                    state: Some(PoolState {
+                        sqrt_price_x96: U256::from(swap.sqrtPriceX96),
+                        liquidity: swap.liquidity,
+                        tick: swap.tick.as_i32(),
+                    }),
+                    ..Self::new(log, V3, Swap)?
+                })
+            }
+            IUniswapV3Pool::Mint::SIGNATURE_HASH => {
+                let mint = log.log_decode::<IUniswapV3Pool::Mint>().ok()?.inner.data;
+                Some(Self {
+                    sender: Some(mint.sender),
+                    recipient: Some(mint.owner),
+                    amounts: Some((mint.amount0, mint.amount1)),
+                    position: Some(Position {
+                        owner: mint.owner,
+                        tick_lower: mint.tickLower.as_i32(),
+                        tick_upper: mint.tickUpper.as_i32(),
+                        liquidity: mint.amount,
+                    }),
+                    ..Self::new(log, V3, Mint)?
+                })
+            }
+            IUniswapV3Pool::Burn::SIGNATURE_HASH => {
+                let burn = log.log_decode::<IUniswapV3Pool::Burn>().ok()?.inner.data;
+                Some(Self {
+                    sender: Some(burn.owner),
+                    amounts: Some((burn.amount0, burn.amount1)),
+                    position: Some(Position {
+                        owner: burn.owner,
+                        tick_lower: burn.tickLower.as_i32(),
+                        tick_upper: burn.tickUpper.as_i32(),
+                        liquidity: burn.amount,
+                    }),
+                    ..Self::new(log, V3, Burn)?
+                })
+            }
+            CurvePool::TokenExchange::SIGNATURE_HASH => {
+                let exchange = log
+                    .log_decode::<CurvePool::TokenExchange>()
+                    .ok()?
+                    .inner
+                    .data;
+                Some(Self {
+                    sender: Some(exchange.buyer),
+                    swap: Some(SwapAmounts {
+                        token_in: exchange.sold_id.try_into().ok()?,
+                        token_out: exchange.bought_id.try_into().ok()?,
+                        amount_in: exchange.tokens_sold,
+                        amount_out: exchange.tokens_bought,
+                    }),
+                    ..Self::new(log, Curve, Swap)?
+                })
+            }
+            CurveCryptoPool::TokenExchange::SIGNATURE_HASH => {
+                let exchange = log
+                    .log_decode::<CurveCryptoPool::TokenExchange>()
+                    .ok()?
+                    .inner
+                    .data;
+                Some(Self {
+                    sender: Some(exchange.buyer),
+                    swap: Some(SwapAmounts {
+                        token_in: exchange.sold_id.try_into().ok()?,
+                        token_out: exchange.bought_id.try_into().ok()?,
+                        amount_in: exchange.tokens_sold,
+                        amount_out: exchange.tokens_bought,
+                    }),
+                    ..Self::new(log, Curve, Swap)?
+                })
+            }
+            CurveMetaPool::TokenExchangeUnderlying::SIGNATURE_HASH => {
+                let exchange = log
+                    .log_decode::<CurveMetaPool::TokenExchangeUnderlying>()
+                    .ok()?
+                    .inner
+                    .data;
+                Some(Self {
+                    sender: Some(exchange.buyer),
+                    swap: Some(SwapAmounts {
+                        token_in: exchange.sold_id.try_into().ok()?,
+                        token_out: exchange.bought_id.try_into().ok()?,
+                        amount_in: exchange.tokens_sold,
+                        amount_out: exchange.tokens_bought,
+                    }),
+                    ..Self::new(log, Curve, SwapUnderlying)?
+                })
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for PoolEvent {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {} #{} {} {} {}",
+            self.block_number, self.tx_hash, self.log_index, self.protocol, self.kind, self.pool
+        )?;
+
+        if let Some(swap) = &self.swap {
+            write!(
+                f,
+                " {} of {} -> {} of {}",
+                swap.amount_in, swap.token_in, swap.amount_out, swap.token_out
+            )?;
+        }
+        if let Some((amount0, amount1)) = &self.amounts {
+            write!(f, " amount0: {amount0} amount1: {amount1}")?;
+        }
+        if let Some(state) = &self.state {
+            write!(
+                f,
+                " sqrt_price_x96: {} liquidity: {} tick: {}",
+                state.sqrt_price_x96, state.liquidity, state.tick
+            )?;
+        }
+        if let Some(position) = &self.position {
+            write!(
+                f,
+                " position: {} [{}, {}] liquidity: {}",
+                position.owner, position.tick_lower, position.tick_upper, position.liquidity
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{aliases::I24, I256};
+
+    fn log(event: &impl SolEvent, pool: Address, log_index: u64) -> Log {
+        Log {
+            inner: alloy::primitives::Log {
+                address: pool,
+                data: event.encode_log_data(),
+            },
+            block_number: Some(100),
+            block_hash: Some(B256::repeat_byte(100)),
+            transaction_hash: Some(TxHash::repeat_byte(1)),
+            log_index: Some(log_index),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_decode_v2_swap() {
+        let swap = IUniswapV2Pool::Swap {
+            sender: Address::repeat_byte(2),
+            amount0In: U256::ZERO,
+            amount1In: U256::from(30),
+            amount0Out: U256::from(10),
+            amount1Out: U256::ZERO,
+            to: Address::repeat_byte(3),
+        };
+        let event = PoolEvent::decode(&log(&swap, Address::repeat_byte(9), 4)).unwrap();
+
+        assert_eq!(
+            (event.protocol, event.kind),
+            (Protocol::V2, EventKind::Swap)
+        );
+        assert_eq!(event.recipient, Some(Address::repeat_byte(3)));
+        assert_eq!(
+            event.swap,
+            Some(SwapAmounts {
+                token_in: 1,
+                token_out: 0,
+                amount_in: U256::from(30),
+                amount_out: U256::from(10),
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_v3_swap() {
+        let swap = IUniswapV3Pool::Swap {
+            sender: Address::repeat_byte(2),
+            recipient: Address::repeat_byte(3),
+            amount0: I256::try_from(50).unwrap(),
+            amount1: I256::try_from(-20).unwrap(),
+            sqrtPriceX96: Default::default(),
+            liquidity: 7,
+            tick: I24::try_from(-5).unwrap(),
+        };
+        let event = PoolEvent::decode(&log(&swap, Address::repeat_byte(9), 4)).unwrap();
+
+        assert_eq!(
+            (event.protocol, event.kind),
+            (Protocol::V3, EventKind::Swap)
+        );
+        assert_eq!(
+            event.swap,
+            Some(SwapAmounts {
+                token_in: 0,
+                token_out: 1,
+                amount_in: U256::from(50),
+                amount_out: U256::from(20),
+            })
+        );
+        assert_eq!(event.state.map(|state| state.tick), Some(-5));
+    }
+
+    #[test]
+    fn test_decode_v3_mint() {
+        let mint = IUniswapV3Pool::Mint {
+            sender: Address::repeat_byte(2),
+            owner: Address::repeat_byte(3),
+            tickLower: I24::try_from(-60).unwrap(),
+            tickUpper: I24::try_from(60).unwrap(),
+            amount: 1000,
+            amount0: U256::from(5),
+            amount1: U256::from(6),
+        };
+        let event = PoolEvent::decode(&log(&mint, Address::repeat_byte(9), 4)).unwrap();
+
+        assert_eq!(event.recipient, Some(Address::repeat_byte(3)));
+        assert_eq!(
+            event.position,
+            Some(Position {
+                owner: Address::repeat_byte(3),
+                tick_lower: -60,
+                tick_upper: 60,
+                liquidity: 1000,
+            })
+        );
+    }
+}
@@ -0,0 +1,235 @@
+use super::*;
+
+/// One swap of a trade, in token addresses rather than pool coin indices
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Hop {
+    pub pool: Address,
+    pub protocol: Protocol,
+    pub log_index: u64,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub amount_in: U256,
+    pub amount_out: U256,
+    /// Reserves after the swap, from the `Sync` a v2 pair emits along with it
+    pub reserves: Option<(U256, U256)>,
+}
+
+impl Hop {
+    /// `None` for pools without known coins, and for Curve underlying swaps whose indices
+    /// refer to the base pool coins
+    pub fn new(
+        event: &PoolEvent,
+        pool_tokens: &PoolTokens,
+        reserves: Option<(U256, U256)>,
+    ) -> Option<Self> {
+        if event.kind != EventKind::Swap {
+            return None;
+        }
+
+        let swap = event.swap.as_ref()?;
+        let tokens = pool_tokens.get(&event.pool)?;
+
+        Some(Self {
+            pool: event.pool,
+            protocol: event.protocol,
+            log_index: event.log_index,
+            token_in: *tokens.get(swap.token_in as usize)?,
+            token_out: *tokens.get(swap.token_out as usize)?,
+            amount_in: swap.amount_in,
+            amount_out: swap.amount_out,
+            reserves,
+        })
+    }
+}
+
+/// Swaps of one transaction chained into a route, each hop spending what the previous one bought
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Trade {
+    pub block_number: u64,
+    pub tx_hash: TxHash,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub amount_in: U256,
+    pub amount_out: U256,
+    pub hops: Vec<Hop>,
+}
+
+impl Trade {
+    fn new(event: &PoolEvent, hop: Hop) -> Self {
+        Self {
+            block_number: event.block_number,
+            tx_hash: event.tx_hash,
+            token_in: hop.token_in,
+            token_out: hop.token_out,
+            amount_in: hop.amount_in,
+            amount_out: hop.amount_out,
+            hops: vec![hop],
+        }
+    }
+
+    fn push(&mut self, hop: Hop) {
+        self.token_out = hop.token_out;
+        self.amount_out = hop.amount_out;
+        self.hops.push(hop);
+    }
+}
+
+impl Display for Trade {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} {}: {} {}",
+            self.block_number, self.tx_hash, self.amount_in, self.token_in
+        )?;
+        for hop in &self.hops[..self.hops.len() - 1] {
+            write!(f, " -> {}", hop.token_out)?;
+        }
+        write!(f, " -> {} {}", self.amount_out, self.token_out)?;
+
+        for hop in &self.hops {
+            write!(
+                f,
+                "\n  #{} {} {}: {} {} -> {} {}",
+                hop.log_index,
+                hop.protocol,
+                hop.pool,
+                hop.amount_in,
+                hop.token_in,
+                hop.amount_out,
+                hop.token_out
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Group events by transaction and chain the swaps of each into trades, in log order
+pub fn group_trades(events: &[PoolEvent], pool_tokens: &PoolTokens) -> Vec<Trade> {
+    let mut txs: Vec<Vec<&PoolEvent>> = vec![];
+    let mut tx_index: HashMap<TxHash, usize> = HashMap::new();
+
+    for event in events {
+        let idx = *tx_index.entry(event.tx_hash).or_insert_with(|| {
+            txs.push(vec![]);
+            txs.len() - 1
+        });
+        txs[idx].push(event);
+    }
+
+    txs.into_iter()
+        .flat_map(|mut events| {
+            events.sort_by_key(|event| event.log_index);
+            tx_trades(&events, pool_tokens)
+        })
+        .collect()
+}
+
+fn tx_trades(events: &[&PoolEvent], pool_tokens: &PoolTokens) -> Vec<Trade> {
+    let mut trades: Vec<Trade> = vec![];
+    // A v2 pair emits `Sync` right before the `Swap` that caused it
+    let mut syncs: HashMap<Address, (U256, U256)> = HashMap::new();
+
+    for event in events {
+        if event.kind == EventKind::Sync {
+            if let Some(reserves) = event.amounts {
+                syncs.insert(event.pool, reserves);
+            }
+            continue;
+        }
+
+        let reserves = match event.protocol {
+            Protocol::V2 => syncs.remove(&event.pool),
+            _ => None,
+        };
+        let Some(hop) = Hop::new(event, pool_tokens, reserves) else {
+            continue;
+        };
+
+        match trades.last_mut() {
+            Some(trade) if trade.token_out == hop.token_in => trade.push(hop),
+            _ => trades.push(Trade::new(event, hop)),
+        }
+    }
+
+    trades
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WETH: Address = Address::repeat_byte(1);
+    const USDC: Address = Address::repeat_byte(2);
+    const DAI: Address = Address::repeat_byte(3);
+    const PAIR: Address = Address::repeat_byte(10);
+    const POOL: Address = Address::repeat_byte(11);
+
+    fn event(tx: u8, log_index: u64, protocol: Protocol, kind: EventKind) -> PoolEvent {
+        PoolEvent {
+            block_number: 100,
+            block_hash: Some(B256::repeat_byte(100)),
+            tx_hash: TxHash::repeat_byte(tx),
+            log_index,
+            pool: if protocol == Protocol::V2 { PAIR } else { POOL },
+            protocol,
+            kind,
+            tx_from: None,
+            sender: None,
+            recipient: None,
+            swap: None,
+            amounts: None,
+            state: None,
+            position: None,
+        }
+    }
+
+    fn swap(event: PoolEvent, token_in: u64, amount_in: u64, amount_out: u64) -> PoolEvent {
+        PoolEvent {
+            swap: Some(SwapAmounts {
+                token_in,
+                token_out: 1 - token_in,
+                amount_in: U256::from(amount_in),
+                amount_out: U256::from(amount_out),
+            }),
+            ..event
+        }
+    }
+
+    #[test]
+    fn test_group_trades() {
+        let pool_tokens = PoolTokens::from([(PAIR, vec![WETH, USDC]), (POOL, vec![DAI, USDC])]);
+        let sync = PoolEvent {
+            amounts: Some((U256::from(7), U256::from(8))),
+            ..event(1, 0, Protocol::V2, EventKind::Sync)
+        };
+        let events = vec![
+            sync,
+            swap(event(1, 1, Protocol::V2, EventKind::Swap), 0, 10, 30_000),
+            // An unrelated transaction interleaved by log index
+            swap(event(2, 2, Protocol::V3, EventKind::Swap), 0, 5, 5),
+            swap(
+                event(1, 3, Protocol::V3, EventKind::Swap),
+                1,
+                30_000,
+                29_900,
+            ),
+        ];
+
+        let trades = group_trades(&events, &pool_tokens);
+        assert_eq!(trades.len(), 2);
+
+        let trade = &trades[0];
+        assert_eq!(trade.tx_hash, TxHash::repeat_byte(1));
+        assert_eq!((trade.token_in, trade.token_out), (WETH, DAI));
+        assert_eq!(
+            (trade.amount_in, trade.amount_out),
+            (U256::from(10), U256::from(29_900))
+        );
+        assert_eq!(trade.hops.len(), 2);
+        assert_eq!(trade.hops[0].reserves, Some((U256::from(7), U256::from(8))));
+        assert_eq!(trade.hops[1].reserves, None);
+
+        assert_eq!((trades[1].token_in, trades[1].token_out), (DAI, USDC));
+    }
+}
@@ -1,81 +1,119 @@
-use crate::{helper::*, structs::*};
+use alloy::{
+    consensus::Transaction,
+    primitives::Address,
+    providers::{Provider, ProviderBuilder, WsConnect},
+};
 use colored::Colorize;
-use futures::StreamExt;
-use std::{collections::HashMap, fs::File, io::BufReader};
-use utils::{CustomError, EnvParser};
-use web3::{
-    ethabi::{Address, Contract, Function},
-    signing::keccak256,
-    transports::WebSocket,
-    types::H160,
-    Web3,
+use decoder::{decode_swaps, Protocol};
+use futures_util::stream::StreamExt;
+use std::time::{Duration, Instant};
+use utils::{
+    metrics::{record_event, record_rpc_error, spawn_exporter, WS_RECONNECTS},
+    EnvParser,
 };
 
-mod helper;
-mod structs;
+/// Delay before resubscribing after a failed subscription, doubled on every failure in a row
+const RESUBSCRIBE_BACKOFF: Duration = Duration::from_millis(500);
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
     let env_parser = EnvParser::new()?;
 
-    let file = File::open("programs/identifier/resources/uniswap_routerv2_abi.json")?;
-    let reader = BufReader::new(file);
+    let ws = WsConnect::new(env_parser.ws_address.clone());
+    let provider = ProviderBuilder::new().connect_ws(ws).await?;
 
-    let contract = Contract::load(reader)?;
+    // Serve /metrics when METRICS_ADDRESS is set
+    spawn_exporter();
 
-    let ws = WebSocket::new(&env_parser.ws_address).await?;
-    let web3 = Web3::new(ws);
+    println!("Listening for pending Ethereum transactions...");
 
-    let mut sub = web3
-        .eth_subscribe()
-        .subscribe_new_pending_transactions()
-        .await?;
+    let mut retries = 0;
+    loop {
+        match listen(&provider, &env_parser).await {
+            Ok(()) => {
+                retries = 0;
+                eprintln!(
+                    "{}",
+                    "Pending transaction subscription ended, resubscribing...".red()
+                );
+            }
+            Err(e) => {
+                let delay = RESUBSCRIBE_BACKOFF * 2u32.pow(retries);
+                retries = (retries + 1).min(6);
+                eprintln!(
+                    "{}",
+                    format!("Pending transaction subscription failed ({e}), retrying in {delay:?}")
+                        .red()
+                );
+                tokio::time::sleep(delay).await;
+            }
+        }
+        WS_RECONNECTS.inc();
+    }
+}
 
-    let (identifiers, funcs, generator, names_map) = generate_maps(&contract)?;
+async fn listen(provider: &impl Provider, env_parser: &EnvParser) -> Result<(), anyhow::Error> {
+    let mut stream = provider
+        .subscribe_pending_transactions()
+        .await
+        .inspect_err(|_| record_rpc_error("eth_subscribe"))?
+        .into_stream();
 
-    println!("Listening for pending Ethereum transactions...");
+    while let Some(hash) = stream.next().await {
+        // Fetch full transaction details using the hash
+        let tx = match provider.get_transaction_by_hash(hash).await {
+            Ok(Some(tx)) => tx,
+            Ok(None) => continue,
+            Err(_) => {
+                record_rpc_error("eth_getTransactionByHash");
+                continue;
+            }
+        };
+        let received = Instant::now();
 
-    while let Some(tx_hash) = sub.next().await {
-        match tx_hash {
-            Ok(hash) => {
-                // Fetch full transaction details using the hash
-                {
-                    if let Ok(Some(tx)) = web3
-                        .eth()
-                        .transaction(web3::types::TransactionId::Hash(hash))
-                        .await
-                    {
-                        if tx.input.0.len() > 4 {
-                            let selector = hex::encode(&tx.input.0[..4]);
-                            let input_data = &tx.input.0[4..];
-                            if let Some(idx) = identifiers.iter().position(|x| selector.eq(x)) {
-                                if let Some(swap_fn) = generator.get(&selector) {
-                                    let names = names_map.get(&identifiers[idx]).unwrap();
-                                    println!("{}", selector);
-                                    println!("{}", hex::encode(input_data));
-                                    swap_fn(funcs[idx], &input_data, names, &env_parser.pools)?;
-                                    if let Some(from) = tx.from {
-                                        if env_parser.pools.contains(&from) {
-                                            println!("{}", format!("Found: {from:?}").green());
-                                        }
-                                    }
-                                    if let Some(to) = tx.to {
-                                        if env_parser.pools.contains(&to) {
-                                            println!("{}", format!("Found: {to:?}").green());
-                                        }
-                                    }
-                                    println!("hash: {:?}", tx.hash);
-                                    println!("from: {:?}", tx.from);
-                                    println!("to {:?}", tx.to);
-                                    println!("{}", "=".repeat(70));
-                                }
-                            }
-                        }
-                    }
-                }
+        let intents = match decode_swaps(tx.input(), tx.value()) {
+            Ok(intents) => intents,
+            Err(e) => {
+                eprintln!("Failed to decode {hash:?}: {e:?}");
+                continue;
             }
-            Err(e) => eprintln!("Subscription error: {:?}", e),
+        };
+
+        if intents.is_empty() {
+            continue;
         }
+
+        let found = |address: &Address| {
+            if env_parser.pools.contains(address) {
+                println!("{}", format!("Found: {address:?}").green());
+            }
+        };
+
+        for intent in intents {
+            let protocol = match intent.protocol {
+                Protocol::UniswapV2 => "v2",
+                Protocol::UniswapV3 => "v3",
+            };
+            record_event("pending_swap", protocol, received);
+
+            println!("{intent}");
+            intent
+                .path
+                .iter()
+                .chain(std::iter::once(&intent.recipient))
+                .for_each(&found);
+        }
+
+        let from = tx.inner.signer();
+        found(&from);
+        if let Some(to) = tx.to() {
+            found(&to);
+        }
+
+        println!("hash: {hash:?}");
+        println!("from: {from:?}");
+        println!("to {:?}", tx.to());
+        println!("{}", "=".repeat(70));
     }
 
     Ok(())
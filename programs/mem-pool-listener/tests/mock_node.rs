@@ -0,0 +1,113 @@
+use alloy::{
+    consensus::{transaction::Recovered, SignableTransaction, TxEnvelope, TxLegacy},
+    network::TxSignerSync,
+    primitives::{address, b256, Address, TxKind, U256},
+    rpc::types::Transaction,
+    signers::local::PrivateKeySigner,
+    sol_types::SolCall,
+};
+use decoder::IUniswapV2Router02;
+use mock_node::{Fixtures, MockNode};
+use std::{process::Stdio, time::Duration};
+use tokio::{
+    io::{AsyncBufReadExt, BufReader},
+    process::Command,
+    time::timeout,
+};
+
+const UNISWAP_V2_ROUTER: Address = address!("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D");
+const WETH: Address = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+const USDC: Address = address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+const RECIPIENT: Address = address!("0x1000000000000000000000000000000000000001");
+
+/// A signed router swap of 1 WETH to USDC, as a node returns it while pending
+fn pending_swap() -> Transaction {
+    let signer = PrivateKeySigner::from_bytes(&b256!(
+        "0x0000000000000000000000000000000000000000000000000000000000000001"
+    ))
+    .unwrap();
+    let call = IUniswapV2Router02::swapExactTokensForTokensCall {
+        amountIn: U256::from(10).pow(U256::from(18)),
+        amountOutMin: U256::ZERO,
+        path: vec![WETH, USDC],
+        to: RECIPIENT,
+        deadline: U256::MAX,
+    };
+    let mut tx = TxLegacy {
+        chain_id: Some(1),
+        nonce: 0,
+        gas_price: 1_000_000_000,
+        gas_limit: 300_000,
+        to: TxKind::Call(UNISWAP_V2_ROUTER),
+        value: U256::ZERO,
+        input: call.abi_encode().into(),
+    };
+    let signature = signer.sign_transaction_sync(&mut tx).unwrap();
+
+    Transaction {
+        inner: Recovered::new_unchecked(
+            TxEnvelope::Legacy(tx.into_signed(signature)),
+            signer.address(),
+        ),
+        block_hash: None,
+        block_number: None,
+        transaction_index: None,
+        effective_gas_price: None,
+    }
+}
+
+#[tokio::test]
+async fn listener_decodes_pending_swaps() {
+    let tx = pending_swap();
+    let hash = *tx.inner.tx_hash();
+    let node = MockNode::spawn(Fixtures {
+        block_number: 100,
+        transactions: vec![tx],
+        ..Default::default()
+    })
+    .await
+    .unwrap();
+
+    // The listener reads `resources/pools.json` from its working directory
+    let workdir = std::env::temp_dir().join(format!("mem-pool-mock-node-{}", std::process::id()));
+    std::fs::create_dir_all(workdir.join("resources")).unwrap();
+    std::fs::write(
+        workdir.join("resources/pools.json"),
+        format!(r#"["{RECIPIENT}"]"#),
+    )
+    .unwrap();
+
+    let mut listener = Command::new(env!("CARGO_BIN_EXE_mem-pool-listener"))
+        .current_dir(&workdir)
+        .env("WEBSOCKET_ENDPOINT", node.ws_address())
+        .env_remove("METRICS_ADDRESS")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+
+    let mut lines = BufReader::new(listener.stdout.take().unwrap()).lines();
+    let output = timeout(Duration::from_secs(30), async {
+        let mut output = Vec::new();
+        while let Some(line) = lines.next_line().await.unwrap() {
+            let done = line.starts_with("hash:");
+            output.push(line);
+            if done {
+                return output;
+            }
+        }
+        panic!("listener exited early: {output:?}");
+    })
+    .await
+    .expect("listener did not report the pending swap");
+
+    assert!(output.contains(&"name: swapExactTokensForTokens".to_string()));
+    assert!(output
+        .iter()
+        .any(|line| line.contains(&format!("Found: {RECIPIENT:?}"))));
+    assert_eq!(output.last().unwrap(), &format!("hash: {hash:?}"));
+
+    listener.kill().await.unwrap();
+    std::fs::remove_dir_all(workdir).unwrap();
+}
@@ -0,0 +1,120 @@
+use super::*;
+
+/// A pair priced apart in two pools, buying `base` on `buy` and selling it on `sell`
+#[derive(Debug, Clone, Serialize)]
+pub struct Divergence {
+    pub block_number: u64,
+    pub base: Address,
+    pub quote: Address,
+    pub buy: Quote,
+    pub sell: Quote,
+    pub divergence_bps: f64,
+    pub net_bps: f64,
+}
+
+impl Divergence {
+    fn new(block_number: u64, buy: Quote, sell: Quote) -> Self {
+        let divergence_bps = (sell.price / buy.price - 1.0) * 10_000.0;
+
+        Self {
+            block_number,
+            base: buy.base,
+            quote: buy.quote,
+            buy,
+            sell,
+            divergence_bps,
+            net_bps: divergence_bps - buy.fee_bps - sell.fee_bps,
+        }
+    }
+}
+
+/// The widest divergence between two pools of every pair clearing `threshold_bps` after fees.
+/// Pools of the same protocol count as separate venues, like Uniswap and Sushiswap v2 pairs or
+/// two v3 fee tiers.
+pub fn detect(block_number: u64, quotes: &[Quote], threshold_bps: f64) -> Vec<Divergence> {
+    let mut pairs: HashMap<(Address, Address), Vec<&Quote>> = HashMap::new();
+
+    for quote in quotes {
+        pairs.entry(quote.pair()).or_default().push(quote);
+    }
+
+    let mut divergences: Vec<Divergence> = pairs
+        .into_values()
+        .filter_map(|venues| {
+            venues
+                .iter()
+                .flat_map(|buy| venues.iter().map(move |sell| (*buy, *sell)))
+                .filter(|(buy, sell)| buy.pool != sell.pool && buy.price < sell.price)
+                .map(|(buy, sell)| Divergence::new(block_number, *buy, *sell))
+                .max_by(|a, b| a.net_bps.total_cmp(&b.net_bps))
+        })
+        .filter(|divergence| divergence.net_bps > threshold_bps)
+        .collect();
+
+    divergences.sort_by(|a, b| b.net_bps.total_cmp(&a.net_bps));
+    divergences
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::address;
+    use solver::enums::Protocol;
+
+    const WETH: Address = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    const USDC: Address = address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+
+    fn quote(pool: u8, protocol: Protocol, price: f64, fee_bps: f64) -> Quote {
+        Quote::new(
+            Address::with_last_byte(pool),
+            protocol,
+            WETH,
+            USDC,
+            price,
+            fee_bps,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn flags_divergence_after_fees() {
+        let quotes = [
+            quote(1, Protocol::UniswapV2, 2000.0, 30.0),
+            quote(2, Protocol::UniswapV3, 2020.0, 5.0),
+        ];
+
+        // 1% apart, 65 bps left once both fees are paid
+        let divergences = detect(1, &quotes, 50.0);
+        assert_eq!(divergences.len(), 1);
+        assert_eq!(divergences[0].sell.protocol, Protocol::UniswapV2);
+        assert!((divergences[0].net_bps - 65.0).abs() < 1e-6);
+
+        assert!(detect(1, &quotes, 70.0).is_empty());
+    }
+
+    #[test]
+    fn flags_pools_of_the_same_protocol() {
+        // A Uniswap and a Sushiswap pair, then two v3 fee tiers
+        let v2 = [
+            quote(1, Protocol::UniswapV2, 2000.0, 30.0),
+            quote(2, Protocol::UniswapV2, 2200.0, 30.0),
+        ];
+        let v3 = [
+            quote(3, Protocol::UniswapV3, 2000.0, 5.0),
+            quote(4, Protocol::UniswapV3, 2100.0, 30.0),
+        ];
+
+        assert_eq!(detect(1, &v2, 0.0).len(), 1);
+        assert_eq!(detect(1, &v3, 0.0).len(), 1);
+    }
+
+    #[test]
+    fn ignores_quotes_of_the_same_pool() {
+        let quotes = [
+            quote(1, Protocol::UniswapV2, 2000.0, 30.0),
+            quote(1, Protocol::UniswapV2, 2200.0, 30.0),
+        ];
+
+        assert!(detect(1, &quotes, 0.0).is_empty());
+    }
+}
@@ -1,13 +0,0 @@
-#[derive(Debug, Clone, Copy)]
-pub enum TxType {
-    Add,
-    Remove,
-    Swap,
-    Sync,
-}
-
-impl Default for TxType {
-    fn default() -> Self {
-        Self::Sync
-    }
-}
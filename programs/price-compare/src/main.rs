@@ -1,64 +1,26 @@
-use crate::{
-    enums::*,
-    fetch::*,
-    parser::*,
-    pools::*,
-    scanner::*,
-    slippage::*,
-    structs::*,
-    IUniswapV2Pool::{Burn, Mint, Swap, Sync},
-};
+use crate::{divergence::*, parser::*, scanner::*};
 use alloy::{
-    primitives::{Address, TxHash, Uint, U256},
-    providers::{
-        fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller},
-        Identity, Provider, ProviderBuilder, RootProvider, WsConnect,
-    },
-    rpc::types::{Filter, Log},
-    sol,
+    eips::BlockId,
+    primitives::Address,
+    providers::{Provider, ProviderBuilder, WsConnect},
+    rpc::types::Filter,
 };
-use colored::Colorize;
 use dotenv::dotenv;
 use futures_util::stream::StreamExt;
-use serde::{Deserialize, Serialize};
-use serde_json::from_reader;
-use std::{
-    collections::HashMap,
-    env,
-    fmt::Display,
-    fs::{File, OpenOptions},
-    io::{BufReader, Write},
-    sync::Arc,
+use serde::Serialize;
+use solver::{
+    parser::EnvParser,
+    pools::{curve, load_pools, token_metadata_to_tokens, v2, v3, CurvePools, TokenMap},
+    quote::{collect_quotes, Quote},
+    scanner::apply_log,
+    SolverProvider,
 };
+use std::{collections::HashMap, env, fs::OpenOptions, io::Write, time::Instant};
+use utils::{debug_time, info_time, CustomError};
 
-use tokio::sync::Mutex;
-use utils::CustomError;
-
-type U112 = Uint<112, 2>;
-
-mod enums;
-mod fetch;
+mod divergence;
 mod parser;
-#[macro_use]
-mod logger;
-mod pools;
 mod scanner;
-mod slippage;
-mod structs;
-
-sol!(
-    #[sol(rpc)]
-    #[derive(Debug)]
-    IUniswapV2Pool,
-    "../../resources/contracts/uniswapv2_pool_abi.json"
-);
-
-sol!(
-    #[sol(rpc)]
-    #[derive(Debug)]
-    IUniswapV2Pair,
-    "../../resources/contracts/uniswapv2_pair.json"
-);
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
@@ -70,47 +32,30 @@ async fn main() -> Result<(), anyhow::Error> {
     info_time!("main()", {
         // Load environment variables from .env file
         let env_parser = info_time!("env_parser", { EnvParser::new()? });
+        let config = Config::new()?;
 
         // Set up the WS transport and connect.
-        let ws = WsConnect::new(env_parser.ws_address);
+        let ws = WsConnect::new(env_parser.ws_address.as_str());
         let provider = ProviderBuilder::new().connect_ws(ws).await?;
 
-        let file = debug_time!("file_open()", {
-            File::open("resources/tokens_to_pool_weth.json")?
-        });
-        let reader = debug_time!("reader()", { BufReader::new(file) });
-        let pools: Vec<Pools> = debug_time!("pools_serialize()", { from_reader(reader)? });
-        let pool_data: PoolData = debug_time!("update_reserves", {
-            update_reserves(provider.clone(), pools).await?
+        let token_map: TokenMap = debug_time!("token_map creation()", {
+            token_metadata_to_tokens(&env_parser.token_metadata)
         });
 
-        let mut file = OpenOptions::new()
-            .write(true)
-            .create(true)
-            .append(true)
-            .open("resources/run.txt")?;
-
-        file.write_all(SearchData::headers().as_bytes())?;
-
-        pool_data.iter().for_each(|(pool_addr, token_data)| {
-            let scan_data = SearchData::new(
-                u64::default(),
-                pool_addr.clone(),
-                token_data.token_a,
-                token_data.token_b,
-                token_data.reserve0,
-                token_data.reserve1,
-            );
-            let _ = file.write_all(scan_data.to_string().as_bytes());
+        let (pool_data_v2, pool_data_v3, curve_pool_data) = debug_time!("load_pools()", {
+            load_pools(&provider, &env_parser, &token_map).await?
         });
 
-        // Scanning the ethereum blockchain for events
+        // Compare venue prices on every new block
         debug_time!("Calling scanner()", {
             scan(
-                provider.clone(),
-                env_parser.pools_addrs,
-                pool_data,
-                &mut file,
+                &provider,
+                &env_parser,
+                &token_map,
+                pool_data_v2,
+                pool_data_v3,
+                curve_pool_data,
+                &config,
             )
             .await?
         });
@@ -1,29 +1,25 @@
 use super::*;
 
-pub struct EnvParser {
-    pub ws_address: String,
-    pub pools_addrs: Vec<Address>,
+/// Basis points a divergence must clear after both pool fees
+const DEFAULT_THRESHOLD_BPS: f64 = 10.0;
+
+pub struct Config {
+    pub threshold_bps: f64,
+    pub output_path: Option<String>,
 }
 
-impl<'a> EnvParser {
+impl<'a> Config {
     pub fn new() -> Result<Self, CustomError<'a>> {
         dotenv().ok();
 
-        // Open the file with contract addresses
-        let file = File::open("resources/pools-weth.json")?;
-        let reader = BufReader::new(file);
-
-        // Parse and decode addresses
-        let addresses: Vec<String> = from_reader(reader)?;
-
-        let pools_addrs: Vec<Address> = addresses
-            .iter()
-            .map(|s| s.parse())
-            .collect::<Result<_, _>>()?;
+        let threshold_bps = match env::var("DIVERGENCE_THRESHOLD_BPS") {
+            Ok(value) => value.parse()?,
+            Err(_) => DEFAULT_THRESHOLD_BPS,
+        };
 
         Ok(Self {
-            ws_address: env::var("WEBSOCKET_ENDPOINT")?,
-            pools_addrs,
+            threshold_bps,
+            output_path: env::var("DIVERGENCE_OUTPUT_PATH").ok(),
         })
     }
 }
@@ -1,67 +1,100 @@
 use super::*;
 
-#[derive(Debug, Deserialize, Serialize, Clone, Copy)]
-pub struct InputData {
-    pub token_a: Address,
-    pub token_b: Address,
-    pub amount_in: U256,
+/// Refetch balances of the curve pools that emitted events in the block, as of that block
+async fn refresh_curve<'a>(
+    provider: &SolverProvider,
+    block: BlockId,
+    mut pools: Vec<CurvePools>,
+    token_map: &TokenMap,
+    curve_pool_data: &mut curve::PoolData,
+) -> Result<(), CustomError<'a>> {
+    if pools.is_empty() {
+        return Ok(());
+    }
+
+    CurvePools::fetch_balances(provider, &mut pools, block).await;
+    curve_pool_data
+        .data
+        .extend(curve::PoolData::new(&pools, token_map)?.data);
+
+    Ok(())
 }
 
 pub async fn scan<'a>(
-    provider: FillProvider<
-        JoinFill<
-            Identity,
-            JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>,
-        >,
-        RootProvider,
-    >,
-    pool_addresses: Vec<Address>,
-    pool_data: PoolData,
-    file: &mut File,
+    provider: &SolverProvider,
+    env_parser: &EnvParser,
+    token_map: &TokenMap,
+    mut pool_data_v2: v2::PoolData,
+    mut pool_data_v3: v3::PoolData,
+    mut curve_pool_data: curve::PoolData,
+    config: &Config,
 ) -> Result<(), CustomError<'a>> {
-    // Create a filter for the events.
-    let filter = provider
-        .clone()
-        .subscribe_logs(&Filter::new().address(pool_addresses))
-        .await?;
+    let curve_pools: HashMap<Address, CurvePools> = env_parser
+        .curve_pools
+        .iter()
+        .map(|pool| (pool.address, pool.clone()))
+        .collect();
+    let mut pool_addresses = env_parser.pool_address.single();
+    pool_addresses.extend(curve_pools.keys());
 
-    log::info!("Waiting for events...");
+    let mut output = match &config.output_path {
+        Some(path) => Some(OpenOptions::new().create(true).append(true).open(path)?),
+        None => None,
+    };
+
+    let mut stream = provider.subscribe_blocks().await?.into_stream();
+
+    log::info!("Waiting for blocks...");
+
+    while let Some(header) = stream.next().await {
+        let filter = Filter::new()
+            .at_block_hash(header.hash)
+            .address(pool_addresses.clone());
+        let logs = provider.get_logs(&filter).await?;
+        let received = Instant::now();
+
+        let mut touched_curve = Vec::new();
+        for log in &logs {
+            if let Some(pool) = curve_pools.get(&log.address()) {
+                if !touched_curve
+                    .iter()
+                    .any(|p: &CurvePools| p.address == pool.address)
+                {
+                    touched_curve.push(pool.clone());
+                }
+            } else {
+                apply_log(log, &mut pool_data_v2, &mut pool_data_v3, received)?;
+            }
+        }
 
-    let mut stream = filter.into_stream();
+        debug_time!("scan::refresh_curve()", {
+            refresh_curve(
+                provider,
+                BlockId::hash(header.hash),
+                touched_curve,
+                token_map,
+                &mut curve_pool_data,
+            )
+            .await?
+        });
 
-    // Create a shared state for the current amount
-    let pool_data = Arc::new(Mutex::new(pool_data));
+        let quotes = collect_quotes(&pool_data_v2, &pool_data_v3, &curve_pool_data);
+        let divergences = detect(header.number, &quotes, config.threshold_bps);
 
-    // Process events from the stream
-    while let Some(log) = stream.next().await {
-        let mut scanner = ScanData::new(&log);
+        log::info!(
+            "Block {}: {} quotes, {} divergences",
+            header.number,
+            quotes.len(),
+            divergences.len()
+        );
 
-        if let Ok(decoded) = log.log_decode() {
-            let swap: Swap = decoded.inner.data;
-            scanner.update_swap(swap, decoded.inner.address);
-        } else if let Ok(decoded) = log.log_decode() {
-            let sync: Sync = decoded.inner.data;
-            scanner.update_sync(sync, decoded.inner.address);
+        for divergence in divergences {
+            let record = serde_json::to_string(&divergence)?;
+            println!("{record}");
 
-            // Update reserves based on the event
-            update_reserve_abs(scanner, &mut *pool_data.lock().await)?;
-            let data = pool_data.lock().await;
-            let token_data = data.get(&scanner.pool_address).unwrap();
-            let scan_data = SearchData::new(
-                log.block_number.unwrap_or_default(),
-                scanner.pool_address,
-                token_data.token_a,
-                token_data.token_b,
-                token_data.reserve0,
-                token_data.reserve1,
-            );
-            file.write_all(scan_data.to_string().as_bytes())?;
-        } else if let Ok(decoded) = log.log_decode() {
-            let mint: Mint = decoded.inner.data;
-            scanner.update_liquidity_events(mint, decoded.inner.address);
-        } else if let Ok(decoded) = log.log_decode() {
-            let burn: Burn = decoded.inner.data;
-            scanner.update_liquidity_events(burn, decoded.inner.address);
+            if let Some(file) = output.as_mut() {
+                writeln!(file, "{record}")?;
+            }
         }
     }
 
@@ -0,0 +1,121 @@
+use serde::de::DeserializeOwned;
+use solver::{
+    fetch::Reserves,
+    parser::{TickData, TickDataReader, TickMap},
+    pools::*,
+};
+use std::{fs::File, io::BufReader};
+use uniswap_sdk_core::prelude::BigInt;
+
+const RESOURCES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../../resources");
+
+// Edges of the benchmarked graph, reached by copying the v2 and v3 pools of the snapshot
+// rather than by loading that many real pools
+pub const GRAPH_EDGES: usize = 10_000;
+
+fn read<T: DeserializeOwned>(name: &str) -> T {
+    let file = File::open(format!("{RESOURCES}/{name}")).unwrap();
+    serde_json::from_reader(BufReader::new(file)).unwrap()
+}
+
+/// `pool` under the address of its `replica`th copy, the original for the first
+fn replicate(pool: &Pools, replica: usize) -> Pools {
+    Pools {
+        address: match replica {
+            0 => pool.address,
+            _ => pool.address.create(replica as u64),
+        },
+        ..pool.clone()
+    }
+}
+
+/// The pool sets the solver loads from `resources/`, grown to [`GRAPH_EDGES`] edges.
+///
+/// This is not mainnet-scale data. The snapshot files hold no v2 reserves, so every pair is
+/// given a made-up million tokens a side. The v2 and v3 pools are copied under fake addresses
+/// that only differ in name. The numbers measure the cost of pricing and searching a graph of
+/// this size, not how a real pool set of this size prices or routes.
+pub struct Snapshot {
+    pub pool_data_v2: v2::PoolData,
+    pub pool_data_v3: v3::PoolData,
+    pub curve_pool_data: curve::PoolData,
+}
+
+impl Snapshot {
+    pub fn load() -> Self {
+        let token_metadata: Vec<TokenMetadata> = read("token_metadata_combined.json");
+        let token_map = token_metadata_to_tokens(&token_metadata);
+
+        let pools_v2: Vec<Pools> = read("uniswapv2_tokens_to_pool.json");
+        let pools_v3: Vec<Pools> = read("uniswapv3_tokens_to_pool.json");
+        let ticks: Vec<TickDataReader> = read("ticks.json");
+        let curve_pools: Vec<CurvePools> = read("curve_tokens_to_pool.json");
+
+        // Pools with a token the metadata does not cover are skipped at load, as in the solver
+        let curve_pools: Vec<CurvePools> = curve_pools
+            .into_iter()
+            .filter(|pool| {
+                pool.tokens
+                    .iter()
+                    .all(|token| token_map.contains_key(token))
+            })
+            .collect();
+        let curve_edges: usize = curve_pools
+            .iter()
+            .map(|pool| pool.tokens.len() * (pool.tokens.len() - 1))
+            .sum();
+        let edges_per_copy = 2 * (pools_v2.len() + pools_v3.len());
+        let replicas = GRAPH_EDGES
+            .saturating_sub(curve_edges)
+            .div_ceil(edges_per_copy);
+
+        let pools_v2: Vec<Pools> = (0..replicas)
+            .flat_map(|replica| pools_v2.iter().map(move |pool| replicate(pool, replica)))
+            .collect();
+
+        // Every copy of a v3 pool shares its tick table
+        let ticks: TickMap = ticks
+            .into_iter()
+            .map(|ticks| (ticks.pool, TickData::from(ticks)))
+            .collect();
+        let mut tick_map = TickMap::new();
+        let pools_v3: Vec<Pools> = (0..replicas)
+            .flat_map(|replica| {
+                pools_v3
+                    .iter()
+                    .map(move |pool| (pool.address, replicate(pool, replica)))
+            })
+            .map(|(original, pool)| {
+                if let Some(tick_data) = ticks.get(&original) {
+                    tick_map.insert(pool.address, tick_data.clone());
+                }
+                pool
+            })
+            .collect();
+
+        let mut pool_data_v2 = v2::PoolData::new(&pools_v2, &token_map).unwrap();
+        // Reserves are not part of the snapshot files, so every pair holds a million tokens a side
+        for token_data in pool_data_v2.data.values_mut() {
+            token_data.update_reserves(Reserves {
+                reserve0: whole_tokens(1_000_000, token_data.token0.token.decimals),
+                reserve1: whole_tokens(1_000_000, token_data.token1.token.decimals),
+            });
+        }
+        pool_data_v2.calc_start_price().unwrap();
+
+        let mut pool_data_v3 = v3::PoolData::new(&pools_v3, &token_map).unwrap();
+        pool_data_v3.calc_start_price(&tick_map).unwrap();
+
+        let curve_pool_data = curve::PoolData::new(&curve_pools, &token_map).unwrap();
+
+        Self {
+            pool_data_v2,
+            pool_data_v3,
+            curve_pool_data,
+        }
+    }
+}
+
+pub fn whole_tokens(amount: u128, decimals: u8) -> BigInt {
+    BigInt::from(amount) * BigInt::from(10u128.pow(u32::from(decimals)))
+}
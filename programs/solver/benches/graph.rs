@@ -0,0 +1,103 @@
+use alloy::primitives::{address, Address};
+use common::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+use futures::executor::block_on;
+use solver::{
+    constants::WETH,
+    dijkstra::{best_path, SwapGraph},
+    filter::PoolFilter,
+    scanner::build_swap_graph,
+};
+
+mod common;
+
+const USDC: Address = address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+
+/// A filter keeping every pool. The v2 reserves are made up, so the depth and consensus checks
+/// would otherwise drop most of the pairs the graph is meant to hold.
+fn pool_filter() -> PoolFilter {
+    PoolFilter {
+        min_tvl: 0.0,
+        min_reserve: 0.0,
+        min_liquidity_v3: 0,
+        max_price_deviation_bps: f64::INFINITY,
+        ..Default::default()
+    }
+}
+
+fn build_swap_graph_benchmark(c: &mut Criterion) {
+    let Snapshot {
+        mut pool_data_v2,
+        mut pool_data_v3,
+        mut curve_pool_data,
+    } = Snapshot::load();
+    let pool_filter = pool_filter();
+    let amount = whole_tokens(1, 18);
+
+    c.bench_function("build_swap_graph", |b| {
+        b.iter(|| {
+            block_on(build_swap_graph(
+                &mut pool_data_v2,
+                &mut pool_data_v3,
+                &mut curve_pool_data,
+                &pool_filter,
+                amount,
+            ))
+            .unwrap()
+        })
+    });
+}
+
+fn to_swap_graph_benchmark(c: &mut Criterion) {
+    let Snapshot {
+        pool_data_v2,
+        pool_data_v3,
+        mut curve_pool_data,
+    } = Snapshot::load();
+    // Curve edges are read from the slippage of each coin pair
+    curve_pool_data.calc_slippage(whole_tokens(1, 18));
+
+    c.bench_function("to_swap_graph", |b| {
+        b.iter(|| {
+            let mut graph = SwapGraph::new();
+            pool_data_v2.to_swap_graph(&mut graph);
+            pool_data_v3.to_swap_graph(&mut graph);
+            curve_pool_data.to_swap_graph(&mut graph);
+            graph
+        })
+    });
+}
+
+fn best_path_benchmark(c: &mut Criterion) {
+    let Snapshot {
+        mut pool_data_v2,
+        mut pool_data_v3,
+        mut curve_pool_data,
+    } = Snapshot::load();
+    let (graph, slippage_adj) = block_on(build_swap_graph(
+        &mut pool_data_v2,
+        &mut pool_data_v3,
+        &mut curve_pool_data,
+        &pool_filter(),
+        whole_tokens(1, 18),
+    ))
+    .unwrap();
+    let edges: usize = graph.values().map(Vec::len).sum();
+    assert!(edges >= GRAPH_EDGES, "graph has {edges} edges");
+
+    c.bench_function("best_path", |b| {
+        b.iter(|| best_path(&graph, &WETH, &USDC, slippage_adj))
+    });
+
+    c.bench_function("best_path_cycle", |b| {
+        b.iter(|| best_path(&graph, &WETH, &WETH, slippage_adj))
+    });
+}
+
+criterion_group!(
+    name = benches;
+    // A pass over every pool of the replicated set takes up to a second
+    config = Criterion::default().sample_size(10);
+    targets = build_swap_graph_benchmark, to_swap_graph_benchmark, best_path_benchmark
+);
+criterion_main!(benches);
@@ -0,0 +1,47 @@
+use common::*;
+use criterion::{criterion_group, criterion_main, Criterion};
+use futures::executor::block_on;
+
+mod common;
+
+fn calc_effective_price_v2_benchmark(c: &mut Criterion) {
+    let Snapshot {
+        mut pool_data_v2, ..
+    } = Snapshot::load();
+    let amount = whole_tokens(1, 18);
+
+    c.bench_function("v2_calc_effective_price", |b| {
+        b.iter(|| pool_data_v2.calc_effective_price(amount).unwrap())
+    });
+}
+
+fn calc_effective_price_v3_benchmark(c: &mut Criterion) {
+    let Snapshot {
+        mut pool_data_v3, ..
+    } = Snapshot::load();
+    let amount = whole_tokens(1, 18);
+
+    c.bench_function("v3_calc_effective_price", |b| {
+        b.iter(|| block_on(pool_data_v3.calc_effective_price(amount)).unwrap())
+    });
+}
+
+fn calc_slippage_curve_benchmark(c: &mut Criterion) {
+    let Snapshot {
+        mut curve_pool_data,
+        ..
+    } = Snapshot::load();
+    let amount = whole_tokens(1, 18);
+
+    c.bench_function("curve_calc_slippage", |b| {
+        b.iter(|| curve_pool_data.calc_slippage(amount))
+    });
+}
+
+criterion_group!(
+    name = benches;
+    // A pass over every pool of the replicated set takes up to a second
+    config = Criterion::default().sample_size(10);
+    targets = calc_effective_price_v2_benchmark, calc_effective_price_v3_benchmark, calc_slippage_curve_benchmark
+);
+criterion_main!(benches);
@@ -0,0 +1,545 @@
+use super::*;
+use alloy::{consensus::Transaction, primitives::B256};
+use decoder::{decode_swaps, Protocol};
+use uniswap_v2_sdk::prelude::compute_pair_address;
+use utils::metrics::record_rpc_error;
+
+pub use decoder::SwapAmount;
+
+/// Token and probe size the backrun search starts and ends its cycles with
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct BackrunConfig {
+    pub token: Address,
+    pub amount_in: U256,
+    #[serde(default)]
+    pub min_profit: U256,
+}
+
+/// A UniswapV2Router swap waiting in the mempool
+#[derive(Debug, Clone)]
+pub struct PendingSwap {
+    pub tx_hash: B256,
+    pub factory: Address,
+    pub path: Vec<Address>,
+    pub amount: SwapAmount,
+}
+
+impl PendingSwap {
+    /// The V2 swaps in a router call, ETH legs take their amount from the tx value
+    pub fn decode(tx_hash: B256, to: Address, input: &[u8], value: U256) -> Vec<PendingSwap> {
+        let factory = match to {
+            UNISWAP_V2_ROUTER => UNISWAP_V2_FACTORY,
+            SUSHI_V2_ROUTER => SUSHI_V2_FACTORY,
+            _ => return vec![],
+        };
+
+        decode_swaps(input, value)
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|intent| intent.protocol == Protocol::UniswapV2 && intent.path.len() >= 2)
+            .map(|intent| PendingSwap {
+                tx_hash,
+                factory,
+                path: intent.path,
+                amount: intent.amount,
+            })
+            .collect()
+    }
+
+    pub fn pairs(&self) -> Vec<Address> {
+        self.path
+            .windows(2)
+            .map(|tokens| compute_pair_address(self.factory, tokens[0], tokens[1]))
+            .collect()
+    }
+
+    /// Replay the swap on `pool_data_v2`, failing wherever the router would revert
+    pub fn apply<'a>(&self, pool_data_v2: &mut v2::PoolData) -> Result<(), CustomError<'a>> {
+        let pairs = self.pairs();
+        let hops: Vec<_> = pairs.iter().zip(self.path.windows(2)).collect();
+
+        let amounts = match self.amount {
+            SwapAmount::ExactIn {
+                amount_in,
+                amount_out_min,
+            } => {
+                let mut amounts = vec![amount_in.to_big_int()];
+                for (pair, tokens) in hops.iter() {
+                    let token_data = pool_data_v2
+                        .data
+                        .get(*pair)
+                        .ok_or_else(|| CustomError::AddressNotFound(**pair))?;
+                    let amount = amounts[amounts.len() - 1];
+                    amounts.push(token_data.get_amount_out(&tokens[0], amount)?);
+                }
+
+                if amounts[amounts.len() - 1] < amount_out_min.to_big_int() {
+                    return Err(CustomError::SimulationError(
+                        "pending swap reverts: insufficient output amount".to_string(),
+                    ));
+                }
+                amounts
+            }
+            SwapAmount::ExactOut {
+                amount_out,
+                amount_in_max,
+            } => {
+                let mut amounts = vec![amount_out.to_big_int()];
+                for (pair, tokens) in hops.iter().rev() {
+                    let token_data = pool_data_v2
+                        .data
+                        .get(*pair)
+                        .ok_or_else(|| CustomError::AddressNotFound(**pair))?;
+                    let amount = amounts[amounts.len() - 1];
+                    amounts.push(token_data.get_amount_in(&tokens[1], amount)?);
+                }
+                amounts.reverse();
+
+                if amounts[0] > amount_in_max.to_big_int() {
+                    return Err(CustomError::SimulationError(
+                        "pending swap reverts: excessive input amount".to_string(),
+                    ));
+                }
+                amounts
+            }
+        };
+
+        for ((pair, tokens), amount) in hops.iter().zip(amounts.windows(2)) {
+            pool_data_v2
+                .data
+                .get_mut(*pair)
+                .ok_or_else(|| CustomError::AddressNotFound(**pair))?
+                .apply_swap(&tokens[0], amount[0], amount[1])?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+pub struct BackrunOpportunity {
+    pub tx_hash: B256,
+    pub path: ShortestPath,
+    pub amount_in: U256,
+    pub amount_out: U256,
+    pub profit: BigInt,
+}
+
+impl Display for BackrunOpportunity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "tx_hash: {}", self.tx_hash)?;
+        writeln!(f, "path: {:?}", self.path.paths)?;
+        writeln!(f, "pools: {:?}", self.path.pools)?;
+        writeln!(f, "amount_in: {}", self.amount_in)?;
+        writeln!(f, "amount_out: {}", self.amount_out)?;
+        write!(f, "profit: {}", self.profit)
+    }
+}
+
+/// Best path between two tokens, where a token reaches itself without any hop
+fn segment(
+    graph: &SwapGraph,
+    from: &Address,
+    to: &Address,
+    slippage_adj: BigInt,
+) -> Option<ShortestPath> {
+    if from == to {
+        return Some(ShortestPath::new(vec![*from], vec![], vec![], BigInt::ZERO));
+    }
+
+    let path = best_path(graph, from, to, slippage_adj);
+    (!path.pools.is_empty()).then_some(path)
+}
+
+/// Pool state priced once per block, which pending swaps are searched against
+pub struct BackrunBase {
+    pub state: PoolState,
+    pub graph: SwapGraph,
+    pub slippage_adj: BigInt,
+}
+
+impl BackrunBase {
+    pub async fn new<'a>(
+        mut state: PoolState,
+        pool_filter: &PoolFilter,
+        amount_in: BigInt,
+    ) -> Result<Self, CustomError<'a>> {
+        let (graph, slippage_adj) = build_swap_graph(
+            &mut state.pool_data_v2,
+            &mut state.pool_data_v3,
+            &mut state.curve_pool_data,
+            pool_filter,
+            amount_in,
+        )
+        .await?;
+
+        Ok(Self {
+            state,
+            graph,
+            slippage_adj,
+        })
+    }
+
+    /// The graph with `skip` left out and the pairs in `repriced` carrying their new slippage
+    fn graph_for(&self, repriced: &[Address], skip: &Address) -> SwapGraph {
+        self.graph
+            .iter()
+            .map(|(token, edges)| {
+                let edges = edges
+                    .iter()
+                    .filter(|edge| edge.pool != *skip)
+                    .map(|edge| match repriced.contains(&edge.pool) {
+                        true => self.repriced_edge(token, edge),
+                        false => edge.clone(),
+                    })
+                    .collect();
+                (*token, edges)
+            })
+            .collect()
+    }
+
+    fn repriced_edge(&self, from: &Address, edge: &SwapEdge) -> SwapEdge {
+        let token_data = &self.state.pool_data_v2.data[&edge.pool];
+        let slippage = match token_data.token0.token.address() == *from {
+            true => token_data.token0.slippage,
+            false => token_data.token1.slippage,
+        };
+
+        SwapEdge::new(edge.to, edge.pool, slippage, edge.fee)
+    }
+}
+
+/// Search the state left behind by `swap` for the most profitable cycle through a pair it moves
+pub fn search_backrun<'a>(
+    base: &mut BackrunBase,
+    swap: &PendingSwap,
+    config: &BackrunConfig,
+) -> Result<Option<BackrunOpportunity>, CustomError<'a>> {
+    let pairs = swap.pairs();
+    let pre_swap: Vec<(Address, v2::TokenData)> = pairs
+        .iter()
+        .filter_map(|pair| {
+            let token_data = base.state.pool_data_v2.data.get(pair)?;
+            Some((*pair, token_data.clone()))
+        })
+        .collect();
+
+    let result = search_post_swap(base, &pairs, swap, config);
+
+    // Only the pairs the swap moved were touched, put them back for the next swap
+    for (pair, token_data) in pre_swap {
+        base.state.pool_data_v2.data.insert(pair, token_data);
+    }
+
+    result
+}
+
+fn search_post_swap<'a>(
+    base: &mut BackrunBase,
+    pairs: &[Address],
+    swap: &PendingSwap,
+    config: &BackrunConfig,
+) -> Result<Option<BackrunOpportunity>, CustomError<'a>> {
+    swap.apply(&mut base.state.pool_data_v2)?;
+
+    let amount_in = config.amount_in.to_big_int();
+
+    // Reprice the moved pairs only, the adjustment can grow but keeps every other edge non-negative
+    let mut slippage_adj = Some(BigInt::ONE - base.slippage_adj);
+    for pair in pairs {
+        let token_data = base
+            .state
+            .pool_data_v2
+            .data
+            .get_mut(pair)
+            .ok_or_else(|| CustomError::AddressNotFound(*pair))?;
+        token_data.calc_start_price()?;
+        token_data.calc_effective_price(amount_in)?;
+        token_data.calc_slippage(&mut slippage_adj);
+    }
+    let slippage_adj = slippage_adj.unwrap_or_default().abs() + BigInt::ONE;
+
+    let mut best: Option<BackrunOpportunity> = None;
+
+    for pair in pairs {
+        let token_data = &base.state.pool_data_v2.data[pair];
+        let token0 = token_data.token0.token.address();
+        let token1 = token_data.token1.token.address();
+
+        // The legs around the moved pair must not route through it again
+        let graph_without_pair = base.graph_for(pairs, pair);
+
+        for (token_in, token_out) in [(token0, token1), (token1, token0)] {
+            let (Some(prefix), Some(suffix)) = (
+                segment(&graph_without_pair, &config.token, &token_in, slippage_adj),
+                segment(&graph_without_pair, &token_out, &config.token, slippage_adj),
+            ) else {
+                continue;
+            };
+
+            let mut paths = prefix.paths;
+            paths.extend(suffix.paths);
+            let mut pools = prefix.pools;
+            pools.push(*pair);
+            pools.extend(suffix.pools);
+            let mut fees = prefix.fees;
+            fees.push(token_data.fee);
+            fees.extend(suffix.fees);
+            let cycle = ShortestPath::new(paths, pools, fees, prefix.cost + suffix.cost);
+
+            let Ok(hops) = quote_hops(
+                &base.state.pool_data_v2,
+                &base.state.pool_data_v3,
+                &base.state.curve_pool_data,
+                &cycle,
+                config.amount_in,
+            ) else {
+                continue;
+            };
+            let amount_out = hops.last().map(|hop| hop.amountOut).unwrap_or_default();
+            let profit = amount_out.to_big_int() - amount_in;
+
+            if best.as_ref().is_none_or(|b| profit > b.profit) {
+                best = Some(BackrunOpportunity {
+                    tx_hash: swap.tx_hash,
+                    path: cycle,
+                    amount_in: config.amount_in,
+                    amount_out,
+                    profit,
+                });
+            }
+        }
+    }
+
+    Ok(best.filter(|opportunity| opportunity.profit > config.min_profit.to_big_int()))
+}
+
+/// Follow pending transactions and report backruns of the V2 router swaps among them
+pub async fn watch_mempool<'a>(
+    provider: &SolverProvider,
+    shared: SharedState,
+    pool_filter: &PoolFilter,
+    config: BackrunConfig,
+) -> Result<(), CustomError<'a>> {
+    let subscription = provider.subscribe_pending_transactions().await?;
+    let mut stream = subscription.into_stream();
+    let mut base: Option<BackrunBase> = None;
+
+    log::info!("Watching mempool for backruns...");
+
+    while let Some(tx_hash) = stream.next().await {
+        let tx = match provider.get_transaction_by_hash(tx_hash).await {
+            Ok(Some(tx)) => tx,
+            Ok(None) => continue,
+            Err(_) => {
+                record_rpc_error("eth_getTransactionByHash");
+                continue;
+            }
+        };
+        let Some(to) = tx.to() else {
+            continue;
+        };
+        let swaps = PendingSwap::decode(tx_hash, to, tx.input(), tx.value());
+        if swaps.is_empty() {
+            continue;
+        }
+
+        log::info!("Pending v2 swap captured, tx: {tx_hash}");
+
+        // The locks are held for the copy only, the graph is priced again once per block
+        let block = *shared.block.lock().await;
+        if base.as_ref().is_none_or(|base| base.state.block != block) {
+            let state = shared.snapshot().await;
+            base = match BackrunBase::new(state, pool_filter, config.amount_in.to_big_int()).await {
+                Ok(base) => Some(base),
+                Err(e) => {
+                    log::debug!("No backrun base at block {block}: {e}");
+                    continue;
+                }
+            };
+        }
+        let Some(base) = base.as_mut() else {
+            continue;
+        };
+
+        for swap in swaps {
+            let result = debug_time!("watch_mempool::search_backrun()", {
+                search_backrun(base, &swap, &config)
+            });
+
+            match result {
+                Ok(Some(opportunity)) => println!("Backrun opportunity:\n{opportunity}"),
+                Ok(None) => {}
+                Err(e) => log::debug!("No backrun for {tx_hash}: {e}"),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolCall;
+
+    const TOKEN_A: Address = address!("0x1000000000000000000000000000000000000001");
+    const TOKEN_B: Address = address!("0x2000000000000000000000000000000000000002");
+    const POOL_2: Address = address!("0x00000000000000000000000000000000000000A2");
+
+    fn ether(amount: u128) -> U256 {
+        U256::from(amount * PRECISION)
+    }
+
+    fn pair() -> Address {
+        compute_pair_address(UNISWAP_V2_FACTORY, TOKEN_A, TOKEN_B)
+    }
+
+    fn create_pool_data() -> v2::PoolData {
+        let mut tokens = TokenMap::new();
+        tokens.insert(TOKEN_A, token!(1, TOKEN_A, 18));
+        tokens.insert(TOKEN_B, token!(1, TOKEN_B, 18));
+
+        let pools: Vec<Pools> = [pair(), POOL_2]
+            .iter()
+            .map(|address| Pools {
+                token0: TOKEN_A,
+                token1: TOKEN_B,
+                fee: 3000,
+                address: *address,
+            })
+            .collect();
+
+        let mut pool_data_v2 = v2::PoolData::new(&pools, &tokens).unwrap();
+        for token_data in pool_data_v2.data.values_mut() {
+            token_data.reserve0 = ether(1000).to_big_int();
+            token_data.reserve1 = ether(1000).to_big_int();
+        }
+
+        pool_data_v2
+    }
+
+    fn exact_in_swap(amount_in: U256, amount_out_min: U256) -> PendingSwap {
+        let input = IUniswapV2Router::swapExactTokensForTokensCall {
+            amountIn: amount_in,
+            amountOutMin: amount_out_min,
+            path: vec![TOKEN_A, TOKEN_B],
+            to: Address::ZERO,
+            deadline: U256::MAX,
+        }
+        .abi_encode();
+
+        PendingSwap::decode(B256::ZERO, UNISWAP_V2_ROUTER, &input, U256::ZERO).remove(0)
+    }
+
+    #[test]
+    fn test_decode_pending_swaps() {
+        let swap = exact_in_swap(ether(100), ether(1));
+        assert_eq!(swap.factory, UNISWAP_V2_FACTORY);
+        assert_eq!(swap.path, vec![TOKEN_A, TOKEN_B]);
+        assert_eq!(swap.pairs(), vec![pair()]);
+        assert_eq!(
+            swap.amount,
+            SwapAmount::ExactIn {
+                amount_in: ether(100),
+                amount_out_min: ether(1)
+            }
+        );
+
+        let input = IUniswapV2Router::swapETHForExactTokensCall {
+            amountOut: ether(5),
+            path: vec![TOKEN_A, TOKEN_B],
+            to: Address::ZERO,
+            deadline: U256::MAX,
+        }
+        .abi_encode();
+        let swap = PendingSwap::decode(B256::ZERO, SUSHI_V2_ROUTER, &input, ether(6)).remove(0);
+        assert_eq!(swap.factory, SUSHI_V2_FACTORY);
+        assert_eq!(
+            swap.amount,
+            SwapAmount::ExactOut {
+                amount_out: ether(5),
+                amount_in_max: ether(6)
+            }
+        );
+
+        // Unknown routers and non-swap calls are ignored
+        assert!(PendingSwap::decode(B256::ZERO, POOL_2, &input, U256::ZERO).is_empty());
+        assert!(
+            PendingSwap::decode(B256::ZERO, UNISWAP_V2_ROUTER, &[0u8; 4], U256::ZERO).is_empty()
+        );
+    }
+
+    #[test]
+    fn test_apply_pending_swap() {
+        let mut pool_data_v2 = create_pool_data();
+        exact_in_swap(ether(100), ether(1))
+            .apply(&mut pool_data_v2)
+            .unwrap();
+
+        let token_data = &pool_data_v2.data[&pair()];
+        assert_eq!(token_data.reserve0, ether(1100).to_big_int());
+        // 1000 - 100 * 997 * 1000 / (1000 * 1000 + 100 * 997)
+        assert_eq!(
+            token_data.reserve1,
+            BigInt::from(909_338_910_611_985_086_842u128)
+        );
+        assert_eq!(
+            pool_data_v2.data[&POOL_2].reserve0,
+            ether(1000).to_big_int()
+        );
+
+        // A swap that would revert leaves no state to search
+        let mut pool_data_v2 = create_pool_data();
+        let result = exact_in_swap(ether(100), ether(100)).apply(&mut pool_data_v2);
+        assert!(matches!(result, Err(CustomError::SimulationError(_))));
+    }
+
+    #[tokio::test]
+    async fn test_search_backrun() {
+        let state = PoolState {
+            token_map: TokenMap::new(),
+            pool_data_v2: create_pool_data(),
+            pool_data_v3: v3::PoolData {
+                data: HashMap::new(),
+            },
+            curve_pool_data: curve::PoolData {
+                data: HashMap::new(),
+            },
+            block: 0,
+        };
+        let config = BackrunConfig {
+            token: TOKEN_A,
+            amount_in: ether(10),
+            min_profit: U256::ZERO,
+        };
+        let pool_filter = PoolFilter {
+            min_tvl: 0.0,
+            min_reserve: 0.0,
+            ..Default::default()
+        };
+
+        let mut base = BackrunBase::new(state, &pool_filter, config.amount_in.to_big_int())
+            .await
+            .unwrap();
+        let swap = exact_in_swap(ether(100), ether(1));
+        let opportunity = search_backrun(&mut base, &swap, &config).unwrap().unwrap();
+
+        // B is cheap on the untouched pool and dear on the pair the swap bought it from
+        assert_eq!(opportunity.path.paths, vec![TOKEN_A, TOKEN_B, TOKEN_A]);
+        assert_eq!(opportunity.path.pools, vec![POOL_2, pair()]);
+        assert!(opportunity.profit > BigInt::ZERO);
+
+        // The pair is put back, so the same swap finds the same backrun again
+        let token_data = &base.state.pool_data_v2.data[&pair()];
+        assert_eq!(token_data.reserve0, ether(1000).to_big_int());
+        let again = search_backrun(&mut base, &swap, &config).unwrap().unwrap();
+        assert_eq!(again.profit, opportunity.profit);
+
+        // A swap that reverts is no backrun and leaves the pair as it was
+        let reverting = exact_in_swap(ether(100), ether(100));
+        assert!(search_backrun(&mut base, &reverting, &config).is_err());
+        let token_data = &base.state.pool_data_v2.data[&pair()];
+        assert_eq!(token_data.reserve0, ether(1000).to_big_int());
+    }
+}
@@ -0,0 +1,518 @@
+use super::*;
+use alloy::providers::{ProviderBuilder, WsConnect};
+use std::{
+    fs::OpenOptions,
+    io::{BufRead, BufWriter, Write},
+    path::{Path, PathBuf},
+    time::Instant,
+};
+use utils::backfill::Backfill;
+
+/// Where a backtest reads its inputs and writes its report, and what it searches for.
+/// Missing snapshot or archive files are captured from the node once, later runs are offline.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BacktestConfig {
+    pub snapshot_path: PathBuf,
+    pub archive_path: PathBuf,
+    pub report_path: PathBuf,
+    /// Block the snapshot is taken at, replay starts with the block after it
+    pub block: u64,
+    pub to_block: u64,
+    /// Equal tokens search for cycles, different ones for routes
+    pub token_a: Address,
+    pub token_b: Address,
+    pub amount_in: U256,
+    #[serde(default)]
+    pub min_profit: U256,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReservesSnapshot {
+    pub pool: Address,
+    pub reserve0: U256,
+    pub reserve1: U256,
+}
+
+/// State of every configured pool at a single block
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoolSnapshot {
+    pub block: u64,
+    pub reserves_v2: Vec<ReservesSnapshot>,
+    pub ticks: Vec<TickDataReader>,
+    pub curve_pools: Vec<CurvePools>,
+}
+
+impl PoolSnapshot {
+    /// Read the state of the pools in `env_parser` as of `block`
+    pub async fn capture<'a>(
+        provider: &SolverProvider,
+        env_parser: &EnvParser,
+        block: u64,
+    ) -> Result<Self, CustomError<'a>> {
+        let pools_v2 = &env_parser.pool_address.v2;
+        let reserves = snapshot::aggregate(
+            provider,
+            block,
+            pools_v2
+                .iter()
+                .map(|pool| (*pool, IUniswapV2Pair::getReservesCall {}))
+                .collect(),
+        )
+        .await?;
+        let reserves_v2 = pools_v2
+            .iter()
+            .zip(reserves)
+            .filter_map(|(pool, reserves)| {
+                let reserves = reserves?;
+                Some(ReservesSnapshot {
+                    pool: *pool,
+                    reserve0: U256::from(reserves._reserve0),
+                    reserve1: U256::from(reserves._reserve1),
+                })
+            })
+            .collect();
+
+        let ticks = snapshot::snapshot_ticks(provider, &env_parser.pool_address.v3, block).await?;
+
+        let mut curve_pools = env_parser.curve_pools.clone();
+        CurvePools::fetch_balances(provider, &mut curve_pools, BlockId::number(block)).await;
+
+        Ok(Self {
+            block,
+            reserves_v2,
+            ticks,
+            curve_pools,
+        })
+    }
+
+    pub fn load<'a>(path: &Path) -> Result<Self, CustomError<'a>> {
+        Ok(from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    pub fn save<'a>(&self, path: &Path) -> Result<(), CustomError<'a>> {
+        let mut writer = BufWriter::new(File::create(path)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Price `pools_v2` and `pools_v3` from the snapshot, the way `load_pools` does from the node
+    pub fn pool_data<'a>(
+        &self,
+        pools_v2: &[Pools],
+        pools_v3: &[Pools],
+        token_map: &TokenMap,
+    ) -> Result<(v2::PoolData, v3::PoolData, curve::PoolData), CustomError<'a>> {
+        let mut pool_data_v2 = v2::PoolData::new(pools_v2, token_map)?;
+        for reserves in &self.reserves_v2 {
+            if let Some(token_data) = pool_data_v2.data.get_mut(&reserves.pool) {
+                token_data.update_reserves(Reserves {
+                    reserve0: reserves.reserve0.to_big_int(),
+                    reserve1: reserves.reserve1.to_big_int(),
+                });
+            }
+        }
+        pool_data_v2.calc_start_price()?;
+
+        let tick_map: TickMap = self
+            .ticks
+            .iter()
+            .map(|tdr| (tdr.pool, TickData::from(tdr.clone())))
+            .collect();
+        let mut pool_data_v3 = v3::PoolData::new(pools_v3, token_map)?;
+        pool_data_v3.calc_start_price(&tick_map)?;
+
+        let curve_pool_data = curve::PoolData::new(&self.curve_pools, token_map)?;
+
+        Ok((pool_data_v2, pool_data_v3, curve_pool_data))
+    }
+}
+
+/// Append the logs of `pools` in `from_block..=to_block` to the archive, one JSON log per line.
+/// A cursor next to the archive lets an interrupted recording carry on where it stopped.
+pub async fn record_archive<'a>(
+    provider: &SolverProvider,
+    pools: Vec<Address>,
+    from_block: u64,
+    to_block: u64,
+    path: &Path,
+) -> Result<(), CustomError<'a>> {
+    let cursor_path = path.with_extension("cursor");
+    let mut writer = BufWriter::new(OpenOptions::new().create(true).append(true).open(path)?);
+    let backfill = Backfill {
+        cursor_path: Some(cursor_path.clone()),
+        ..Backfill::new(Filter::new().address(pools))
+    };
+
+    backfill
+        .run(provider, from_block, to_block, |logs, to| {
+            for log in &logs {
+                serde_json::to_writer(&mut writer, log)?;
+                writer.write_all(b"\n")?;
+            }
+            writer.flush()?;
+
+            log::info!("Archived {} logs up to block {to}", logs.len());
+            Ok::<_, CustomError>(())
+        })
+        .await?;
+
+    std::fs::remove_file(cursor_path)?;
+
+    Ok(())
+}
+
+/// Logs of an archive written by [`record_archive`], in chain order.
+/// A recording resumed after a crash repeats the logs flushed after the last saved cursor,
+/// so only the first copy of each `(block_number, log_index)` is kept.
+pub fn read_archive<'a>(path: &Path) -> Result<Vec<Log>, CustomError<'a>> {
+    let mut seen = HashSet::new();
+    let mut logs: Vec<Log> = Vec::new();
+    for line in BufReader::new(File::open(path)?).lines() {
+        let line = line?;
+        if !line.trim().is_empty() {
+            let log: Log = serde_json::from_str(&line)?;
+            if seen.insert((log.block_number, log.log_index)) {
+                logs.push(log);
+            }
+        }
+    }
+    logs.sort_by_key(|log| (log.block_number, log.log_index));
+
+    Ok(logs)
+}
+
+/// Best route or cycle found after a block's logs were applied
+#[derive(Debug, Clone, Serialize)]
+pub struct Opportunity {
+    pub tokens: Vec<Address>,
+    pub pools: Vec<Address>,
+    pub amount_in: U256,
+    pub amount_out: U256,
+    /// Output less input, only set for cycles
+    pub profit: Option<String>,
+}
+
+/// One line of the backtest report
+#[derive(Debug, Clone, Serialize)]
+pub struct BlockReport {
+    pub block: u64,
+    pub logs: usize,
+    pub opportunity: Option<Opportunity>,
+    pub search_micros: u128,
+}
+
+async fn search<'a>(
+    pool_data_v2: &mut v2::PoolData,
+    pool_data_v3: &mut v3::PoolData,
+    curve_pool_data: &mut curve::PoolData,
+    pool_filter: &PoolFilter,
+    config: &BacktestConfig,
+) -> Result<Option<Opportunity>, CustomError<'a>> {
+    let (graph, slippage_adj) = build_swap_graph(
+        pool_data_v2,
+        pool_data_v3,
+        curve_pool_data,
+        pool_filter,
+        config.amount_in.to_big_int(),
+    )
+    .await?;
+
+    let path = best_path(&graph, &config.token_a, &config.token_b, slippage_adj);
+    if path.pools.is_empty() {
+        return Ok(None);
+    }
+
+    let hops = quote_hops(
+        pool_data_v2,
+        pool_data_v3,
+        curve_pool_data,
+        &path,
+        config.amount_in,
+    )?;
+    let amount_out = hops.last().map(|hop| hop.amountOut).unwrap_or_default();
+
+    let profit = path
+        .is_cycle()
+        .then(|| amount_out.to_big_int() - config.amount_in.to_big_int());
+    if profit.is_some_and(|profit| profit <= config.min_profit.to_big_int()) {
+        return Ok(None);
+    }
+
+    Ok(Some(Opportunity {
+        tokens: path.paths,
+        pools: path.pools,
+        amount_in: config.amount_in,
+        amount_out,
+        profit: profit.map(|profit| profit.to_string()),
+    }))
+}
+
+/// Replay archived `logs` on top of the snapshotted pools block by block, through the scanner's
+/// `apply_log`, and write what the search finds after each block to `report` as JSON lines.
+/// Logs at or before `snapshot_block` are already part of the state and skipped.
+/// Every block up to `config.to_block` gets a line, blocks without logs repeat the last result.
+#[allow(clippy::too_many_arguments)]
+pub async fn backtest<'a>(
+    pool_data_v2: &mut v2::PoolData,
+    pool_data_v3: &mut v3::PoolData,
+    curve_pool_data: &mut curve::PoolData,
+    pool_filter: &PoolFilter,
+    logs: &[Log],
+    snapshot_block: u64,
+    config: &BacktestConfig,
+    report: &mut impl Write,
+) -> Result<Vec<BlockReport>, CustomError<'a>> {
+    let logs: Vec<&Log> = logs
+        .iter()
+        .filter(|log| log.block_number.unwrap_or_default() > snapshot_block)
+        .collect();
+
+    let last_block = logs
+        .last()
+        .and_then(|log| log.block_number)
+        .map_or(config.to_block, |block| block.max(config.to_block));
+
+    let mut reports: Vec<BlockReport> = Vec::new();
+    let mut remaining = logs.as_slice();
+    for block in snapshot_block + 1..=last_block {
+        let count = remaining
+            .iter()
+            .take_while(|log| log.block_number == Some(block))
+            .count();
+        let (block_logs, rest) = remaining.split_at(count);
+        remaining = rest;
+        for log in block_logs {
+            apply_log(log, pool_data_v2, pool_data_v3, Instant::now())?;
+        }
+
+        // Without logs the pools are unchanged, and so is what the search finds
+        let started = Instant::now();
+        let opportunity = match reports.last() {
+            Some(previous) if block_logs.is_empty() => previous.opportunity.clone(),
+            _ => {
+                search(
+                    pool_data_v2,
+                    pool_data_v3,
+                    curve_pool_data,
+                    pool_filter,
+                    config,
+                )
+                .await?
+            }
+        };
+
+        let block_report = BlockReport {
+            block,
+            logs: block_logs.len(),
+            opportunity,
+            search_micros: started.elapsed().as_micros(),
+        };
+        serde_json::to_writer(&mut *report, &block_report)?;
+        report.write_all(b"\n")?;
+        reports.push(block_report);
+    }
+    report.flush()?;
+
+    Ok(reports)
+}
+
+/// Run the backtest in `config`, capturing its snapshot and archive first if they are missing
+pub async fn run_backtest<'a>(
+    env_parser: &EnvParser,
+    token_map: &TokenMap,
+    config: &BacktestConfig,
+) -> Result<(), CustomError<'a>> {
+    let archive_cursor = config.archive_path.with_extension("cursor");
+    if !config.snapshot_path.exists() || !config.archive_path.exists() || archive_cursor.exists() {
+        let ws = WsConnect::new(env_parser.ws_address.as_str());
+        let provider = ProviderBuilder::new().connect_ws(ws).await?;
+
+        if !config.snapshot_path.exists() {
+            log::info!("Capturing pool snapshot at block {}", config.block);
+            PoolSnapshot::capture(&provider, env_parser, config.block)
+                .await?
+                .save(&config.snapshot_path)?;
+        }
+        if !config.archive_path.exists() || archive_cursor.exists() {
+            log::info!(
+                "Recording logs of blocks {}-{}",
+                config.block + 1,
+                config.to_block
+            );
+            record_archive(
+                &provider,
+                env_parser.pool_address.single(),
+                config.block + 1,
+                config.to_block,
+                &config.archive_path,
+            )
+            .await?;
+        }
+    }
+
+    let snapshot = PoolSnapshot::load(&config.snapshot_path)?;
+    let (mut pool_data_v2, mut pool_data_v3, mut curve_pool_data) =
+        snapshot.pool_data(&env_parser.pools_v2, &env_parser.pools_v3, token_map)?;
+    let logs = read_archive(&config.archive_path)?;
+
+    let mut report = BufWriter::new(File::create(&config.report_path)?);
+    let reports = backtest(
+        &mut pool_data_v2,
+        &mut pool_data_v3,
+        &mut curve_pool_data,
+        &env_parser.pool_filter,
+        &logs,
+        snapshot.block,
+        config,
+        &mut report,
+    )
+    .await?;
+
+    log::info!(
+        "Replayed {} logs over {} blocks, {} opportunities written to {}",
+        logs.len(),
+        reports.len(),
+        reports.iter().filter(|r| r.opportunity.is_some()).count(),
+        config.report_path.display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::{primitives::Log as PrimitiveLog, sol_types::SolEvent};
+    use alloy_primitives::aliases::U112;
+
+    const WETH: Address = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    const USDC: Address = address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const POOL: Address = address!("0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc");
+
+    fn sync(block: u64, reserve0: u128, reserve1: u128) -> Log {
+        let event = IUniswapV2Pool::Sync {
+            reserve0: U112::from(reserve0),
+            reserve1: U112::from(reserve1),
+        };
+
+        Log {
+            inner: PrimitiveLog {
+                address: POOL,
+                data: event.encode_log_data(),
+            },
+            block_number: Some(block),
+            ..Default::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn replays_blocks_after_the_snapshot() {
+        let token_map: TokenMap = [
+            (USDC, token!(1, USDC, 6, "USDC")),
+            (WETH, token!(1, WETH, 18, "WETH")),
+        ]
+        .into_iter()
+        .collect();
+        let pools = [Pools {
+            token0: USDC,
+            token1: WETH,
+            fee: 3000,
+            address: POOL,
+        }];
+        let snapshot = PoolSnapshot {
+            block: 100,
+            reserves_v2: vec![ReservesSnapshot {
+                pool: POOL,
+                reserve0: U256::from(3_000_000_000_000u128), // 3m USDC
+                reserve1: U256::from(1_000_000_000_000_000_000_000u128), // 1000 WETH
+            }],
+            ticks: vec![],
+            curve_pools: vec![],
+        };
+        let (mut pool_data_v2, mut pool_data_v3, mut curve_pool_data) =
+            snapshot.pool_data(&pools, &[], &token_map).unwrap();
+
+        let config = BacktestConfig {
+            snapshot_path: PathBuf::new(),
+            archive_path: PathBuf::new(),
+            report_path: PathBuf::new(),
+            block: 100,
+            to_block: 103,
+            token_a: WETH,
+            token_b: USDC,
+            amount_in: U256::from(10).pow(U256::from(18)),
+            min_profit: U256::ZERO,
+        };
+        // The first log is already in the snapshot, the last doubles the USDC side, 103 has none
+        let logs = [
+            sync(100, 1, 1),
+            sync(101, 3_000_000_000_000, 1_000_000_000_000_000_000_000),
+            sync(102, 6_000_000_000_000, 1_000_000_000_000_000_000_000),
+        ];
+
+        let mut report = Vec::new();
+        let reports = backtest(
+            &mut pool_data_v2,
+            &mut pool_data_v3,
+            &mut curve_pool_data,
+            &PoolFilter::default(),
+            &logs,
+            snapshot.block,
+            &config,
+            &mut report,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            reports.iter().map(|r| r.block).collect::<Vec<_>>(),
+            vec![101, 102, 103]
+        );
+        let out = |r: &BlockReport| r.opportunity.as_ref().unwrap().amount_out;
+        assert!(out(&reports[1]) > out(&reports[0]) * U256::from(19) / U256::from(10));
+        assert_eq!(reports[2].logs, 0);
+        assert_eq!(out(&reports[2]), out(&reports[1]));
+        assert!(reports
+            .iter()
+            .all(|r| r.opportunity.as_ref().unwrap().profit.is_none()));
+        assert_eq!(String::from_utf8(report).unwrap().lines().count(), 3);
+    }
+
+    #[test]
+    fn read_archive_drops_logs_repeated_on_resume() {
+        let path = std::env::temp_dir().join(format!(
+            "backtest_archive_test-{}.jsonl",
+            std::process::id()
+        ));
+        let log = |block: u64, index: u64| Log {
+            log_index: Some(index),
+            ..sync(block, block as u128, 1)
+        };
+        // Block 102 was flushed before the crash and written again after resuming
+        let archived = [
+            log(101, 0),
+            log(102, 0),
+            log(102, 1),
+            log(102, 0),
+            log(102, 1),
+            log(103, 0),
+        ];
+        let lines: Vec<String> = archived
+            .iter()
+            .map(|log| serde_json::to_string(log).unwrap())
+            .collect();
+        std::fs::write(&path, lines.join("\n")).unwrap();
+
+        let logs = read_archive(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            logs.iter()
+                .map(|log| (log.block_number.unwrap(), log.log_index.unwrap()))
+                .collect::<Vec<_>>(),
+            vec![(101, 0), (102, 0), (102, 1), (103, 0)]
+        );
+    }
+}
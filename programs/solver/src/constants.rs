@@ -1,2 +1,33 @@
+use super::*;
+
 // Constant for precision 10^18
 pub const PRECISION: u128 = 1_000_000_000_000_000_000u128;
+
+// Scale of `A_precise()` over `A()` in Curve pools that expose it
+pub const A_PRECISION: u128 = 100;
+
+// Denominator of Curve pool fees
+pub const FEE_DENOMINATOR: u128 = 10_000_000_000;
+
+// Base token pools are valued in
+pub const WETH: Address = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+
+// Flash loan providers on mainnet
+pub const BALANCER_VAULT: Address = address!("0xBA12222222228d8Ba445958a75a0704d566BF2C8");
+pub const AAVE_V3_POOL: Address = address!("0x87870Bca3F3fD6335C3F4ce8392D69350B4fA4E2");
+
+// Aave v3 flash loan premium in bps
+pub const AAVE_FLASH_PREMIUM_BPS: u64 = 5;
+
+// Periphery used as ground truth when simulating quotes
+pub const UNISWAP_V2_FACTORY: Address = address!("0x5C69bEe701ef814a2B6a3EDD4B1652CB9cc5aA6f");
+pub const SUSHI_V2_FACTORY: Address = address!("0xC0AEe478e3658e2610c5F7A4A2E1777cE9e4f2Ac");
+pub const UNISWAP_V2_ROUTER: Address = address!("0x7a250d5630B4cF539739dF2C5dAcb4c659F2488D");
+pub const SUSHI_V2_INIT_CODE_HASH: B256 =
+    b256!("0xe18a34eb0e04b04f7a0ac29a6e80748dca96319b42c520a4c87b6b8bbb2fb8aa");
+pub const SUSHI_V2_ROUTER: Address = address!("0xd9e1cE17f2641f24aE83637ab66a2cca9C378B9F");
+pub const UNISWAP_V3_QUOTER_V2: Address = address!("0x61fFE014bA17989E743c5F6cB21bF9697530B21e");
+
+// Factories and registries announcing new pools
+pub const UNISWAP_V3_FACTORY: Address = address!("0x1F98431c8aD98523631AE4a59f267346ea31F984");
+pub const CURVE_REGISTRY: Address = address!("0x90E00ACe148ca3b23Ac1bC8C240C2a7Dd9c2d7f5");
@@ -1,3 +1,5 @@
+#![allow(clippy::too_many_arguments)]
+
 use super::*;
 
 sol!(
@@ -14,6 +16,13 @@ sol!(
     "../../resources/contracts/uniswapv2_pair.json"
 );
 
+sol!(
+    #[sol(rpc)]
+    #[derive(Debug)]
+    IUniswapV2Factory,
+    "../../resources/contracts/uniswapv2_factory.json"
+);
+
 sol!(
     #[sol(rpc)]
     #[derive(Debug)]
@@ -48,3 +57,31 @@ sol!(
     CurvePool1,
     "../../resources/contracts/curve_pool_1.json"
 );
+
+sol!(
+    #[sol(rpc)]
+    #[derive(Debug)]
+    CurveMetaPool,
+    "../../resources/contracts/curve_meta_contract.json"
+);
+
+sol!(
+    #[sol(rpc)]
+    #[derive(Debug)]
+    CurveRegistry,
+    "../../resources/contracts/curve_registry_contract.json"
+);
+
+sol!(
+    #[sol(rpc)]
+    #[derive(Debug)]
+    IArbExecutor,
+    "../../resources/contracts/arb_executor.json"
+);
+
+sol!(
+    #[sol(rpc)]
+    #[derive(Debug)]
+    IUniswapV2Router,
+    "../../resources/contracts/uniswapv2_router.json"
+);
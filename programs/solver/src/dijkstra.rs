@@ -66,6 +66,11 @@ impl ShortestPath {
             cost,
         }
     }
+
+    /// A path that starts and ends on the same token with at least one hop
+    pub fn is_cycle(&self) -> bool {
+        !self.pools.is_empty() && self.paths.first() == self.paths.last()
+    }
 }
 
 pub fn build_bidirectional_graph(
@@ -89,12 +94,19 @@ pub fn build_bidirectional_graph(
     graph
 }
 
+/// Cost label of a search state. Cycles are searched once per pool leaving the start token, so a
+/// round trip through a single pool cannot claim the best cost of a token a real cycle needs.
+fn label(token: Address, pools: &[Address], cycle: bool) -> (Address, Option<Address>) {
+    (token, pools.first().copied().filter(|_| cycle))
+}
+
 pub fn best_path(
     graph: &SwapGraph,
     start: &Address,
     end: &Address,
     slippage_adj: BigInt,
 ) -> ShortestPath {
+    let cycle = start == end;
     let mut heap = BinaryHeap::new();
     let mut best_cost = HashMap::new();
 
@@ -114,28 +126,36 @@ pub fn best_path(
         fees,
     }) = heap.pop()
     {
-        if &token == end {
+        // A cycle (start == end) has to leave the start token first
+        if &token == end && !pools.is_empty() {
             return ShortestPath::new(paths, pools, fees, cost);
         }
 
-        if cost > *best_cost.get(&token).unwrap_or(&BigInt::MAX) {
+        if cost
+            > *best_cost
+                .get(&label(token, &pools, cycle))
+                .unwrap_or(&BigInt::MAX)
+        {
             continue;
         }
 
         if let Some(neighbors) = graph.get(&token) {
-            for edge in neighbors {
+            // A pool is swapped through once per path, its state after the first hop is unknown
+            for edge in neighbors.iter().filter(|edge| !pools.contains(&edge.pool)) {
                 let new_cost = cost + edge.slippage + slippage_adj;
-                if new_cost < *best_cost.get(&edge.to).unwrap_or(&BigInt::MAX) {
+
+                let mut new_pools = pools.clone();
+                new_pools.push(edge.pool);
+                let new_label = label(edge.to, &new_pools, cycle);
+
+                if new_cost < *best_cost.get(&new_label).unwrap_or(&BigInt::MAX) {
                     let mut new_paths = paths.clone();
                     new_paths.push(edge.to);
 
-                    let mut new_pools = pools.clone();
-                    new_pools.push(edge.pool);
-
                     let mut new_fees = fees.clone();
                     new_fees.push(edge.fee);
 
-                    best_cost.insert(edge.to, new_cost);
+                    best_cost.insert(new_label, new_cost);
 
                     heap.push(State {
                         token: edge.to,
@@ -301,4 +321,50 @@ mod tests {
         assert_eq!(path_reverse.paths, vec![e, b, a]);
         assert_eq!(path_reverse.cost, BigInt::from(12));
     }
+
+    #[test]
+    pub fn test_cycle_through_another_pool() {
+        let a = address!("000000000000000000000000000000000000000A");
+        let b = address!("000000000000000000000000000000000000000B");
+        let p1 = address!("00000000000000000000000000000000000000A1");
+        let p2 = address!("00000000000000000000000000000000000000A2");
+        let mut graph = SwapGraph::new();
+        // B is reached cheapest through P1, but only a cycle through P2 returns through P1
+        graph.insert(
+            a,
+            vec![
+                SwapEdge::new(b, p1, BigInt::from(1), 0),
+                SwapEdge::new(b, p2, BigInt::from(5), 0),
+            ],
+        );
+        graph.insert(b, vec![SwapEdge::new(a, p1, BigInt::from(1), 0)]);
+
+        let path = best_path(&graph, &a, &a, BigInt::ZERO);
+        assert!(path.is_cycle());
+        assert_eq!(path.paths, vec![a, b, a]);
+        assert_eq!(path.pools, vec![p2, p1]);
+        assert_eq!(path.cost, BigInt::from(6));
+    }
+
+    #[test]
+    pub fn test_no_round_trip_through_one_pool() {
+        let a = address!("000000000000000000000000000000000000000A");
+        let b = address!("000000000000000000000000000000000000000B");
+        let p_a_b = address!("00000000000000000000000000000000000000AB");
+        let graph = build_bidirectional_graph(&[(a, b, p_a_b, U256::from(1), U256::from(1), 0)]);
+
+        let path = best_path(&graph, &a, &a, BigInt::ZERO);
+        assert!(!path.is_cycle());
+        assert!(path.paths.is_empty());
+    }
+
+    #[test]
+    pub fn test_is_cycle() {
+        let graph = create_graph();
+        let a = address!("000000000000000000000000000000000000000A");
+        let d = address!("000000000000000000000000000000000000000D");
+
+        assert!(!best_path(&graph, &a, &d, BigInt::ZERO).is_cycle());
+        assert!(!ShortestPath::new(vec![a], vec![], vec![], BigInt::ZERO).is_cycle());
+    }
 }
@@ -1,3 +1,5 @@
+use super::*;
+
 #[derive(Debug, Clone, Copy)]
 pub enum TxType {
     Add,
@@ -11,3 +13,42 @@ impl Default for TxType {
         Self::Sync
     }
 }
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Protocol {
+    UniswapV2,
+    UniswapV3,
+    Curve,
+}
+
+impl Protocol {
+    /// Inverse of the `protocol as u8` id encoded into executor hops
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Self::UniswapV2),
+            1 => Some(Self::UniswapV3),
+            2 => Some(Self::Curve),
+            _ => None,
+        }
+    }
+}
+
+/// How a Curve stable pool scales its amplification and when `get_dy` takes the fee
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum CurveFamily {
+    /// Pools up to 3pool: `A()` is the amplification, the fee comes off the output in coin units
+    #[default]
+    Legacy,
+    /// Pools with `A_precise()`: amplification scaled by `A_PRECISION`, fee taken before the
+    /// output is converted to coin units
+    Precise,
+}
+
+impl CurveFamily {
+    pub fn a_precision(&self) -> BigInt {
+        match self {
+            Self::Legacy => BigInt::ONE,
+            Self::Precise => BigInt::from(A_PRECISION),
+        }
+    }
+}
@@ -0,0 +1,247 @@
+use super::*;
+
+/// Thresholds and curated lists deciding which pools the swap graph routes through
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PoolFilter {
+    /// Token TVL is measured in
+    pub base_token: Address,
+    /// Minimum TVL in whole base tokens
+    pub min_tvl: f64,
+    /// Minimum whole tokens per side for pools none of whose tokens can be valued
+    pub min_reserve: f64,
+    /// Minimum active liquidity of a v3 pool
+    pub min_liquidity_v3: u128,
+    /// Maximum distance of a pool's mid price from the median of its pair
+    pub max_price_deviation_bps: f64,
+    /// Pools kept whatever their state
+    pub allow: HashSet<Address>,
+    /// Pools dropped whatever their state
+    pub deny: HashSet<Address>,
+}
+
+impl Default for PoolFilter {
+    fn default() -> Self {
+        Self {
+            base_token: WETH,
+            min_tvl: 1.0,
+            min_reserve: 1.0,
+            min_liquidity_v3: 1,
+            max_price_deviation_bps: 500.0,
+            allow: HashSet::new(),
+            deny: HashSet::new(),
+        }
+    }
+}
+
+/// Layout of `curve_error_pools.json`, whose `errors` are pools the Curve math fails on
+#[derive(Debug, Deserialize)]
+pub struct CurveErrorPools {
+    pub errors: Vec<Address>,
+}
+
+fn median(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    values[values.len() / 2]
+}
+
+fn to_f64(value: BigInt) -> f64 {
+    value.to_string().parse().unwrap_or_default()
+}
+
+fn whole(amount: BigInt, token: &Token) -> f64 {
+    to_f64(amount) / 10f64.powi(i32::from(token.decimals()))
+}
+
+impl PoolFilter {
+    /// Price of every token paired directly with the base token, as the median over its pools
+    fn base_prices(&self, quotes: &[Quote]) -> HashMap<Address, f64> {
+        let mut prices: HashMap<Address, Vec<f64>> = HashMap::new();
+        prices.entry(self.base_token).or_default().push(1.0);
+
+        for quote in quotes {
+            if quote.base == self.base_token {
+                prices
+                    .entry(quote.quote)
+                    .or_default()
+                    .push(quote.price.recip());
+            } else if quote.quote == self.base_token {
+                prices.entry(quote.base).or_default().push(quote.price);
+            }
+        }
+
+        prices
+            .into_iter()
+            .map(|(token, mut values)| (token, median(&mut values)))
+            .collect()
+    }
+
+    /// Pools off the median price of their pair, for pairs quoted by at least three pools
+    fn off_consensus(&self, quotes: &[Quote]) -> HashSet<Address> {
+        let mut pairs: HashMap<(Address, Address), Vec<&Quote>> = HashMap::new();
+        for quote in quotes {
+            pairs.entry(quote.pair()).or_default().push(quote);
+        }
+
+        pairs
+            .into_values()
+            .filter(|quotes| quotes.len() >= 3)
+            .flat_map(|quotes| {
+                let consensus = median(&mut quotes.iter().map(|q| q.price).collect::<Vec<_>>());
+                quotes.into_iter().filter_map(move |quote| {
+                    let deviation_bps = (quote.price / consensus - 1.0).abs() * 10_000.0;
+                    (deviation_bps > self.max_price_deviation_bps).then_some(quote.pool)
+                })
+            })
+            .collect()
+    }
+
+    /// TVL in base tokens of the valued sides, `None` when no side can be valued
+    fn tvl(&self, sides: &[(Address, f64)], prices: &HashMap<Address, f64>) -> Option<f64> {
+        let valued: Vec<f64> = sides
+            .iter()
+            .filter_map(|(token, amount)| prices.get(token).map(|price| amount * price))
+            .collect();
+
+        match valued.len() {
+            0 => None,
+            // Constant product pools hold equal value on both sides
+            1 if sides.len() == 2 => Some(valued[0] * 2.0),
+            _ => Some(valued.iter().sum()),
+        }
+    }
+
+    fn is_deep(&self, sides: &[(Address, f64)], prices: &HashMap<Address, f64>) -> bool {
+        match self.tvl(sides, prices) {
+            Some(tvl) => tvl >= self.min_tvl,
+            None => sides.iter().all(|(_, amount)| *amount >= self.min_reserve),
+        }
+    }
+
+    /// Pools the swap graph must leave out given the current state
+    pub fn excluded(
+        &self,
+        pool_data_v2: &v2::PoolData,
+        pool_data_v3: &v3::PoolData,
+        curve_pool_data: &curve::PoolData,
+    ) -> HashSet<Address> {
+        let quotes = collect_quotes(pool_data_v2, pool_data_v3, curve_pool_data);
+        let prices = self.base_prices(&quotes);
+        let mut excluded = self.off_consensus(&quotes);
+
+        for (pool, token_data) in pool_data_v2.data.iter() {
+            let sides = [
+                (
+                    token_data.token0.token.address(),
+                    whole(token_data.reserve0, &token_data.token0.token),
+                ),
+                (
+                    token_data.token1.token.address(),
+                    whole(token_data.reserve1, &token_data.token1.token),
+                ),
+            ];
+
+            if !self.is_deep(&sides, &prices) {
+                excluded.insert(*pool);
+            }
+        }
+
+        for (pool, token_data) in pool_data_v3.data.iter() {
+            if token_data.liquidity < self.min_liquidity_v3 || token_data.sqrt_price_x96.is_zero() {
+                excluded.insert(*pool);
+            }
+        }
+
+        for (pool, token_data) in curve_pool_data.data.iter() {
+            // xp is normalised to 18 decimals whatever the token
+            let sides: Vec<(Address, f64)> = token_data
+                .tokens
+                .iter()
+                .zip(&token_data.xp)
+                .map(|(token, xp)| (*token, to_f64(*xp) / PRECISION as f64))
+                .collect();
+
+            if sides.is_empty() || !self.is_deep(&sides, &prices) {
+                excluded.insert(*pool);
+            }
+        }
+
+        excluded.retain(|pool| !self.allow.contains(pool));
+        excluded.extend(&self.deny);
+
+        excluded
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const USDC: Address = address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+
+    fn pool(n: u8) -> Address {
+        Address::with_last_byte(n)
+    }
+
+    /// USDC/WETH pools with the given whole-token reserves
+    fn pool_data(reserves: &[(u128, u128)]) -> v2::PoolData {
+        let token_map: TokenMap = [
+            (USDC, token!(1, USDC, 6, "USDC")),
+            (WETH, token!(1, WETH, 18, "WETH")),
+        ]
+        .into_iter()
+        .collect();
+        let pools: Vec<Pools> = (0..reserves.len())
+            .map(|i| Pools {
+                token0: USDC,
+                token1: WETH,
+                fee: 3000,
+                address: pool(i as u8 + 1),
+            })
+            .collect();
+
+        let mut pool_data = v2::PoolData::new(&pools, &token_map).unwrap();
+        for (i, (usdc, weth)) in reserves.iter().enumerate() {
+            let token_data = pool_data.data.get_mut(&pool(i as u8 + 1)).unwrap();
+            token_data.reserve0 = BigInt::from(usdc * 10u128.pow(6));
+            token_data.reserve1 = BigInt::from(weth * 10u128.pow(18));
+        }
+
+        pool_data
+    }
+
+    fn excluded(filter: &PoolFilter, pool_data_v2: &v2::PoolData) -> HashSet<Address> {
+        let tokens = TokenMap::new();
+        filter.excluded(
+            pool_data_v2,
+            &v3::PoolData::new(&[], &tokens).unwrap(),
+            &curve::PoolData::new(&[], &tokens).unwrap(),
+        )
+    }
+
+    #[test]
+    fn drops_dust_and_off_consensus_pools() {
+        let pool_data_v2 = pool_data(&[
+            (2_000_000, 1_000),
+            (4_000_000, 2_000),
+            (2, 0),         // dust
+            (300_000, 100), // priced 3000 against a 2000 consensus
+        ]);
+
+        let excluded = excluded(&PoolFilter::default(), &pool_data_v2);
+
+        assert_eq!(excluded, HashSet::from([pool(3), pool(4)]));
+    }
+
+    #[test]
+    fn curated_lists_override_state() {
+        let pool_data_v2 = pool_data(&[(2_000_000, 1_000), (2, 0)]);
+        let filter = PoolFilter {
+            allow: HashSet::from([pool(2)]),
+            deny: HashSet::from([pool(1)]),
+            ..Default::default()
+        };
+
+        assert_eq!(excluded(&filter, &pool_data_v2), HashSet::from([pool(1)]));
+    }
+}
@@ -0,0 +1,405 @@
+use super::*;
+use alloy::{primitives::Bytes, sol_types::SolCall};
+use IArbExecutor::{Hop, Loan};
+
+/// Source of the borrowed input for a cycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Lender {
+    /// Flash swap from a v2 pair through the `swap` callback data
+    UniswapV2(Address),
+    /// `flash` on a v3 pool, `fee` in hundredths of a bip
+    UniswapV3 {
+        pool: Address,
+        fee: u32,
+    },
+    Balancer,
+    AaveV3,
+}
+
+impl Lender {
+    /// Identifier understood by the executor contract
+    pub fn id(&self) -> u8 {
+        match self {
+            Self::UniswapV2(_) => 0,
+            Self::UniswapV3 { .. } => 1,
+            Self::Balancer => 2,
+            Self::AaveV3 => 3,
+        }
+    }
+
+    pub fn address(&self) -> Address {
+        match self {
+            Self::UniswapV2(pair) => *pair,
+            Self::UniswapV3 { pool, .. } => *pool,
+            Self::Balancer => BALANCER_VAULT,
+            Self::AaveV3 => AAVE_V3_POOL,
+        }
+    }
+
+    /// Fee charged on top of `amount`, rounded the same way as the lender contract
+    pub fn fee(&self, amount: U256) -> U256 {
+        match self {
+            // amount * 1000 / 997 - amount, rounded up by the pair's k check
+            Self::UniswapV2(_) => amount * U256::from(3) / U256::from(997) + U256::ONE,
+            // FullMath.mulDivRoundingUp(amount, fee, 1e6)
+            Self::UniswapV3 { fee, .. } => {
+                let denominator = U256::from(1_000_000);
+                (amount * U256::from(*fee)).div_ceil(denominator)
+            }
+            Self::Balancer => U256::ZERO,
+            // PercentageMath.percentMul rounds half up
+            Self::AaveV3 => {
+                (amount * U256::from(AAVE_FLASH_PREMIUM_BPS) + U256::from(5_000))
+                    / U256::from(10_000)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FlashPlan {
+    pub lender: Lender,
+    pub token: Address,
+    pub amount: U256,
+    pub fee: U256,
+    pub repay_amount: U256,
+    pub amount_out: U256,
+    pub profit: BigInt,
+    pub hops: Vec<Hop>,
+    pub calldata: Bytes,
+}
+
+impl FlashPlan {
+    pub fn is_profitable(&self) -> bool {
+        self.profit > BigInt::ZERO
+    }
+}
+
+impl Display for FlashPlan {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "lender: {:?}\ntoken: {}\namount: {}\nfee: {}\nrepay_amount: {}\namount_out: {}\nprofit: {}",
+            self.lender,
+            self.token,
+            self.amount,
+            self.fee,
+            self.repay_amount,
+            self.amount_out,
+            self.profit
+        )?;
+        for hop in &self.hops {
+            writeln!(
+                f,
+                "hop: {} {} -> {} in: {} out: {}",
+                hop.pool, hop.tokenIn, hop.tokenOut, hop.amountIn, hop.amountOut
+            )?;
+        }
+        write!(f, "calldata: {}", self.calldata)
+    }
+}
+
+/// Chain the solver's quotes along a path, each hop spending the previous hop's output
+pub fn quote_hops<'a>(
+    pool_data_v2: &v2::PoolData,
+    pool_data_v3: &v3::PoolData,
+    curve_pool_data: &curve::PoolData,
+    path: &ShortestPath,
+    amount: U256,
+) -> Result<Vec<Hop>, CustomError<'a>> {
+    let mut hops = Vec::with_capacity(path.pools.len());
+    let mut amount_in = amount.to_big_int();
+
+    for (idx, pool) in path.pools.iter().enumerate() {
+        let token_in = path.paths[idx];
+        let token_out = path.paths[idx + 1];
+
+        let (protocol, amount_out) = get_amount_out(
+            pool_data_v2,
+            pool_data_v3,
+            curve_pool_data,
+            pool,
+            &token_in,
+            &token_out,
+            amount_in,
+        )?;
+
+        // Curve hops swap by coin index, a missing coin must not fall back to index 0
+        let (i, j) = match curve_pool_data.data.get(pool) {
+            Some(token_data) => {
+                let index = |token: &Address| {
+                    token_data
+                        .tokens
+                        .iter()
+                        .position(|t| t == token)
+                        .ok_or(CustomError::AddressNotFound(*token))
+                };
+                (index(&token_in)?, index(&token_out)?)
+            }
+            None => (0, 0),
+        };
+
+        hops.push(Hop {
+            protocol: protocol as u8,
+            pool: *pool,
+            tokenIn: token_in,
+            tokenOut: token_out,
+            i: i as i128,
+            j: j as i128,
+            amountIn: U256::from_big_int(amount_in),
+            amountOut: U256::from_big_int(amount_out),
+        });
+
+        amount_in = amount_out;
+    }
+
+    Ok(hops)
+}
+
+pub struct FlashPlanBuilder<'p> {
+    cycle: &'p ShortestPath,
+    lender: Option<Lender>,
+    amount_in: Option<U256>,
+    min_profit: U256,
+}
+
+impl<'p> FlashPlanBuilder<'p> {
+    pub fn new(cycle: &'p ShortestPath) -> Self {
+        Self {
+            cycle,
+            lender: None,
+            amount_in: None,
+            min_profit: U256::ZERO,
+        }
+    }
+
+    pub fn lender(mut self, lender: Lender) -> Self {
+        self.lender = Some(lender);
+        self
+    }
+
+    pub fn amount_in(mut self, amount_in: U256) -> Self {
+        self.amount_in = Some(amount_in);
+        self
+    }
+
+    pub fn min_profit(mut self, min_profit: U256) -> Self {
+        self.min_profit = min_profit;
+        self
+    }
+
+    /// Quote every hop of the cycle and encode the executor call
+    pub fn build<'a>(
+        self,
+        pool_data_v2: &v2::PoolData,
+        pool_data_v3: &v3::PoolData,
+        curve_pool_data: &curve::PoolData,
+    ) -> Result<FlashPlan, CustomError<'a>> {
+        let lender = self.lender.ok_or(CustomError::NotFound("flash lender"))?;
+        let amount = self
+            .amount_in
+            .ok_or(CustomError::NotFound("flash amount"))?;
+
+        if !self.cycle.is_cycle() {
+            return Err(CustomError::InvalidPath("path is not a cycle"));
+        }
+
+        // The lender's reentrancy lock would make the hop through it revert
+        if self.cycle.pools.contains(&lender.address()) {
+            return Err(CustomError::InvalidPath("lender pool is part of the cycle"));
+        }
+
+        let token = self.cycle.paths[0];
+        let hops = quote_hops(
+            pool_data_v2,
+            pool_data_v3,
+            curve_pool_data,
+            self.cycle,
+            amount,
+        )?;
+        let amount_in = hops
+            .last()
+            .map(|hop| hop.amountOut.to_big_int())
+            .unwrap_or_default();
+
+        let fee = lender.fee(amount);
+        let repay_amount = amount + fee;
+        let amount_out = U256::from_big_int(amount_in);
+        let profit = amount_in - repay_amount.to_big_int();
+
+        let calldata = IArbExecutor::executeCall {
+            loan: Loan {
+                lender: lender.id(),
+                lenderAddress: lender.address(),
+                token,
+                amount,
+                repayAmount: repay_amount,
+                minProfit: self.min_profit,
+            },
+            hops: hops.clone(),
+        }
+        .abi_encode()
+        .into();
+
+        Ok(FlashPlan {
+            lender,
+            token,
+            amount,
+            fee,
+            repay_amount,
+            amount_out,
+            profit,
+            hops,
+            calldata,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKEN_A: Address = address!("0x1000000000000000000000000000000000000001");
+    const TOKEN_B: Address = address!("0x2000000000000000000000000000000000000002");
+    const POOL_1: Address = address!("0x00000000000000000000000000000000000000A1");
+    const POOL_2: Address = address!("0x00000000000000000000000000000000000000A2");
+
+    fn ether(amount: u128) -> BigInt {
+        BigInt::from(amount * PRECISION)
+    }
+
+    // Pool 2 prices B cheaper than pool 1, so A -> B (pool 2) -> A (pool 1) is profitable
+    fn create_pool_data() -> (v2::PoolData, v3::PoolData, curve::PoolData) {
+        let mut tokens = TokenMap::new();
+        tokens.insert(TOKEN_A, token!(1, TOKEN_A, 18));
+        tokens.insert(TOKEN_B, token!(1, TOKEN_B, 18));
+
+        let pools: Vec<Pools> = [POOL_1, POOL_2]
+            .iter()
+            .map(|address| Pools {
+                token0: TOKEN_A,
+                token1: TOKEN_B,
+                fee: 3000,
+                address: *address,
+            })
+            .collect();
+
+        let mut pool_data_v2 = v2::PoolData::new(&pools, &tokens).unwrap();
+        pool_data_v2.data.entry(POOL_1).and_modify(|data| {
+            data.reserve0 = ether(1000);
+            data.reserve1 = ether(1000);
+        });
+        pool_data_v2.data.entry(POOL_2).and_modify(|data| {
+            data.reserve0 = ether(1000);
+            data.reserve1 = ether(1100);
+        });
+
+        (
+            pool_data_v2,
+            v3::PoolData {
+                data: HashMap::new(),
+            },
+            curve::PoolData {
+                data: HashMap::new(),
+            },
+        )
+    }
+
+    fn create_cycle() -> ShortestPath {
+        ShortestPath::new(
+            vec![TOKEN_A, TOKEN_B, TOKEN_A],
+            vec![POOL_2, POOL_1],
+            vec![3000, 3000],
+            BigInt::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_lender_fees() {
+        let amount = U256::from(PRECISION);
+
+        assert_eq!(
+            Lender::UniswapV2(POOL_1).fee(amount),
+            U256::from(3_009_027_081_243_732u128)
+        );
+        assert_eq!(
+            Lender::UniswapV3 {
+                pool: POOL_1,
+                fee: 500
+            }
+            .fee(amount),
+            U256::from(500_000_000_000_000u128)
+        );
+        assert_eq!(
+            Lender::UniswapV3 {
+                pool: POOL_1,
+                fee: 3000
+            }
+            .fee(U256::from(1)),
+            U256::from(1)
+        );
+        assert_eq!(Lender::Balancer.fee(amount), U256::ZERO);
+        assert_eq!(
+            Lender::AaveV3.fee(amount),
+            U256::from(500_000_000_000_000u128)
+        );
+    }
+
+    #[test]
+    fn test_build_profitable_plan() {
+        let (pool_data_v2, pool_data_v3, curve_pool_data) = create_pool_data();
+        let cycle = create_cycle();
+        let amount = U256::from(PRECISION);
+
+        let plan = FlashPlanBuilder::new(&cycle)
+            .lender(Lender::AaveV3)
+            .amount_in(amount)
+            .build(&pool_data_v2, &pool_data_v3, &curve_pool_data)
+            .unwrap();
+
+        assert!(plan.is_profitable());
+        assert_eq!(plan.repay_amount, amount + Lender::AaveV3.fee(amount));
+        assert_eq!(plan.hops.len(), 2);
+        assert_eq!(plan.hops[0].amountIn, amount);
+        assert_eq!(plan.hops[1].amountIn, plan.hops[0].amountOut);
+        assert_eq!(plan.amount_out, plan.hops[1].amountOut);
+        assert_eq!(
+            plan.profit,
+            plan.amount_out.to_big_int() - plan.repay_amount.to_big_int()
+        );
+
+        let decoded = IArbExecutor::executeCall::abi_decode(&plan.calldata).unwrap();
+        assert_eq!(decoded.loan.lender, 3);
+        assert_eq!(decoded.loan.lenderAddress, AAVE_V3_POOL);
+        assert_eq!(decoded.loan.token, TOKEN_A);
+        assert_eq!(decoded.loan.repayAmount, plan.repay_amount);
+        assert_eq!(decoded.hops.len(), 2);
+        assert_eq!(decoded.hops[0].pool, POOL_2);
+        assert_eq!(decoded.hops[1].tokenOut, TOKEN_A);
+    }
+
+    #[test]
+    fn test_build_rejects_invalid_cycles() {
+        let (pool_data_v2, pool_data_v3, curve_pool_data) = create_pool_data();
+
+        let path = ShortestPath::new(
+            vec![TOKEN_A, TOKEN_B],
+            vec![POOL_1],
+            vec![3000],
+            BigInt::ZERO,
+        );
+        let result = FlashPlanBuilder::new(&path)
+            .lender(Lender::Balancer)
+            .amount_in(U256::from(PRECISION))
+            .build(&pool_data_v2, &pool_data_v3, &curve_pool_data);
+        assert!(matches!(result, Err(CustomError::InvalidPath(_))));
+
+        let cycle = create_cycle();
+        let result = FlashPlanBuilder::new(&cycle)
+            .lender(Lender::UniswapV2(POOL_1))
+            .amount_in(U256::from(PRECISION))
+            .build(&pool_data_v2, &pool_data_v3, &curve_pool_data);
+        assert!(matches!(result, Err(CustomError::InvalidPath(_))));
+    }
+}
@@ -0,0 +1,69 @@
+use crate::{
+    backrun::*, backtest::*, constants::*, contracts::*, dijkstra::*, enums::*, fetch::*,
+    filter::*, flash::*, helper::*, onboard::*, parser::*, pools::*, quote::*, route::*,
+    scanner::*, simulation::*, slippage::*, structs::*,
+};
+use alloy::{
+    eips::BlockId,
+    primitives::{
+        address,
+        aliases::{I24, U160},
+        b256, Address, TxHash, B256, U256,
+    },
+    providers::{
+        fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller},
+        Identity, Provider, RootProvider,
+    },
+    rpc::types::{Filter, Log},
+    sol,
+};
+use colored::Colorize;
+use dotenv::dotenv;
+use futures::stream::FuturesUnordered;
+use futures_util::stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::from_reader;
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap, HashSet},
+    env,
+    fmt::Display,
+    fs::File,
+    io::BufReader,
+    sync::Arc,
+};
+use tokio::sync::{mpsc, Mutex};
+use uniswap_sdk_core::{prelude::*, token};
+use uniswap_v3_sdk::prelude::tick_sync::TickSync;
+use utils::{debug_time, format_with_decimals, CustomError};
+
+pub type SolverProvider = FillProvider<
+    JoinFill<
+        Identity,
+        JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>,
+    >,
+    RootProvider,
+>;
+
+pub mod backrun;
+pub mod backtest;
+pub mod constants;
+pub mod contracts;
+pub mod dijkstra;
+pub mod enums;
+pub mod fetch;
+pub mod filter;
+pub mod flash;
+pub mod helper;
+pub mod onboard;
+pub mod parser;
+pub mod pools;
+pub mod quote;
+pub mod route;
+pub mod scanner;
+pub mod simulation;
+pub mod slippage;
+pub mod snapshot;
+pub mod structs;
+pub mod tick_replay;
+pub mod validate;
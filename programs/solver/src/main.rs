@@ -1,59 +1,6 @@
-use crate::{
-    constants::*, contracts::*, dijkstra::*, enums::*, fetch::*, helper::*, parser::*, pools::*,
-    scanner::*, slippage::*, structs::*,
-};
-use alloy::{
-    primitives::{
-        address,
-        aliases::{I24, U160},
-        Address, TxHash, U256,
-    },
-    providers::{
-        fillers::{BlobGasFiller, ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller},
-        Identity, Provider, ProviderBuilder, RootProvider, WsConnect,
-    },
-    rpc::types::{Filter, Log},
-    sol,
-};
-use colored::Colorize;
-use dotenv::dotenv;
-use futures::stream::FuturesUnordered;
-use futures_util::stream::StreamExt;
-use serde::{Deserialize, Serialize};
-use serde_json::from_reader;
-use std::{
-    cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
-    env,
-    fmt::Display,
-    fs::File,
-    io::BufReader,
-    sync::Arc,
-};
-use tokio::sync::{mpsc, Mutex};
-use uniswap_sdk_core::{prelude::*, token};
-use uniswap_v3_sdk::prelude::tick_sync::TickSync;
-use utils::{debug_time, info_time, CustomError};
-
-type SolverProvider = FillProvider<
-    JoinFill<
-        Identity,
-        JoinFill<GasFiller, JoinFill<BlobGasFiller, JoinFill<NonceFiller, ChainIdFiller>>>,
-    >,
-    RootProvider,
->;
-
-mod constants;
-mod contracts;
-mod dijkstra;
-mod enums;
-mod fetch;
-mod helper;
-mod parser;
-mod pools;
-mod scanner;
-mod slippage;
-mod structs;
+use alloy::providers::{ProviderBuilder, WsConnect};
+use solver::{backtest::*, parser::*, pools::*, scanner::*};
+use utils::{debug_time, info_time, metrics::spawn_exporter};
 
 #[tokio::main]
 async fn main() -> Result<(), anyhow::Error> {
@@ -62,58 +9,44 @@ async fn main() -> Result<(), anyhow::Error> {
 
     log::info!("Logger initialized");
 
+    // Serve /metrics when METRICS_ADDRESS is set
+    spawn_exporter();
+
     info_time!("main()", {
         // Load environment variables from .env file
         let env_parser = info_time!("env_parser", { EnvParser::new()? });
 
-        // Set up the WS transport and connect.
-        let ws = WsConnect::new(env_parser.ws_address);
-        let provider = ProviderBuilder::new().connect_ws(ws).await?;
-
         let token_map: TokenMap = debug_time!("token_map creation()", {
             token_metadata_to_tokens(&env_parser.token_metadata)
         });
 
-        let mut pool_data_v2: v2::PoolData = debug_time!("pool_data_v2()", {
-            v2::PoolData::new(&env_parser.pools_v2, &token_map)?
-        });
+        // Replay recorded blocks offline instead of following the chain
+        if let Some(config) = &env_parser.backtest {
+            info_time!("run_backtest()", {
+                run_backtest(&env_parser, &token_map, config).await?
+            });
+            return Ok(());
+        }
 
-        debug_time!("update_reserve()", {
-            pool_data_v2
-                .update_reserves(&provider, &env_parser.pool_address.v2)
-                .await?
-        });
-
-        debug_time!("calulate_start_price_v2()", {
-            pool_data_v2.calc_start_price()?
-        });
-
-        let mut pool_data_v3: v3::PoolData = debug_time!("pool_data_v3()", {
-            v3::PoolData::new(&env_parser.pools_v3, &token_map)?
-        });
-
-        debug_time!("calulate_start_price_v3()", {
-            pool_data_v3.calc_start_price(&env_parser.tick_map)?
-        });
-
-        let mut curve_pools = env_parser.curve_pools;
-
-        debug_time!("fetch_balances()", {
-            CurvePools::fetch_balances(&provider, &mut curve_pools).await;
-        });
+        // Set up the WS transport and connect.
+        let ws = WsConnect::new(env_parser.ws_address.as_str());
+        let provider = ProviderBuilder::new().connect_ws(ws).await?;
 
-        let curve_pool_data: curve::PoolData = debug_time!("cuve_data_pools()", {
-            curve::PoolData::new(&curve_pools, &token_map)?
+        let (pool_data_v2, pool_data_v3, curve_pool_data) = debug_time!("load_pools()", {
+            load_pools(&provider, &env_parser, &token_map).await?
         });
 
         // Scanning the ethereum blockchain for events
         debug_time!("Calling scanner()", {
             scan(
                 &provider,
+                token_map,
                 env_parser.pool_address.single(),
                 pool_data_v2,
                 pool_data_v3,
                 curve_pool_data,
+                env_parser.pool_filter,
+                env_parser.backrun,
             )
             .await?
         });
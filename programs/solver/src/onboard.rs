@@ -0,0 +1,447 @@
+use super::*;
+use alloy::sol_types::SolEvent;
+use std::time::{Duration, Instant};
+use uniswap_v3_sdk::prelude::EphemeralTickDataProvider;
+
+/// Whole tokens every side of a new pool must hold before it's routed through
+const MIN_WHOLE_TOKENS: u128 = 1;
+
+/// Tokens announcing more decimals than this are not real tokens worth routing through
+const MAX_TOKEN_DECIMALS: u8 = 36;
+
+/// Pause between liquidity checks of a pending pool, however many logs it emits
+const RECHECK_INTERVAL: Duration = Duration::from_secs(30);
+
+/// First pause after a check failed to read the pool, doubled on every failure up to 64x
+const RETRY_BACKOFF: Duration = Duration::from_secs(1);
+
+/// A pool announced by a factory or registry after startup
+#[derive(Debug, Clone)]
+pub enum NewPool {
+    V2(Pools),
+    V3(Pools),
+    Curve(Address),
+}
+
+impl NewPool {
+    /// Decode a `PairCreated`, `PoolCreated` or `PoolAdded` log
+    pub fn decode(log: &Log) -> Option<Self> {
+        match (log.address(), log.topic0().copied()?) {
+            (
+                UNISWAP_V2_FACTORY | SUSHI_V2_FACTORY,
+                IUniswapV2Factory::PairCreated::SIGNATURE_HASH,
+            ) => {
+                let event = IUniswapV2Factory::PairCreated::decode_log(&log.inner).ok()?;
+                Some(Self::V2(Pools {
+                    token0: event.token0,
+                    token1: event.token1,
+                    fee: 3000,
+                    address: event.pair,
+                }))
+            }
+            (UNISWAP_V3_FACTORY, IUniswapV3Factory::PoolCreated::SIGNATURE_HASH) => {
+                let event = IUniswapV3Factory::PoolCreated::decode_log(&log.inner).ok()?;
+                Some(Self::V3(Pools {
+                    token0: event.token0,
+                    token1: event.token1,
+                    fee: event.fee.to(),
+                    address: event.pool,
+                }))
+            }
+            (CURVE_REGISTRY, CurveRegistry::PoolAdded::SIGNATURE_HASH) => {
+                let event = CurveRegistry::PoolAdded::decode_log(&log.inner).ok()?;
+                Some(Self::Curve(event.pool))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn address(&self) -> Address {
+        match self {
+            Self::V2(pool) | Self::V3(pool) => pool.address,
+            Self::Curve(address) => *address,
+        }
+    }
+}
+
+/// Creation events of every watched factory and registry
+pub fn onboarding_filter() -> Filter {
+    Filter::new()
+        .address(vec![
+            UNISWAP_V2_FACTORY,
+            SUSHI_V2_FACTORY,
+            UNISWAP_V3_FACTORY,
+            CURVE_REGISTRY,
+        ])
+        .event_signature(vec![
+            IUniswapV2Factory::PairCreated::SIGNATURE_HASH,
+            IUniswapV3Factory::PoolCreated::SIGNATURE_HASH,
+            CurveRegistry::PoolAdded::SIGNATURE_HASH,
+        ])
+}
+
+fn min_balance<'a>(token: &Token) -> Result<BigInt, CustomError<'a>> {
+    U256::from(10)
+        .checked_pow(U256::from(token.decimals()))
+        .and_then(|scale| scale.checked_mul(U256::from(MIN_WHOLE_TOKENS)))
+        .map(|balance| balance.to_big_int())
+        .ok_or(CustomError::InvalidDecimals(
+            token.address(),
+            token.decimals(),
+        ))
+}
+
+/// Fetch decimals, symbol and name of the tokens missing from `token_map`
+async fn fetch_tokens<'a>(
+    provider: &SolverProvider,
+    tokens: &[Address],
+    token_map: &mut TokenMap,
+) -> Result<(), CustomError<'a>> {
+    for &address in tokens {
+        if token_map.contains_key(&address) {
+            continue;
+        }
+
+        let contract = ERC20::new(address, provider.clone());
+        let decimals = contract.decimals().call().await?;
+        if decimals > MAX_TOKEN_DECIMALS {
+            return Err(CustomError::InvalidDecimals(address, decimals));
+        }
+        let symbol = contract.symbol().call().await.unwrap_or_default();
+        let name = contract.name().call().await.unwrap_or_default();
+
+        token_map.insert(address, token!(1, address, decimals, symbol, name));
+    }
+
+    Ok(())
+}
+
+/// Price, liquidity and initialized ticks of a v3 pool as of `block`
+pub async fn fetch_tick_data<'a>(
+    provider: &SolverProvider,
+    pool: Address,
+    block: u64,
+) -> Result<TickData, CustomError<'a>> {
+    let block_id = BlockId::number(block);
+    let contract = IUniswapV3Pool::new(pool, provider.clone());
+    let slot0 = contract.slot0().block(block_id).call().await?;
+    let liquidity = contract.liquidity().block(block_id).call().await?;
+    let ticks =
+        EphemeralTickDataProvider::<I24>::new(pool, provider.clone(), None, None, Some(block_id))
+            .await?
+            .ticks
+            .into_iter()
+            .map(|tick| TickSync {
+                index: tick.index.as_i32(),
+                liquidity_gross: tick.liquidity_gross,
+                liquidity_net: tick.liquidity_net,
+                is_init: true,
+            })
+            .collect();
+
+    Ok(TickData {
+        block,
+        current_tick: slot0.tick,
+        sqrt_price_x96: slot0.sqrtPriceX96,
+        liquidity,
+        ticks,
+    })
+}
+
+/// A pending pool that passed the liquidity filters, fetched without touching the shared state
+pub struct Promotion {
+    /// Tokens of the pool, some of which the shared token map may be missing
+    pub tokens: TokenMap,
+    pub pool_data: PromotedPool,
+}
+
+pub enum PromotedPool {
+    V2(v2::PoolData),
+    V3(v3::PoolData),
+    Curve(curve::PoolData),
+}
+
+impl Promotion {
+    /// Read the state of `new_pool`, `None` while it doesn't pass the liquidity filters.
+    /// Tokens missing from `token_map`, a copy of the shared one, are fetched.
+    pub async fn fetch<'a>(
+        provider: &SolverProvider,
+        new_pool: NewPool,
+        mut token_map: TokenMap,
+    ) -> Result<Option<Self>, CustomError<'a>> {
+        let (tokens, pool_data) = match new_pool {
+            NewPool::V2(pools) => {
+                let tokens = vec![pools.token0, pools.token1];
+                fetch_tokens(provider, &tokens, &mut token_map).await?;
+
+                let mut candidate = v2::PoolData::new(std::slice::from_ref(&pools), &token_map)?;
+                candidate
+                    .update_reserves(provider, &[pools.address])
+                    .await?;
+
+                for data in candidate.data.values() {
+                    if data.reserve0 < min_balance(&data.token0.token)?
+                        || data.reserve1 < min_balance(&data.token1.token)?
+                    {
+                        return Ok(None);
+                    }
+                }
+                candidate.calc_start_price()?;
+
+                (tokens, PromotedPool::V2(candidate))
+            }
+            NewPool::V3(pools) => {
+                let tokens = vec![pools.token0, pools.token1];
+                fetch_tokens(provider, &tokens, &mut token_map).await?;
+
+                let block = provider.get_block_number().await?;
+                let tick_data = fetch_tick_data(provider, pools.address, block).await?;
+                if tick_data.liquidity == 0 || tick_data.ticks.is_empty() {
+                    return Ok(None);
+                }
+                let mut candidate = v3::PoolData::new(std::slice::from_ref(&pools), &token_map)?;
+                candidate.calc_start_price(&TickMap::from([(pools.address, tick_data)]))?;
+
+                (tokens, PromotedPool::V3(candidate))
+            }
+            NewPool::Curve(address) => {
+                let registry = CurveRegistry::new(CURVE_REGISTRY, provider.clone());
+                let tokens: Vec<Address> = registry
+                    .get_coins(address)
+                    .call()
+                    .await?
+                    .into_iter()
+                    .filter(|token| !token.is_zero())
+                    .collect();
+                fetch_tokens(provider, &tokens, &mut token_map).await?;
+
+                let contract = CurvePool::new(address, provider.clone());
+                let mut pools = vec![CurvePools {
+                    tokens: tokens.clone(),
+                    balances: Vec::new(),
+                    fee: contract.fee().call().await?,
+                    a: contract.A().call().await?,
+                    a_precise: CurveMetaPool::new(address, provider.clone())
+                        .A_precise()
+                        .call()
+                        .await
+                        .ok(),
+                    address,
+                }];
+                CurvePools::fetch_balances(provider, &mut pools, BlockId::latest()).await;
+
+                let candidate = curve::PoolData::new(&pools, &token_map)?;
+                let liquid = candidate.data.values().all(|data| {
+                    data.xp.len() == data.tokens.len()
+                        && data
+                            .xp
+                            .iter()
+                            .all(|x| *x >= BigInt::from(MIN_WHOLE_TOKENS * PRECISION))
+                });
+                if !liquid {
+                    return Ok(None);
+                }
+
+                (tokens, PromotedPool::Curve(candidate))
+            }
+        };
+
+        Ok(Some(Self {
+            tokens: tokens
+                .into_iter()
+                .filter_map(|address| token_map.remove_entry(&address))
+                .collect(),
+            pool_data,
+        }))
+    }
+
+    /// Add the pool and its tokens to the shared state, locking only what it changes
+    pub async fn insert(self, shared: &SharedState) {
+        let mut token_map = shared.token_map.lock().await;
+        for (address, token) in self.tokens {
+            token_map.entry(address).or_insert(token);
+        }
+
+        match self.pool_data {
+            PromotedPool::V2(candidate) => {
+                shared.pool_data_v2.lock().await.data.extend(candidate.data)
+            }
+            PromotedPool::V3(candidate) => {
+                shared.pool_data_v3.lock().await.data.extend(candidate.data)
+            }
+            PromotedPool::Curve(candidate) => shared
+                .curve_pool_data
+                .lock()
+                .await
+                .data
+                .extend(candidate.data),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Pending {
+    pool: NewPool,
+    next_check: Instant,
+    failures: u32,
+    checking: bool,
+}
+
+/// Pools created since startup that don't pass the liquidity filters yet
+#[derive(Debug, Default)]
+pub struct Onboarding {
+    pending: HashMap<Address, Pending>,
+}
+
+impl Onboarding {
+    pub fn add(&mut self, pool: NewPool, now: Instant) {
+        self.pending.insert(
+            pool.address(),
+            Pending {
+                pool,
+                next_check: now,
+                failures: 0,
+                checking: false,
+            },
+        );
+    }
+
+    pub fn remove(&mut self, pool: &Address) {
+        self.pending.remove(pool);
+    }
+
+    pub fn is_pending(&self, pool: &Address) -> bool {
+        self.pending.contains_key(pool)
+    }
+
+    /// Claim a check of `pool` if it is pending, not being checked and its pause is over
+    pub fn start_check(&mut self, pool: &Address, now: Instant) -> Option<NewPool> {
+        let pending = self.pending.get_mut(pool)?;
+        if pending.checking || now < pending.next_check {
+            return None;
+        }
+
+        pending.checking = true;
+        Some(pending.pool.clone())
+    }
+
+    /// The pool was read but isn't liquid yet, check it again after [`RECHECK_INTERVAL`]
+    pub fn not_liquid(&mut self, pool: &Address, now: Instant) {
+        if let Some(pending) = self.pending.get_mut(pool) {
+            pending.checking = false;
+            pending.failures = 0;
+            pending.next_check = now + RECHECK_INTERVAL;
+        }
+    }
+
+    /// The pool couldn't be read, keep it pending and back off before the next check
+    pub fn failed(&mut self, pool: &Address, now: Instant) -> Option<Duration> {
+        let pending = self.pending.get_mut(pool)?;
+        let delay = RETRY_BACKOFF * 2u32.pow(pending.failures);
+        pending.checking = false;
+        pending.failures = (pending.failures + 1).min(6);
+        pending.next_check = now + delay;
+
+        Some(delay)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{aliases::U24, LogData};
+
+    fn log(address: Address, data: LogData) -> Log {
+        Log {
+            inner: alloy::primitives::Log { address, data },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn min_balance_checks_decimals() {
+        let token = address!("0x1000000000000000000000000000000000000001");
+
+        assert_eq!(
+            min_balance(&token!(1, token, 18)).unwrap(),
+            BigInt::from(PRECISION)
+        );
+        // 10^39 no longer fits a u128, 10^80 not even a U256
+        assert_eq!(
+            min_balance(&token!(1, token, 39)).unwrap(),
+            BigInt::from(10u8).pow(39)
+        );
+        assert!(matches!(
+            min_balance(&token!(1, token, 80)),
+            Err(CustomError::InvalidDecimals(_, 80))
+        ));
+    }
+
+    #[test]
+    fn decodes_creation_events() {
+        let (token0, token1, pool) = (
+            address!("0x1000000000000000000000000000000000000001"),
+            address!("0x2000000000000000000000000000000000000002"),
+            address!("0x3000000000000000000000000000000000000003"),
+        );
+
+        let pair_created = IUniswapV2Factory::PairCreated {
+            token0,
+            token1,
+            pair: pool,
+            _3: U256::from(1),
+        };
+        let Some(NewPool::V2(pools)) =
+            NewPool::decode(&log(SUSHI_V2_FACTORY, pair_created.encode_log_data()))
+        else {
+            panic!("PairCreated not decoded");
+        };
+        assert_eq!(
+            (pools.token0, pools.token1, pools.address),
+            (token0, token1, pool)
+        );
+
+        let pool_created = IUniswapV3Factory::PoolCreated {
+            token0,
+            token1,
+            fee: U24::from(500),
+            tickSpacing: I24::unchecked_from(10),
+            pool,
+        };
+        let Some(NewPool::V3(pools)) =
+            NewPool::decode(&log(UNISWAP_V3_FACTORY, pool_created.encode_log_data()))
+        else {
+            panic!("PoolCreated not decoded");
+        };
+        assert_eq!(pools.fee, 500);
+
+        // Creation events from anywhere but the watched factories are ignored
+        assert!(NewPool::decode(&log(pool, pool_created.encode_log_data())).is_none());
+    }
+
+    #[test]
+    fn rate_limits_and_backs_off_checks() {
+        let pool = address!("0x3000000000000000000000000000000000000003");
+        let now = Instant::now();
+        let mut onboarding = Onboarding::default();
+        onboarding.add(NewPool::Curve(pool), now);
+
+        assert!(onboarding.start_check(&pool, now).is_some());
+        // A check in flight is not started twice
+        assert!(onboarding.start_check(&pool, now).is_none());
+
+        onboarding.not_liquid(&pool, now);
+        assert!(onboarding.start_check(&pool, now).is_none());
+        assert!(onboarding
+            .start_check(&pool, now + RECHECK_INTERVAL)
+            .is_some());
+
+        let later = now + RECHECK_INTERVAL;
+        assert_eq!(onboarding.failed(&pool, later), Some(RETRY_BACKOFF));
+        assert!(onboarding
+            .start_check(&pool, later + RETRY_BACKOFF)
+            .is_some());
+        assert_eq!(onboarding.failed(&pool, later), Some(RETRY_BACKOFF * 2));
+        assert!(onboarding.is_pending(&pool));
+    }
+}
@@ -17,12 +17,26 @@ impl PoolAddress {
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct TickDataReader {
-    block: u64,
-    pool: Address,
-    current_tick: I24,
-    sqrt_price_x96: U160,
-    liquidity: u128,
-    ticks: Vec<TickSync>,
+    pub block: u64,
+    pub pool: Address,
+    pub current_tick: I24,
+    pub sqrt_price_x96: U160,
+    pub liquidity: u128,
+    pub ticks: Vec<TickSync>,
+}
+
+impl TickDataReader {
+    /// Tag a pool's tick data with its address, as stored in `ticks.json`
+    pub fn new(pool: Address, tick_data: TickData) -> Self {
+        Self {
+            block: tick_data.block,
+            pool,
+            current_tick: tick_data.current_tick,
+            sqrt_price_x96: tick_data.sqrt_price_x96,
+            liquidity: tick_data.liquidity,
+            ticks: tick_data.ticks,
+        }
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -54,6 +68,9 @@ pub struct EnvParser {
     pub pools_v3: Vec<Pools>,
     pub curve_pools: Vec<CurvePools>,
     pub tick_map: TickMap,
+    pub backrun: Option<BackrunConfig>,
+    pub backtest: Option<BacktestConfig>,
+    pub pool_filter: PoolFilter,
 }
 
 impl<'a> EnvParser {
@@ -84,6 +101,30 @@ impl<'a> EnvParser {
         let ticks_reader = BufReader::new(ticks_file);
         let tick_data_reader: Vec<TickDataReader> = from_reader(ticks_reader)?;
 
+        // Mempool backrun search is only enabled when configured
+        let backrun = match env::var("BACKRUN_CONFIG_PATH") {
+            Ok(path) => Some(from_reader(BufReader::new(File::open(path)?))?),
+            Err(_) => None,
+        };
+
+        // The solver replays recorded blocks instead of scanning when a backtest is configured
+        let backtest = match env::var("BACKTEST_CONFIG_PATH") {
+            Ok(path) => Some(from_reader(BufReader::new(File::open(path)?))?),
+            Err(_) => None,
+        };
+
+        // Pool filtering falls back to the defaults when no thresholds are configured
+        let mut pool_filter: PoolFilter = match env::var("POOL_FILTER_PATH") {
+            Ok(path) => from_reader(BufReader::new(File::open(path)?))?,
+            Err(_) => PoolFilter::default(),
+        };
+
+        // Pools the Curve math is known to fail on are always denied
+        if let Ok(path) = env::var("CURVE_ERROR_POOLS_PATH") {
+            let error_pools: CurveErrorPools = from_reader(BufReader::new(File::open(path)?))?;
+            pool_filter.deny.extend(error_pools.errors);
+        }
+
         Ok(Self {
             ws_address: env::var("WEBSOCKET_ENDPOINT")?,
             pool_address: from_reader(pool_reader)?,
@@ -95,6 +136,9 @@ impl<'a> EnvParser {
                 .iter()
                 .map(|tdr| (tdr.pool, TickData::from(tdr.clone())))
                 .collect(),
+            backrun,
+            backtest,
+            pool_filter,
         })
     }
 }
@@ -1,29 +1,44 @@
 use super::*;
 
+pub mod parity;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CurvePools {
     pub tokens: Vec<Address>,
     pub balances: Vec<U256>,
     pub fee: U256,
     pub a: U256,
+    /// `A_precise()`, for pools that have it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub a_precise: Option<U256>,
     pub address: Address,
 }
 
 impl CurvePools {
-    pub async fn fetch_balances<'a>(provider: &SolverProvider, pools: &mut Vec<CurvePools>) {
+    pub fn family(&self) -> CurveFamily {
+        match self.a_precise {
+            Some(_) => CurveFamily::Precise,
+            None => CurveFamily::Legacy,
+        }
+    }
+
+    /// Fill in the balances of `pools` as of `block`, leaving those of failing pools untouched
+    pub async fn fetch_balances<'a>(
+        provider: &SolverProvider,
+        pools: &mut Vec<CurvePools>,
+        block: BlockId,
+    ) {
         let provider = Arc::new(provider.clone());
 
         let mut tasks = FuturesUnordered::new();
 
         for pool in pools.iter_mut() {
             let provider = Arc::clone(&provider);
-            let pool_ptr: *mut CurvePools = pool;
 
             tasks.push(async move {
-                let pool = unsafe { &mut *pool_ptr };
                 let contract = CurvePool::new(pool.address, provider.as_ref().clone());
                 let contract_1 = CurvePool1::new(pool.address, provider.as_ref().clone());
-                let mut multicall = provider.multicall().dynamic();
+                let mut multicall = provider.multicall().dynamic().block(block);
 
                 for i in 0..pool.tokens.len() {
                     multicall = multicall.add_dynamic(contract.balances(U256::from(i)));
@@ -32,7 +47,7 @@ impl CurvePools {
                 if let Ok(bals) = multicall.aggregate().await {
                     pool.balances = bals;
                 } else {
-                    let mut multicall = provider.multicall().dynamic();
+                    let mut multicall = provider.multicall().dynamic().block(block);
 
                     for i in 0..pool.tokens.len() {
                         multicall = multicall.add_dynamic(contract_1.balances(i as i128));
@@ -55,12 +70,16 @@ pub struct TokenData {
     pub xp: Vec<BigInt>,
     pub precisions: Vec<BigInt>,
     pub fee: BigInt,
+    /// Amplification as the pool's math uses it, `A_precise` for the precise family
     pub a: BigInt,
+    pub family: CurveFamily,
     pub slippage: Vec<BigInt>,
 }
 
 impl TokenData {
     fn new(cp: CurvePools, precisions: Vec<BigInt>) -> Self {
+        let family = cp.family();
+
         Self {
             tokens: cp.tokens,
             xp: cp
@@ -71,68 +90,124 @@ impl TokenData {
                 .collect(),
             precisions,
             fee: cp.fee.to_big_int(),
-            a: cp.a.to_big_int(),
+            a: cp.a_precise.unwrap_or(cp.a).to_big_int(),
+            family,
             slippage: Vec::default(),
         }
     }
 
     fn calc_slippage(&mut self, dx: BigInt) {
-        let fee_denomination = BigInt::from(10_000_000_000u128);
         let d = self.get_d();
-
-        let precision = BigInt::from(PRECISION);
         let n = self.tokens.len();
 
+        // Edges are read back by position, so the previous amount's entries have to go
+        self.slippage.clear();
         for i in 0..n {
             for j in 0..n {
                 if i != j {
-                    let x = self.xp[i] + ((dx * precision) / self.precisions[i]);
-                    let y = self.get_y(i, j, d, x);
-                    let dy = ((self.xp[j] - y - BigInt::ONE) * self.precisions[j]) / precision;
-                    let _fee = (self.fee * dy) / fee_denomination;
-                    let dy = dy - _fee;
-
+                    // Swaps the pool would revert count as no output, like failed v3 quotes
+                    let dy = d
+                        .and_then(|d| self.get_dy_with_d(i, j, dx, d))
+                        .unwrap_or_default();
                     self.slippage.push(calc_slippage(dx, dy, &mut None));
                 }
             }
         }
     }
 
-    fn get_d(&self) -> BigInt {
+    pub fn get_amount_out<'a>(
+        &self,
+        token_in: &Address,
+        token_out: &Address,
+        amount_in: BigInt,
+    ) -> Result<BigInt, CustomError<'a>> {
+        let i = self
+            .tokens
+            .iter()
+            .position(|t| t == token_in)
+            .ok_or_else(|| CustomError::AddressNotFound(*token_in))?;
+        let j = self
+            .tokens
+            .iter()
+            .position(|t| t == token_out)
+            .ok_or_else(|| CustomError::AddressNotFound(*token_out))?;
+
+        if self.xp.iter().any(|x| x.is_zero()) {
+            return Err(CustomError::NotFound("curve pool balances"));
+        }
+
+        self.get_dy(i, j, amount_in)
+            .ok_or(CustomError::NotFound("curve amount out"))
+    }
+
+    /// `get_dy(i, j, dx)` of the pool contract, `None` where the contract reverts
+    pub fn get_dy(&self, i: usize, j: usize, dx: BigInt) -> Option<BigInt> {
+        self.get_dy_with_d(i, j, dx, self.get_d()?)
+    }
+
+    fn get_dy_with_d(&self, i: usize, j: usize, dx: BigInt, d: BigInt) -> Option<BigInt> {
+        let fee_denominator = BigInt::from(FEE_DENOMINATOR);
+        let precision = BigInt::from(PRECISION);
+
+        let x = self.xp[i] + ((dx * precision) / self.precisions[i]);
+        let y = self.get_y(i, j, d, x)?;
+        let dy = self.xp[j] - y - BigInt::ONE;
+        if dy.is_negative() {
+            return None;
+        }
+
+        match self.family {
+            CurveFamily::Legacy => {
+                let dy = (dy * self.precisions[j]) / precision;
+                Some(dy - (self.fee * dy) / fee_denominator)
+            }
+            CurveFamily::Precise => {
+                let fee = (self.fee * dy) / fee_denominator;
+                Some(((dy - fee) * self.precisions[j]) / precision)
+            }
+        }
+    }
+
+    fn get_d(&self) -> Option<BigInt> {
         let n = self.tokens.len();
+        let a_precision = self.family.a_precision();
         let ann = self.a * BigInt::from(n);
-        let ann_1 = ann - BigInt::ONE;
         let s = self.xp.iter().sum::<BigInt>();
         let mut d = s;
         let n = BigInt::from(n);
         let n_1 = n + BigInt::ONE;
 
-        if s.is_zero() {
-            return BigInt::default();
+        // The contract divides by every balance, so an emptied coin makes swaps revert
+        if ann.is_zero() || self.xp.iter().any(|x| x.is_zero()) {
+            return None;
         }
 
         let mut d_prev;
         let mut d_p;
         for _ in 0..255 {
             d_p = d;
-            for _x in self.xp.clone() {
-                // TODO: Handle divide by 0
-                // If division by 0, this will be borked: only withdrawal will work. And that is good
-                d_p = (d_p * d) / (_x * n);
+            for _x in &self.xp {
+                d_p = (d_p * d) / (*_x * n);
             }
             d_prev = d;
-            d = (((ann * s) + (n * d_p)) * d) / ((ann_1 * d) + (n_1 * d_p));
+            d = (((ann * s / a_precision) + (n * d_p)) * d)
+                / (((ann - a_precision) * d / a_precision) + (n_1 * d_p));
 
             if is_abs_le_1(&d, &d_prev) {
-                break;
+                return Some(d);
             }
         }
 
-        d
+        // Legacy pools return the last estimate, newer ones revert when it does not converge
+        match self.family {
+            CurveFamily::Legacy => Some(d),
+            CurveFamily::Precise => None,
+        }
     }
 
-    fn get_y(&self, i: usize, j: usize, d: BigInt, x: BigInt) -> BigInt {
+    fn get_y(&self, i: usize, j: usize, d: BigInt, x: BigInt) -> Option<BigInt> {
         let n = self.tokens.len();
+        let a_precision = self.family.a_precision();
         let ann = self.a * BigInt::from(n);
         let mut c = d;
         let mut s_ = BigInt::default();
@@ -151,25 +226,32 @@ impl TokenData {
             c = (c * d) / (_x * BigInt::from(n));
         }
 
-        c = (c * d) / (ann * n_big);
-        let b = s_ + d / ann;
+        c = (c * d * a_precision) / (ann * n_big);
+        let b = s_ + d * a_precision / ann;
         let mut y_prev;
         let mut y = d;
 
         for _i in 0..255 {
             y_prev = y;
-            y = ((y * y) + c) / ((y * BigInt::from(2)) + b - d);
+            let denominator = (y * BigInt::from(2)) + b - d;
+            if !denominator.is_positive() {
+                return None;
+            }
+            y = ((y * y) + c) / denominator;
 
             if is_abs_le_1(&y_prev, &y) {
-                break;
+                return Some(y);
             }
         }
 
-        y
+        match self.family {
+            CurveFamily::Legacy => Some(y),
+            CurveFamily::Precise => None,
+        }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PoolData {
     pub data: HashMap<Address, TokenData>,
 }
@@ -226,3 +308,56 @@ impl PoolData {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TOKENS: [Address; 3] = [
+        address!("0x1000000000000000000000000000000000000001"),
+        address!("0x2000000000000000000000000000000000000002"),
+        address!("0x3000000000000000000000000000000000000003"),
+    ];
+    const POOL: Address = address!("0x00000000000000000000000000000000000000C3");
+
+    fn pool_data() -> PoolData {
+        let mut tokens = TokenMap::new();
+        for token in TOKENS {
+            tokens.insert(token, token!(1, token, 18));
+        }
+        let pools = [CurvePools {
+            tokens: TOKENS.to_vec(),
+            balances: [1_000_000, 2_000_000, 3_000_000]
+                .map(|balance| U256::from(balance) * U256::from(PRECISION))
+                .to_vec(),
+            fee: U256::from(4_000_000),
+            a: U256::from(200),
+            a_precise: None,
+            address: POOL,
+        }];
+
+        PoolData::new(&pools, &tokens).unwrap()
+    }
+
+    #[test]
+    fn test_calc_slippage_replaces_previous_amount() {
+        let small = BigInt::from(PRECISION);
+        let large = BigInt::from(100_000 * PRECISION);
+
+        let mut repriced = pool_data();
+        repriced.calc_slippage(small);
+        repriced.calc_slippage(large);
+
+        let mut fresh = pool_data();
+        fresh.calc_slippage(large);
+
+        // One entry per ordered pair of coins, all priced for the second amount
+        let slippage = &repriced.data[&POOL].slippage;
+        assert_eq!(slippage.len(), TOKENS.len() * (TOKENS.len() - 1));
+        assert_eq!(slippage, &fresh.data[&POOL].slippage);
+
+        let mut small_only = pool_data();
+        small_only.calc_slippage(small);
+        assert_ne!(slippage, &small_only.data[&POOL].slippage);
+    }
+}
@@ -0,0 +1,139 @@
+use super::*;
+use crate::snapshot::aggregate;
+
+// Swap sizes recorded for every ordered pair of coins, in bps of the input coin's balance
+const DX_BPS: [u64; 5] = [1, 10, 100, 1_000, 5_000];
+
+/// `get_dy(i, j, dx)` of the pool contract, `None` if the call reverted
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RecordedQuote {
+    pub i: usize,
+    pub j: usize,
+    pub dx: U256,
+    pub dy: Option<U256>,
+}
+
+/// A pool's state next to the quotes its contract gave for it, replayed offline through
+/// [`TokenData`] by the parity tests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurveFixture {
+    pub family: CurveFamily,
+    /// Block state and quotes were read at, the parity tests reject fixtures without one
+    pub block: Option<u64>,
+    pub pool: CurvePools,
+    pub decimals: Vec<u8>,
+    pub quotes: Vec<RecordedQuote>,
+    /// Why [`TokenData`] doesn't model the pool, its quotes are kept but not held to parity
+    #[serde(default)]
+    pub unsupported: Option<String>,
+}
+
+/// A quote the solver's math does not reproduce to the wei
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Mismatch {
+    pub pool: Address,
+    pub quote: RecordedQuote,
+    pub computed: Option<BigInt>,
+}
+
+impl CurveFixture {
+    /// Read parameters, balances and `get_dy` quotes of `pool` in `block`
+    pub async fn record<'a>(
+        provider: &SolverProvider,
+        pool: &CurvePools,
+        decimals: Vec<u8>,
+        block: u64,
+    ) -> Result<Self, CustomError<'a>> {
+        let block_id = BlockId::number(block);
+        let contract = CurvePool::new(pool.address, provider.clone());
+
+        let mut pools = vec![CurvePools {
+            tokens: pool.tokens.clone(),
+            balances: Vec::new(),
+            fee: contract.fee().block(block_id).call().await?,
+            a: contract.A().block(block_id).call().await?,
+            a_precise: CurveMetaPool::new(pool.address, provider.clone())
+                .A_precise()
+                .block(block_id)
+                .call()
+                .await
+                .ok(),
+            address: pool.address,
+        }];
+        CurvePools::fetch_balances(provider, &mut pools, block_id).await;
+        let pool = pools.remove(0);
+
+        if pool.balances.len() != pool.tokens.len() {
+            return Err(CustomError::NotFound("curve pool balances"));
+        }
+
+        let n = pool.tokens.len();
+        let mut quotes = Vec::new();
+        for i in 0..n {
+            for j in (0..n).filter(|j| *j != i) {
+                for bps in DX_BPS {
+                    let dx = pool.balances[i] * U256::from(bps) / U256::from(10_000);
+                    if !dx.is_zero() {
+                        quotes.push(RecordedQuote { i, j, dx, dy: None });
+                    }
+                }
+            }
+        }
+
+        let calls = quotes
+            .iter()
+            .map(|quote| {
+                let call = CurvePool::get_dyCall {
+                    i: quote.i as i128,
+                    j: quote.j as i128,
+                    dx: quote.dx,
+                };
+                (pool.address, call)
+            })
+            .collect();
+        for (quote, dy) in quotes
+            .iter_mut()
+            .zip(aggregate(provider, block, calls).await?)
+        {
+            quote.dy = dy;
+        }
+
+        Ok(Self {
+            family: pool.family(),
+            block: Some(block),
+            pool,
+            decimals,
+            quotes,
+            unsupported: None,
+        })
+    }
+
+    pub fn token_data(&self) -> TokenData {
+        let precisions = self
+            .decimals
+            .iter()
+            .map(|decimals| BigInt::from(10u128.pow(u32::from(*decimals))))
+            .collect();
+
+        TokenData::new(self.pool.clone(), precisions)
+    }
+
+    /// Recorded quotes the solver computes differently, including reverts it does not predict
+    pub fn mismatches(&self) -> Vec<Mismatch> {
+        let token_data = self.token_data();
+
+        self.quotes
+            .iter()
+            .filter_map(|quote| {
+                let computed = token_data.get_dy(quote.i, quote.j, quote.dx.to_big_int());
+                let expected = quote.dy.map(|dy| dy.to_big_int());
+
+                (computed != expected).then(|| Mismatch {
+                    pool: self.pool.address,
+                    quote: quote.clone(),
+                    computed,
+                })
+            })
+            .collect()
+    }
+}
@@ -10,10 +10,10 @@ pub type TokenMap = HashMap<Address, Token>;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pools {
-    token0: Address,
-    token1: Address,
-    fee: u16,
-    address: Address,
+    pub token0: Address,
+    pub token1: Address,
+    pub fee: u16,
+    pub address: Address,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -36,6 +36,77 @@ pub fn token_metadata_to_tokens(token_metadata: &[TokenMetadata]) -> TokenMap {
         .collect()
 }
 
+/// Build every pool set from the parsed environment and fetch its on-chain state
+pub async fn load_pools<'a>(
+    provider: &SolverProvider,
+    env_parser: &EnvParser,
+    token_map: &TokenMap,
+) -> Result<(v2::PoolData, v3::PoolData, curve::PoolData), CustomError<'a>> {
+    let mut pool_data_v2: v2::PoolData = debug_time!("pool_data_v2()", {
+        v2::PoolData::new(&env_parser.pools_v2, token_map)?
+    });
+
+    debug_time!("update_reserve()", {
+        pool_data_v2
+            .update_reserves(provider, &env_parser.pool_address.v2)
+            .await?
+    });
+
+    debug_time!("calulate_start_price_v2()", {
+        pool_data_v2.calc_start_price()?
+    });
+
+    let mut pool_data_v3: v3::PoolData = debug_time!("pool_data_v3()", {
+        v3::PoolData::new(&env_parser.pools_v3, token_map)?
+    });
+
+    debug_time!("calulate_start_price_v3()", {
+        pool_data_v3.calc_start_price(&env_parser.tick_map)?
+    });
+
+    let mut curve_pools = env_parser.curve_pools.clone();
+
+    debug_time!("fetch_balances()", {
+        CurvePools::fetch_balances(provider, &mut curve_pools, BlockId::latest()).await;
+    });
+
+    let curve_pool_data: curve::PoolData = debug_time!("cuve_data_pools()", {
+        curve::PoolData::new(&curve_pools, token_map)?
+    });
+
+    Ok((pool_data_v2, pool_data_v3, curve_pool_data))
+}
+
+/// Quote a single hop through whichever pool set holds `pool`
+pub fn get_amount_out<'a>(
+    pool_data_v2: &v2::PoolData,
+    pool_data_v3: &v3::PoolData,
+    curve_pool_data: &curve::PoolData,
+    pool: &Address,
+    token_in: &Address,
+    token_out: &Address,
+    amount_in: BigInt,
+) -> Result<(Protocol, BigInt), CustomError<'a>> {
+    if let Some(token_data) = pool_data_v2.data.get(pool) {
+        Ok((
+            Protocol::UniswapV2,
+            token_data.get_amount_out(token_in, amount_in)?,
+        ))
+    } else if let Some(token_data) = pool_data_v3.data.get(pool) {
+        Ok((
+            Protocol::UniswapV3,
+            token_data.get_amount_out(token_in, amount_in)?,
+        ))
+    } else if let Some(token_data) = curve_pool_data.data.get(pool) {
+        Ok((
+            Protocol::Curve,
+            token_data.get_amount_out(token_in, token_out, amount_in)?,
+        ))
+    } else {
+        Err(CustomError::AddressNotFound(*pool))
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct TokenDetails {
     pub token: Token,
@@ -1,7 +1,7 @@
 use super::*;
 use uniswap_v2_sdk::prelude::*;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct PoolData {
     pub data: HashMap<Address, TokenData>,
 }
@@ -51,68 +51,14 @@ impl PoolData {
 
     pub fn calc_start_price<'a>(&mut self) -> Result<(), CustomError<'a>> {
         for token_data in self.data.values_mut() {
-            let token0mount_a = CurrencyAmount::from_raw_amount(
-                token_data.token0.token.clone(),
-                token_data.reserve0,
-            )?;
-            let token0mount_b = CurrencyAmount::from_raw_amount(
-                token_data.token1.token.clone(),
-                token_data.reserve1,
-            )?;
-
-            let pair = Pair::new(token0mount_a.clone(), token0mount_b.clone())?;
-
-            let mut price = pair.token0_price();
-            token_data.token0.price_start = (price.numerator() * token_data.token0.scale())
-                / (price.denominator() * token_data.token0.precision());
-
-            price = pair.token1_price();
-            token_data.token1.price_start = (price.numerator() * token_data.token1.scale())
-                / (price.denominator() * token_data.token1.precision());
+            token_data.calc_start_price()?;
         }
         Ok(())
     }
 
     pub fn calc_effective_price<'a>(&mut self, amount: BigInt) -> Result<(), CustomError<'a>> {
-        for (_pool_addr, token_data) in self.data.iter_mut() {
-            let token0mount_a = CurrencyAmount::from_raw_amount(
-                token_data.token0.token.clone(),
-                token_data.reserve0,
-            )?;
-            let token0mount_b = CurrencyAmount::from_raw_amount(
-                token_data.token1.token.clone(),
-                token_data.reserve1,
-            )?;
-
-            let pair = Pair::new(token0mount_a.clone(), token0mount_b.clone())?;
-
-            let amount0_in =
-                CurrencyAmount::from_raw_amount(token_data.token0.token.clone(), amount)?;
-            let (amount0_out, _) = match pair.get_output_amount(&amount0_in, false) {
-                Ok(a) => a,
-                Err(_err) => {
-                    // log::error!("amount0_out calculation failed for {_pool_addr}, due to {_err}");
-                    continue;
-                }
-            };
-
-            let amount1_out =
-                CurrencyAmount::from_raw_amount(token_data.token1.token.clone(), amount)?;
-            let (amount1_in, _) = match pair.get_input_amount(&amount0_in, false) {
-                Ok(a) => a,
-                Err(_err) => {
-                    // log::error!("amount1_in calculation failed for {_pool_addr}, due to {_err}");
-                    continue;
-                }
-            };
-
-            let mut price = Price::from_currency_amounts(amount0_in, amount0_out);
-            token_data.token0.price_effective = (price.numerator() * token_data.token0.scale())
-                / (price.denominator() * token_data.token0.precision());
-
-            price = Price::from_currency_amounts(amount1_out, amount1_in);
-            token_data.token1.price_effective = (price.numerator() * token_data.token1.scale())
-                / (price.denominator() * token_data.token1.precision());
+        for token_data in self.data.values_mut() {
+            token_data.calc_effective_price(amount)?;
         }
         Ok(())
     }
@@ -122,17 +68,7 @@ impl PoolData {
         slippage_adj: &mut Option<BigInt>,
     ) -> Result<(), CustomError<'a>> {
         for token_data in self.data.values_mut() {
-            token_data.token0.slippage = calc_slippage(
-                token_data.token0.price_start,
-                token_data.token0.price_effective,
-                slippage_adj,
-            );
-
-            token_data.token1.slippage = calc_slippage(
-                token_data.token1.price_start,
-                token_data.token1.price_effective,
-                slippage_adj,
-            );
+            token_data.calc_slippage(slippage_adj);
         }
 
         Ok(())
@@ -183,6 +119,134 @@ impl TokenData {
         self.reserve0 = reserves.reserve0;
         self.reserve1 = reserves.reserve1;
     }
+
+    fn pair<'a>(&self) -> Result<Pair, CustomError<'a>> {
+        let amount0 = CurrencyAmount::from_raw_amount(self.token0.token.clone(), self.reserve0)?;
+        let amount1 = CurrencyAmount::from_raw_amount(self.token1.token.clone(), self.reserve1)?;
+
+        Ok(Pair::new(amount0, amount1)?)
+    }
+
+    pub fn calc_start_price<'a>(&mut self) -> Result<(), CustomError<'a>> {
+        let pair = self.pair()?;
+
+        let mut price = pair.token0_price();
+        self.token0.price_start = (price.numerator() * self.token0.scale())
+            / (price.denominator() * self.token0.precision());
+
+        price = pair.token1_price();
+        self.token1.price_start = (price.numerator() * self.token1.scale())
+            / (price.denominator() * self.token1.precision());
+
+        Ok(())
+    }
+
+    /// Price a trade of `amount` both ways, leaving the prices as they were when the pair can't fill it
+    pub fn calc_effective_price<'a>(&mut self, amount: BigInt) -> Result<(), CustomError<'a>> {
+        let pair = self.pair()?;
+
+        let amount0_in = CurrencyAmount::from_raw_amount(self.token0.token.clone(), amount)?;
+        let Ok((amount0_out, _)) = pair.get_output_amount(&amount0_in, false) else {
+            return Ok(());
+        };
+
+        let amount1_out = CurrencyAmount::from_raw_amount(self.token1.token.clone(), amount)?;
+        let Ok((amount1_in, _)) = pair.get_input_amount(&amount0_in, false) else {
+            return Ok(());
+        };
+
+        let mut price = Price::from_currency_amounts(amount0_in, amount0_out);
+        self.token0.price_effective = (price.numerator() * self.token0.scale())
+            / (price.denominator() * self.token0.precision());
+
+        price = Price::from_currency_amounts(amount1_out, amount1_in);
+        self.token1.price_effective = (price.numerator() * self.token1.scale())
+            / (price.denominator() * self.token1.precision());
+
+        Ok(())
+    }
+
+    pub fn calc_slippage(&mut self, slippage_adj: &mut Option<BigInt>) {
+        self.token0.slippage = calc_slippage(
+            self.token0.price_start,
+            self.token0.price_effective,
+            slippage_adj,
+        );
+
+        self.token1.slippage = calc_slippage(
+            self.token1.price_start,
+            self.token1.price_effective,
+            slippage_adj,
+        );
+    }
+
+    pub fn get_amount_out<'a>(
+        &self,
+        token_in: &Address,
+        amount_in: BigInt,
+    ) -> Result<BigInt, CustomError<'a>> {
+        let input_token = if token_in == &self.token0.token.address() {
+            self.token0.token.clone()
+        } else if token_in == &self.token1.token.address() {
+            self.token1.token.clone()
+        } else {
+            return Err(CustomError::AddressNotFound(*token_in));
+        };
+
+        let pair = Pair::new(
+            CurrencyAmount::from_raw_amount(self.token0.token.clone(), self.reserve0)?,
+            CurrencyAmount::from_raw_amount(self.token1.token.clone(), self.reserve1)?,
+        )?;
+
+        let amount_in = CurrencyAmount::from_raw_amount(input_token, amount_in)?;
+        let (amount_out, _) = pair.get_output_amount(&amount_in, false)?;
+
+        Ok(amount_out.quotient())
+    }
+
+    pub fn get_amount_in<'a>(
+        &self,
+        token_out: &Address,
+        amount_out: BigInt,
+    ) -> Result<BigInt, CustomError<'a>> {
+        let output_token = if token_out == &self.token0.token.address() {
+            self.token0.token.clone()
+        } else if token_out == &self.token1.token.address() {
+            self.token1.token.clone()
+        } else {
+            return Err(CustomError::AddressNotFound(*token_out));
+        };
+
+        let pair = Pair::new(
+            CurrencyAmount::from_raw_amount(self.token0.token.clone(), self.reserve0)?,
+            CurrencyAmount::from_raw_amount(self.token1.token.clone(), self.reserve1)?,
+        )?;
+
+        let amount_out = CurrencyAmount::from_raw_amount(output_token, amount_out)?;
+        let (amount_in, _) = pair.get_input_amount(&amount_out, false)?;
+
+        Ok(amount_in.quotient())
+    }
+
+    /// Move the reserves as if `amount_in` of `token_in` was swapped for `amount_out`
+    pub fn apply_swap<'a>(
+        &mut self,
+        token_in: &Address,
+        amount_in: BigInt,
+        amount_out: BigInt,
+    ) -> Result<(), CustomError<'a>> {
+        if token_in == &self.token0.token.address() {
+            self.reserve0 += amount_in;
+            self.reserve1 -= amount_out;
+        } else if token_in == &self.token1.token.address() {
+            self.reserve1 += amount_in;
+            self.reserve0 -= amount_out;
+        } else {
+            return Err(CustomError::AddressNotFound(*token_in));
+        }
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
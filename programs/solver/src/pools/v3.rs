@@ -1,5 +1,6 @@
 use super::*;
 
+#[derive(Clone)]
 pub struct PoolData {
     pub data: HashMap<Address, TokenData>,
 }
@@ -246,6 +247,34 @@ impl TokenData {
         }
     }
 
+    pub fn get_amount_out<'a>(
+        &self,
+        token_in: &Address,
+        amount_in: BigInt,
+    ) -> Result<BigInt, CustomError<'a>> {
+        let input_token = if token_in == &self.token_a.token.address() {
+            self.token_a.token.clone()
+        } else if token_in == &self.token_b.token.address() {
+            self.token_b.token.clone()
+        } else {
+            return Err(CustomError::AddressNotFound(*token_in));
+        };
+
+        let pool = Pool::new(
+            self.token_a.token.clone(),
+            self.token_b.token.clone(),
+            self.fee(),
+            self.sqrt_price_x96,
+            self.liquidity,
+        )?;
+
+        let amount_in = CurrencyAmount::from_raw_amount(input_token, amount_in)?;
+        let amount_out =
+            pool.get_output_amount_sync(&amount_in, None, self.current_tick, &self.ticks)?;
+
+        Ok(amount_out.quotient())
+    }
+
     fn fee(&self) -> FeeAmount {
         use FeeAmount::*;
         match self.fee {
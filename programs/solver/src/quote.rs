@@ -0,0 +1,181 @@
+use super::*;
+
+/// Curve mid prices are sampled by swapping 1 bps of the input balance
+const CURVE_SAMPLE_DIVISOR: u128 = 10_000;
+
+/// Mid price of a pool in `quote` per one `base`, both in whole token units
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct Quote {
+    pub pool: Address,
+    pub protocol: Protocol,
+    pub base: Address,
+    pub quote: Address,
+    pub price: f64,
+    pub fee_bps: f64,
+}
+
+impl Quote {
+    /// Orients a `token_out` per `token_in` price so the lower address is always the base
+    pub fn new(
+        pool: Address,
+        protocol: Protocol,
+        token_in: Address,
+        token_out: Address,
+        price: f64,
+        fee_bps: f64,
+    ) -> Option<Self> {
+        if !price.is_finite() || price <= 0.0 {
+            return None;
+        }
+
+        let (base, quote, price) = if token_in < token_out {
+            (token_in, token_out, price)
+        } else {
+            (token_out, token_in, price.recip())
+        };
+
+        Some(Self {
+            pool,
+            protocol,
+            base,
+            quote,
+            price,
+            fee_bps,
+        })
+    }
+
+    pub fn pair(&self) -> (Address, Address) {
+        (self.base, self.quote)
+    }
+}
+
+fn to_f64(value: impl ToString) -> f64 {
+    value.to_string().parse().unwrap_or_default()
+}
+
+fn scale(decimals: u8) -> f64 {
+    10f64.powi(i32::from(decimals))
+}
+
+pub fn quote_v2(pool: &Address, token_data: &v2::TokenData) -> Option<Quote> {
+    let token0 = &token_data.token0.token;
+    let token1 = &token_data.token1.token;
+    let reserve0 = to_f64(token_data.reserve0) / scale(token0.decimals());
+    let reserve1 = to_f64(token_data.reserve1) / scale(token1.decimals());
+
+    Quote::new(
+        *pool,
+        Protocol::UniswapV2,
+        token0.address(),
+        token1.address(),
+        reserve1 / reserve0,
+        f64::from(token_data.fee) / 100.0,
+    )
+}
+
+pub fn quote_v3(pool: &Address, token_data: &v3::TokenData) -> Option<Quote> {
+    // The pool price is always token1 per token0 in raw units
+    let (token0, token1) =
+        if token_data.token_a.token.address() < token_data.token_b.token.address() {
+            (&token_data.token_a.token, &token_data.token_b.token)
+        } else {
+            (&token_data.token_b.token, &token_data.token_a.token)
+        };
+    let sqrt_price = to_f64(token_data.sqrt_price_x96) / 2f64.powi(96);
+    let price = sqrt_price * sqrt_price * scale(token0.decimals()) / scale(token1.decimals());
+
+    Quote::new(
+        *pool,
+        Protocol::UniswapV3,
+        token0.address(),
+        token1.address(),
+        price,
+        f64::from(token_data.fee) / 100.0,
+    )
+}
+
+pub fn quote_curve(pool: &Address, token_data: &curve::TokenData) -> Vec<Quote> {
+    let fee = to_f64(token_data.fee) / 1e10;
+    let divisor = BigInt::from(PRECISION) * BigInt::from(CURVE_SAMPLE_DIVISOR);
+    let n = token_data.tokens.len();
+    let mut quotes = Vec::new();
+
+    for i in 0..n {
+        for j in i + 1..n {
+            let (token_in, token_out) = (token_data.tokens[i], token_data.tokens[j]);
+            let amount_in = token_data.xp[i] * token_data.precisions[i] / divisor;
+
+            if amount_in.is_zero() {
+                continue;
+            }
+
+            let Ok(amount_out) = token_data.get_amount_out(&token_in, &token_out, amount_in) else {
+                continue;
+            };
+
+            // Gross the output up by the fee so the quote is a mid price
+            let price = (to_f64(amount_out) / to_f64(token_data.precisions[j]))
+                / (to_f64(amount_in) / to_f64(token_data.precisions[i]))
+                / (1.0 - fee);
+
+            quotes.extend(Quote::new(
+                *pool,
+                Protocol::Curve,
+                token_in,
+                token_out,
+                price,
+                fee * 10_000.0,
+            ));
+        }
+    }
+
+    quotes
+}
+
+/// Mid prices of every pool across all venues
+pub fn collect_quotes(
+    pool_data_v2: &v2::PoolData,
+    pool_data_v3: &v3::PoolData,
+    curve_pool_data: &curve::PoolData,
+) -> Vec<Quote> {
+    let v2 = pool_data_v2
+        .data
+        .iter()
+        .filter_map(|(pool, token_data)| quote_v2(pool, token_data));
+    let v3 = pool_data_v3
+        .data
+        .iter()
+        .filter_map(|(pool, token_data)| quote_v3(pool, token_data));
+    let curve = curve_pool_data
+        .data
+        .iter()
+        .flat_map(|(pool, token_data)| quote_curve(pool, token_data));
+
+    v2.chain(v3).chain(curve).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_is_oriented_by_address() {
+        let low = address!("0x0000000000000000000000000000000000000001");
+        let high = address!("0x0000000000000000000000000000000000000002");
+
+        let quote = Quote::new(Address::ZERO, Protocol::Curve, high, low, 4.0, 4.0).unwrap();
+
+        assert_eq!(quote.pair(), (low, high));
+        assert_eq!(quote.price, 0.25);
+        assert!(Quote::new(Address::ZERO, Protocol::Curve, low, high, 0.0, 4.0).is_none());
+        assert!(Quote::new(
+            Address::ZERO,
+            Protocol::Curve,
+            low,
+            high,
+            f64::INFINITY,
+            4.0
+        )
+        .is_none());
+    }
+}
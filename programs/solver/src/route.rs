@@ -0,0 +1,256 @@
+use super::*;
+
+/// Output rendering requested alongside a path query
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+/// A token amount in raw units next to its decimal rendering
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenAmount {
+    pub token: Address,
+    pub symbol: String,
+    pub decimals: u8,
+    pub raw: U256,
+    pub amount: String,
+}
+
+impl TokenAmount {
+    pub fn new(token: &Token, raw: U256) -> Self {
+        Self {
+            token: token.address(),
+            symbol: token.symbol().cloned().unwrap_or_default(),
+            decimals: token.decimals(),
+            raw,
+            amount: format_with_decimals(raw, u32::from(token.decimals())),
+        }
+    }
+}
+
+impl Display for TokenAmount {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} {}", self.amount, self.symbol)
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteHop {
+    pub pool: Address,
+    pub protocol: Protocol,
+    pub fee_percent: f64,
+    pub amount_in: TokenAmount,
+    pub amount_out: TokenAmount,
+    /// Percent of output lost against the pool's marginal price, fees excluded
+    pub price_impact: f64,
+}
+
+/// A priced path with every hop quoted in human units
+#[derive(Debug, Clone, Serialize)]
+pub struct RouteResult {
+    pub block: u64,
+    pub amount_in: TokenAmount,
+    pub expected_out: TokenAmount,
+    pub hops: Vec<RouteHop>,
+}
+
+impl RouteResult {
+    pub fn new<'a>(
+        pool_data_v2: &v2::PoolData,
+        pool_data_v3: &v3::PoolData,
+        curve_pool_data: &curve::PoolData,
+        token_map: &TokenMap,
+        path: &ShortestPath,
+        amount_in: U256,
+        block: u64,
+    ) -> Result<Self, CustomError<'a>> {
+        if path.pools.is_empty() {
+            return Err(CustomError::NotFound("route"));
+        }
+
+        let token = |address: &Address| {
+            token_map
+                .get(address)
+                .ok_or(CustomError::AddressNotFound(*address))
+        };
+        let quote = |pool: &Address, token_in: &Address, token_out: &Address, amount: BigInt| {
+            get_amount_out(
+                pool_data_v2,
+                pool_data_v3,
+                curve_pool_data,
+                pool,
+                token_in,
+                token_out,
+                amount,
+            )
+        };
+
+        let mut hops = Vec::with_capacity(path.pools.len());
+        let mut hop_in = amount_in.to_big_int();
+
+        for (idx, pool) in path.pools.iter().enumerate() {
+            let (token_in, token_out) = (&path.paths[idx], &path.paths[idx + 1]);
+            let (protocol, hop_out) = quote(pool, token_in, token_out, hop_in)?;
+
+            // The marginal price is sampled with a millionth of the hop input
+            let probe_in = (hop_in / BigInt::from(1_000_000)).max(BigInt::ONE);
+            let (_, probe_out) = quote(pool, token_in, token_out, probe_in)?;
+            // Nothing comes in after a hop that quoted nothing, so there is no price to compare
+            let price_impact = if hop_in.is_zero() || probe_out.is_zero() {
+                0.0
+            } else {
+                let ppm = BigInt::from(1_000_000)
+                    - (hop_out * probe_in * BigInt::from(1_000_000)) / (hop_in * probe_out);
+                ppm.to_string().parse::<f64>().unwrap_or_default() / 10_000.0
+            };
+
+            hops.push(RouteHop {
+                pool: *pool,
+                protocol,
+                fee_percent: fee_percent(pool_data_v2, pool_data_v3, curve_pool_data, pool),
+                amount_in: TokenAmount::new(token(token_in)?, U256::from_big_int(hop_in)),
+                amount_out: TokenAmount::new(token(token_out)?, U256::from_big_int(hop_out)),
+                price_impact,
+            });
+
+            hop_in = hop_out;
+        }
+
+        Ok(Self {
+            block,
+            amount_in: hops[0].amount_in.clone(),
+            expected_out: hops[hops.len() - 1].amount_out.clone(),
+            hops,
+        })
+    }
+
+    pub fn to_json<'a>(&self) -> Result<String, CustomError<'a>> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+}
+
+fn fee_percent(
+    pool_data_v2: &v2::PoolData,
+    pool_data_v3: &v3::PoolData,
+    curve_pool_data: &curve::PoolData,
+    pool: &Address,
+) -> f64 {
+    if let Some(token_data) = pool_data_v2.data.get(pool) {
+        f64::from(token_data.fee) / 10_000.0
+    } else if let Some(token_data) = pool_data_v3.data.get(pool) {
+        f64::from(token_data.fee) / 10_000.0
+    } else if let Some(token_data) = curve_pool_data.data.get(pool) {
+        // Curve fees are in 1e10 units
+        token_data
+            .fee
+            .to_string()
+            .parse::<f64>()
+            .unwrap_or_default()
+            / 100_000_000.0
+    } else {
+        0.0
+    }
+}
+
+impl Display for RouteResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Route at block {}: {} -> {}",
+            self.block, self.amount_in, self.expected_out
+        )?;
+        writeln!(
+            f,
+            "{:<3} {:<10} {:<42} {:>8} {:>32} {:>32} {:>8}",
+            "#", "protocol", "pool", "fee", "in", "out", "impact"
+        )?;
+
+        for (idx, hop) in self.hops.iter().enumerate() {
+            writeln!(
+                f,
+                "{:<3} {:<10} {:<42} {:>7.4}% {:>32} {:>32} {:>7.4}%",
+                idx + 1,
+                format!("{:?}", hop.protocol),
+                hop.pool.to_string(),
+                hop.fee_percent,
+                hop.amount_in.to_string(),
+                hop.amount_out.to_string(),
+                hop.price_impact
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WETH: Address = address!("0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    const USDC: Address = address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const POOL: Address = address!("0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc");
+
+    fn route<'a>(amount_in: U256) -> Result<RouteResult, CustomError<'a>> {
+        let token_map: TokenMap = [
+            (USDC, token!(1, USDC, 6, "USDC")),
+            (WETH, token!(1, WETH, 18, "WETH")),
+        ]
+        .into_iter()
+        .collect();
+        let pool = Pools {
+            token0: USDC,
+            token1: WETH,
+            fee: 3000,
+            address: POOL,
+        };
+
+        let mut pool_data_v2 = v2::PoolData::new(&[pool], &token_map).unwrap();
+        let token_data = pool_data_v2.data.get_mut(&POOL).unwrap();
+        token_data.reserve0 = BigInt::from(3_000_000_000_000u128); // 3m USDC
+        token_data.reserve1 = BigInt::from(1_000_000_000_000_000_000_000u128); // 1000 WETH
+
+        let path = ShortestPath::new(vec![WETH, USDC], vec![POOL], vec![3000], BigInt::ZERO);
+
+        RouteResult::new(
+            &pool_data_v2,
+            &v3::PoolData::new(&[], &token_map).unwrap(),
+            &curve::PoolData::new(&[], &token_map).unwrap(),
+            &token_map,
+            &path,
+            amount_in,
+            1,
+        )
+    }
+
+    #[test]
+    fn amounts_use_token_decimals() {
+        let result = route(U256::from(10).pow(U256::from(18))).unwrap();
+
+        assert_eq!(result.amount_in.amount, "1.000000000000000000");
+        assert_eq!(result.expected_out.symbol, "USDC");
+        assert_eq!(
+            result.expected_out.amount.split('.').nth(1).unwrap().len(),
+            6
+        );
+        assert_eq!(result.hops[0].fee_percent, 0.3);
+        assert!(result.to_json().unwrap().contains("\"symbol\": \"WETH\""));
+    }
+
+    #[test]
+    fn price_impact_grows_with_size() {
+        let small = route(U256::from(10).pow(U256::from(18))).unwrap();
+        let large = route(U256::from(10).pow(U256::from(20))).unwrap();
+
+        assert!(small.hops[0].price_impact < 0.2);
+        assert!(large.hops[0].price_impact > 9.0);
+    }
+
+    #[test]
+    fn zero_input_is_an_error() {
+        assert!(route(U256::ZERO).is_err());
+    }
+}
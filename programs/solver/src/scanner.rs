@@ -1,60 +1,174 @@
 use super::*;
+use alloy::pubsub::SubscriptionStream;
+use futures::FutureExt;
+use std::time::{Duration, Instant};
 use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use utils::metrics::*;
+
+// Logs buffered between the subscriptions and the loop applying them
+const LOG_CHANNEL_SIZE: usize = 1024;
+
+// First delay before resubscribing after a failed `eth_subscribe`, doubled up to 64x
+const RESUBSCRIBE_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Outcome of checking a pending pool in the background
+type Check = (Address, Result<Option<Promotion>, CustomError<'static>>);
 
 #[derive(Debug, Deserialize, Serialize, Clone, Copy)]
 pub struct InputData {
     pub token_a: Address,
     pub token_b: Address,
     pub amount_in: U256,
+    #[serde(default)]
+    pub lender: Option<Lender>,
+    #[serde(default)]
+    pub min_profit: U256,
+    #[serde(default)]
+    pub simulate: bool,
+    #[serde(default)]
+    pub executor: Option<Address>,
+    #[serde(default)]
+    pub format: OutputFormat,
 }
 
-async fn calculate_path<'a>(
+/// Price every pool for `amount_in` and collect the edges of those passing `pool_filter` into a single graph
+pub async fn build_swap_graph<'a>(
     pool_data_v2: &mut v2::PoolData,
     pool_data_v3: &mut v3::PoolData,
     curve_pool_data: &mut curve::PoolData,
-    input_data: InputData,
-) -> Result<(), CustomError<'a>> {
+    pool_filter: &PoolFilter,
+    amount_in: BigInt,
+) -> Result<(SwapGraph, BigInt), CustomError<'a>> {
+    let excluded = debug_time!("build_swap_graph::pool_filter()", {
+        pool_filter.excluded(pool_data_v2, pool_data_v3, curve_pool_data)
+    });
+
     let mut slippage_adj = Some(BigInt::MAX);
-    let amount_in = input_data.amount_in.to_big_int();
 
-    debug_time!("calculate_path::calc_effective_price()", {
+    debug_time!("build_swap_graph::calc_effective_price()", {
         pool_data_v2.calc_effective_price(amount_in)?;
     });
 
-    debug_time!("calculate_path::calc_slippage_v2()", {
+    debug_time!("build_swap_graph::calc_slippage_v2()", {
         pool_data_v2.calc_slippage(&mut slippage_adj)?;
     });
 
-    debug_time!("calculate_path::calc_effective_price_v3()", {
+    debug_time!("build_swap_graph::calc_effective_price_v3()", {
         pool_data_v3.calc_effective_price(amount_in).await?;
     });
 
-    debug_time!("calculate_path::calc_slippage_v3()", {
+    debug_time!("build_swap_graph::calc_slippage_v3()", {
         pool_data_v3.calc_slippage(&mut slippage_adj)?;
     });
 
-    debug_time!("calculate_path::calc_slippage_curve()", {
+    debug_time!("build_swap_graph::calc_slippage_curve()", {
         curve_pool_data.calc_slippage(amount_in);
     });
 
     let mut graph: SwapGraph =
         HashMap::with_capacity((pool_data_v2.data.len() + pool_data_v2.data.len()) * 2);
 
-    debug_time!("calculate_path::into_v2_swap_graph()", {
+    debug_time!("build_swap_graph::into_v2_swap_graph()", {
         pool_data_v2.to_swap_graph(&mut graph);
     });
 
-    debug_time!("calculate_path::into_v3_swap_graph()", {
+    debug_time!("build_swap_graph::into_v3_swap_graph()", {
         pool_data_v3.to_swap_graph(&mut graph);
     });
 
-    debug_time!("calculate_path::into_curve_swap_graph()", {
+    debug_time!("build_swap_graph::into_curve_swap_graph()", {
         curve_pool_data.to_swap_graph(&mut graph);
     });
 
-    log::info!("Total {} nodes collected!", graph.len());
+    graph
+        .values_mut()
+        .for_each(|edges| edges.retain(|edge| !excluded.contains(&edge.pool)));
+
+    GRAPH_NODES.set(graph.len() as i64);
+    GRAPH_EDGES.set(graph.values().map(Vec::len).sum::<usize>() as i64);
 
     let slippage_adj = slippage_adj.unwrap_or_default().abs() + BigInt::ONE;
+
+    Ok((graph, slippage_adj))
+}
+
+/// Pool state shared between the log applier, path queries and the backrun watcher
+#[derive(Clone)]
+pub struct SharedState {
+    pub token_map: Arc<Mutex<TokenMap>>,
+    pub pool_data_v2: Arc<Mutex<v2::PoolData>>,
+    pub pool_data_v3: Arc<Mutex<v3::PoolData>>,
+    pub curve_pool_data: Arc<Mutex<curve::PoolData>>,
+    /// Block the pool state reflects, advanced by every applied log
+    pub block: Arc<Mutex<u64>>,
+}
+
+/// A copy of the shared pool state, to price and simulate on without holding its locks
+#[derive(Clone)]
+pub struct PoolState {
+    pub token_map: TokenMap,
+    pub pool_data_v2: v2::PoolData,
+    pub pool_data_v3: v3::PoolData,
+    pub curve_pool_data: curve::PoolData,
+    pub block: u64,
+}
+
+impl SharedState {
+    pub fn new(
+        token_map: TokenMap,
+        pool_data_v2: v2::PoolData,
+        pool_data_v3: v3::PoolData,
+        curve_pool_data: curve::PoolData,
+        block: u64,
+    ) -> Self {
+        Self {
+            token_map: Arc::new(Mutex::new(token_map)),
+            pool_data_v2: Arc::new(Mutex::new(pool_data_v2)),
+            pool_data_v3: Arc::new(Mutex::new(pool_data_v3)),
+            curve_pool_data: Arc::new(Mutex::new(curve_pool_data)),
+            block: Arc::new(Mutex::new(block)),
+        }
+    }
+
+    /// Copy the state, holding every lock at once so the pools and block agree
+    pub async fn snapshot(&self) -> PoolState {
+        let token_map = self.token_map.lock().await;
+        let pool_data_v2 = self.pool_data_v2.lock().await;
+        let pool_data_v3 = self.pool_data_v3.lock().await;
+        let curve_pool_data = self.curve_pool_data.lock().await;
+        let block = self.block.lock().await;
+
+        PoolState {
+            token_map: token_map.clone(),
+            pool_data_v2: pool_data_v2.clone(),
+            pool_data_v3: pool_data_v3.clone(),
+            curve_pool_data: curve_pool_data.clone(),
+            block: *block,
+        }
+    }
+}
+
+/// Route on a copy of the pool state, so logs keep being applied through RPC calls and simulation
+async fn calculate_path<'a>(
+    provider: &SolverProvider,
+    mut state: PoolState,
+    pool_filter: &PoolFilter,
+    input_data: InputData,
+) -> Result<(), CustomError<'a>> {
+    let timer = PATH_QUERY_SECONDS.start_timer();
+    let (graph, slippage_adj) = build_swap_graph(
+        &mut state.pool_data_v2,
+        &mut state.pool_data_v3,
+        &mut state.curve_pool_data,
+        pool_filter,
+        input_data.amount_in.to_big_int(),
+    )
+    .await?;
+
+    log::info!("Total {} nodes collected!", graph.len());
+
     let mut path = debug_time!("calculate_path::best_path()", {
         best_path(
             &graph,
@@ -64,43 +178,298 @@ async fn calculate_path<'a>(
         )
     });
     path.cost -= slippage_adj * BigInt::from(path.pools.len());
+    timer.observe_duration();
+
+    let route = RouteResult::new(
+        &state.pool_data_v2,
+        &state.pool_data_v3,
+        &state.curve_pool_data,
+        &state.token_map,
+        &path,
+        input_data.amount_in,
+        state.block,
+    )?;
+
+    match input_data.format {
+        OutputFormat::Table => println!("{route}"),
+        OutputFormat::Json => println!("{}", route.to_json()?),
+    }
+
+    // A cycle can be run without inventory by borrowing the input
+    let mut plan = None;
+    if let Some(lender) = input_data.lender {
+        if path.is_cycle() {
+            let flash_plan = FlashPlanBuilder::new(&path)
+                .lender(lender)
+                .amount_in(input_data.amount_in)
+                .min_profit(input_data.min_profit)
+                .build(
+                    &state.pool_data_v2,
+                    &state.pool_data_v3,
+                    &state.curve_pool_data,
+                )?;
+
+            log::info!("Flash plan profitable: {}", flash_plan.is_profitable());
+            println!("Flash plan:\n{flash_plan}");
+            plan = Some(flash_plan);
+        }
+    }
+
+    if input_data.simulate {
+        // Replay from a saved snapshot when one exists, otherwise fork the latest block
+        let snapshot = env::var("SIMULATION_SNAPSHOT").ok();
+        let mut simulator = match snapshot.as_deref() {
+            Some(path) if std::path::Path::new(path).exists() => Simulator::from_snapshot(path)?,
+            _ => {
+                debug_time!("calculate_path::Simulator::new()", {
+                    Simulator::new(provider, state.block).await?
+                })
+            }
+        };
+        let block = simulator.cache.block;
+
+        let comparisons = debug_time!("calculate_path::compare_path()", {
+            simulator
+                .compare_path(
+                    &state.pool_data_v2,
+                    &state.pool_data_v3,
+                    &state.curve_pool_data,
+                    &path,
+                    input_data.amount_in,
+                )
+                .await?
+        });
+
+        println!("Simulated hops at block {block}:");
+        for comparison in comparisons {
+            println!("{comparison}");
+        }
+
+        if let (Some(plan), Some(executor)) = (plan, input_data.executor) {
+            let result = simulator
+                .simulate(&SimulationTx::call(executor, plan.calldata))
+                .await?;
+            println!("Simulated flash plan:\n{result}");
+        }
+
+        if let Some(path) = snapshot.as_deref() {
+            simulator.save_snapshot(path)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Apply a single pool event to the cached v2 reserves or v3 price
+pub fn apply_log<'a>(
+    log: &Log,
+    pool_data_v2: &mut v2::PoolData,
+    pool_data_v3: &mut v3::PoolData,
+    received: Instant,
+) -> Result<(), CustomError<'a>> {
+    let mut scanner = ScanData::new(log);
+
+    if let Ok(decoded) = log.log_decode() {
+        let sync: IUniswapV2Pool::Sync = decoded.inner.data;
+        let pool_address = decoded.inner.address;
+        if !pool_data_v2.data.contains_key(&pool_address) {
+            log::debug!("Skipping v2 swap of unknown pool {pool_address}");
+            return Ok(());
+        }
+        log::info!("v2 swap captured, pool: {pool_address}");
+        scanner.update_sync(sync, pool_address);
+
+        // Update reserves based on the event
+        debug_time!("v2::calc_slippage::update_reserve_abs()", {
+            update_reserve_abs(scanner, pool_data_v2)?;
+        });
+        record_event("sync", "v2", received);
+    } else if let Ok(decoded) = log.log_decode() {
+        let swap: IUniswapV3Pool::Swap = decoded.inner.data;
+        let pool_address = decoded.inner.address;
+        if !pool_data_v3.data.contains_key(&pool_address) {
+            log::debug!("Skipping v3 swap of unknown pool {pool_address}");
+            return Ok(());
+        }
+        log::info!("v3 swap captured, pool: {pool_address}",);
+
+        // Update start price
+        debug_time!("v3::calc_start_price_from_sqrt_price_x96", {
+            pool_data_v3.calc_start_price_from_sqrt_price_x96(&pool_address, swap)?;
+        });
+        record_event("swap", "v3", received);
+    }
 
-    println!(
-        "Optimal path for input {:#?}:
-{:#?}",
-        input_data, path
-    );
     Ok(())
 }
 
+/// Check a pending pool in the background, unless a check is in flight or it was checked too recently
+async fn check_pending(
+    provider: &SolverProvider,
+    onboarding: &mut Onboarding,
+    pool: &Address,
+    shared: &SharedState,
+    checks: &mpsc::Sender<Check>,
+) {
+    let Some(new_pool) = onboarding.start_check(pool, Instant::now()) else {
+        return;
+    };
+
+    // The pool state is fetched without holding any lock, only its insertion takes them
+    let token_map = shared.token_map.lock().await.clone();
+    let (provider, pool, checks) = (provider.clone(), *pool, checks.clone());
+    tokio::spawn(async move {
+        let result = Promotion::fetch(&provider, new_pool, token_map).await;
+        let _ = checks.send((pool, result)).await;
+    });
+}
+
+async fn subscribe_pool_logs<'a>(
+    provider: &SolverProvider,
+    filter: &Filter,
+) -> Result<SubscriptionStream<Log>, CustomError<'a>> {
+    let subscription = provider
+        .subscribe_logs(filter)
+        .await
+        .inspect_err(|_| record_rpc_error("eth_subscribe"))?;
+
+    Ok(subscription.into_stream())
+}
+
+/// Forward the logs matching the latest `filter` into `tx`, resubscribing on its own whenever
+/// the subscription ends or fails, and replacing it whenever the filter changes
+fn follow_logs(
+    provider: SolverProvider,
+    mut filter: watch::Receiver<Filter>,
+    tx: mpsc::Sender<Log>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut retries = 0;
+
+        loop {
+            let current = filter.borrow_and_update().clone();
+            let mut stream = match subscribe_pool_logs(&provider, &current).await {
+                Ok(stream) => stream,
+                Err(e) => {
+                    let delay = RESUBSCRIBE_BACKOFF * 2u32.pow(retries);
+                    retries = (retries + 1).min(6);
+                    log::warn!("Log subscription failed ({e}), retrying in {delay:?}");
+                    tokio::time::sleep(delay).await;
+                    continue;
+                }
+            };
+            retries = 0;
+
+            loop {
+                tokio::select! {
+                    log = stream.next() => {
+                        let Some(log) = log else {
+                            log::warn!("Log subscription ended, resubscribing...");
+                            WS_RECONNECTS.inc();
+                            break;
+                        };
+                        if tx.send(log).await.is_err() {
+                            return;
+                        }
+                    }
+                    Ok(()) = filter.changed() => {
+                        // Subscribe to the new filter before dropping the old one, so no log falls in between
+                        let current = filter.borrow_and_update().clone();
+                        let next = match subscribe_pool_logs(&provider, &current).await {
+                            Ok(next) => next,
+                            Err(e) => {
+                                log::warn!("Log subscription failed ({e}), resubscribing...");
+                                break;
+                            }
+                        };
+                        while let Some(Some(log)) = stream.next().now_or_never() {
+                            if tx.send(log).await.is_err() {
+                                return;
+                            }
+                        }
+                        stream = next;
+                    }
+                }
+            }
+        }
+    })
+}
+
+#[allow(clippy::too_many_arguments)]
 pub async fn scan<'a>(
     provider: &SolverProvider,
+    token_map: TokenMap,
     pool_addresses: Vec<Address>,
     pool_data_v2: v2::PoolData,
     pool_data_v3: v3::PoolData,
     curve_pool_data: curve::PoolData,
+    pool_filter: PoolFilter,
+    backrun: Option<BackrunConfig>,
 ) -> Result<(), CustomError<'a>> {
-    // Create a filter for the events.
-    let filter = provider
-        .clone()
-        .subscribe_logs(&Filter::new().address(pool_addresses))
-        .await?;
+    let excluded = pool_filter.excluded(&pool_data_v2, &pool_data_v3, &curve_pool_data);
+    log::info!(
+        "{} of {} pools filtered out",
+        excluded.len(),
+        pool_data_v2.data.len() + pool_data_v3.data.len() + curve_pool_data.data.len()
+    );
+    let pool_filter = Arc::new(pool_filter);
+
+    // Every subscription reconnects by itself and feeds one of these channels
+    let (pool_tx, mut pool_logs) = mpsc::channel(LOG_CHANNEL_SIZE);
+    let (factory_tx, mut factory_logs) = mpsc::channel(LOG_CHANNEL_SIZE);
+
+    // A single subscription follows the loaded pools plus those created since, pending or promoted
+    let mut followed = pool_addresses;
+    let (pool_filter_tx, pool_filter_rx) = watch::channel(Filter::new().address(followed.clone()));
+    let followers = [
+        follow_logs(provider.clone(), pool_filter_rx, pool_tx),
+        // Pools created after startup are picked up from their factories
+        follow_logs(
+            provider.clone(),
+            watch::channel(onboarding_filter()).1,
+            factory_tx,
+        ),
+    ];
+
+    let mut onboarding = Onboarding::default();
+    let (check_tx, mut checks) = mpsc::channel::<Check>(LOG_CHANNEL_SIZE);
+    let (retry_tx, mut retries) = mpsc::channel(LOG_CHANNEL_SIZE);
 
     log::info!("Waiting for events...");
 
-    let mut stream = filter.into_stream();
     let (tx, rx) = mpsc::channel(32);
 
-    // Create a shared state for the current amount
-    let pool_data_v2 = Arc::new(Mutex::new(pool_data_v2));
-    let pool_data_v3 = Arc::new(Mutex::new(pool_data_v3));
-    let curve_pool_data = Arc::new(Mutex::new(curve_pool_data));
+    // The pools were loaded at the head, logs advance the block from there
+    let block = provider
+        .get_block_number()
+        .await
+        .inspect_err(|_| record_rpc_error("eth_blockNumber"))?;
+    let shared = SharedState::new(
+        token_map,
+        pool_data_v2,
+        pool_data_v3,
+        curve_pool_data,
+        block,
+    );
+
+    // Spawn a task searching pending swaps for backruns
+    let backrun_handle = backrun.map(|config| {
+        let provider = provider.clone();
+        let shared = shared.clone();
+        let pool_filter = Arc::clone(&pool_filter);
+
+        tokio::spawn(async move {
+            if let Err(e) = watch_mempool(&provider, shared, &pool_filter, config).await {
+                log::error!("Mempool watcher stopped: {}", e);
+            }
+        })
+    });
 
     // Spawn a task to handle user input
-    let input_handle = {
-        let pool_data_v2_clone = Arc::clone(&pool_data_v2);
-        let pool_data_v3_clone = Arc::clone(&pool_data_v3);
-        let curve_pool_data_clone = Arc::clone(&curve_pool_data);
+    let mut input_handle = {
+        let shared = shared.clone();
+        let pool_filter = Arc::clone(&pool_filter);
+        let provider = provider.clone();
 
         tokio::spawn(async move {
             let stdin = tokio::io::stdin();
@@ -124,13 +493,9 @@ pub async fn scan<'a>(
                 let buffer = input.trim();
                 if let Ok(input_data) = serde_json::from_str::<InputData>(buffer) {
                     // Calculate path immediately after receiving amount
-                    if let Err(e) = calculate_path(
-                        &mut *pool_data_v2_clone.lock().await,
-                        &mut *pool_data_v3_clone.lock().await,
-                        &mut *curve_pool_data_clone.lock().await,
-                        input_data,
-                    )
-                    .await
+                    if let Err(e) =
+                        calculate_path(&provider, shared.snapshot().await, &pool_filter, input_data)
+                            .await
                     {
                         log::error!("Error calculating path: {}", e);
                     }
@@ -146,36 +511,85 @@ pub async fn scan<'a>(
         })
     };
 
-    // Process events from the stream
-    while let Some(log) = stream.next().await {
-        let mut scanner = ScanData::new(&log);
-
-        if let Ok(decoded) = log.log_decode() {
-            let sync: IUniswapV2Pool::Sync = decoded.inner.data;
-            let pool_address = decoded.inner.address;
-            log::info!("v2 swap captured, pool: {pool_address}");
-            scanner.update_sync(sync, pool_address);
-
-            // Update reserves based on the event
-            debug_time!("v2::calc_slippage::update_reserve_abs()", {
-                update_reserve_abs(scanner, &mut *pool_data_v2.lock().await)?;
-            });
-        } else if let Ok(decoded) = log.log_decode() {
-            let swap: IUniswapV3Pool::Swap = decoded.inner.data;
-            let pool_address = decoded.inner.address;
-            log::info!("v3 swap captured, pool: {pool_address}",);
-            let pool_data = &mut pool_data_v3.lock().await;
-
-            // Update start price
-            debug_time!("v3::calc_start_price_from_sqrt_price_x96", {
-                pool_data.calc_start_price_from_sqrt_price_x96(&pool_address, swap)?;
-            });
+    // Process events until the input loop quits
+    loop {
+        tokio::select! {
+            Some(log) = pool_logs.recv() => {
+                let pool = log.address();
+
+                if onboarding.is_pending(&pool) {
+                    check_pending(provider, &mut onboarding, &pool, &shared, &check_tx).await;
+                } else {
+                    let received = Instant::now();
+                    let mut pool_data_v2 = shared.pool_data_v2.lock().await;
+                    let mut pool_data_v3 = shared.pool_data_v3.lock().await;
+                    if let Err(e) = apply_log(&log, &mut pool_data_v2, &mut pool_data_v3, received) {
+                        log::warn!("Failed to apply log of pool {pool}: {e}");
+                        continue;
+                    }
+
+                    // Advanced under the pool locks, so snapshots never pair new state with an old block
+                    if let Some(number) = log.block_number {
+                        let mut block = shared.block.lock().await;
+                        *block = (*block).max(number);
+                    }
+                }
+            }
+            Some(log) = factory_logs.recv() => {
+                let Some(new_pool) = NewPool::decode(&log) else {
+                    continue;
+                };
+                let pool = new_pool.address();
+                log::info!("New pool created: {pool}");
+
+                onboarding.add(new_pool, Instant::now());
+                check_pending(provider, &mut onboarding, &pool, &shared, &check_tx).await;
+
+                followed.push(pool);
+                pool_filter_tx.send_replace(Filter::new().address(followed.clone()));
+            }
+            Some((pool, result)) = checks.recv() => match result {
+                Ok(Some(promotion)) => {
+                    promotion.insert(&shared).await;
+                    onboarding.remove(&pool);
+                    log::info!("Pool {pool} passed the liquidity filters, routing through it");
+                }
+                Ok(None) => {
+                    onboarding.not_liquid(&pool, Instant::now());
+                    log::debug!("Pool {pool} is not liquid yet");
+                }
+                Err(e @ CustomError::InvalidDecimals(..)) => {
+                    // Pools whose tokens can't be priced are never routed through
+                    log::warn!("Dropping new pool {pool}: {e}");
+                    onboarding.remove(&pool);
+                    followed.retain(|followed| *followed != pool);
+                    pool_filter_tx.send_replace(Filter::new().address(followed.clone()));
+                }
+                Err(e) => {
+                    if let Some(delay) = onboarding.failed(&pool, Instant::now()) {
+                        log::warn!("Checking new pool {pool} failed ({e}), retrying in {delay:?}");
+                        let retry_tx = retry_tx.clone();
+                        tokio::spawn(async move {
+                            tokio::time::sleep(delay).await;
+                            let _ = retry_tx.send(pool).await;
+                        });
+                    }
+                }
+            },
+            Some(pool) = retries.recv() => {
+                check_pending(provider, &mut onboarding, &pool, &shared, &check_tx).await;
+            }
+            _ = &mut input_handle => break,
         }
     }
 
     // Clean up
     drop(rx);
     input_handle.abort();
+    followers.iter().for_each(JoinHandle::abort);
+    if let Some(handle) = backrun_handle {
+        handle.abort();
+    }
 
     Ok(())
 }
@@ -0,0 +1,609 @@
+use super::*;
+use alloy::{
+    eips::BlockId,
+    primitives::{aliases::U24, keccak256, Bytes, B256},
+    sol_types::{decode_revert_reason, SolCall},
+};
+use revm::{
+    bytecode::Bytecode,
+    context::{result::ExecutionResult, TxEnv},
+    database_interface::DBErrorMarker,
+    primitives::{TxKind, KECCAK_EMPTY},
+    state::AccountInfo,
+    Context, Database, ExecuteEvm, MainBuilder, MainContext,
+};
+use std::{fs, io::Write};
+use tokio::runtime::Handle;
+use uniswap_v3_sdk::prelude::IQuoterV2;
+
+const BLOCK_GAS_LIMIT: u64 = 30_000_000;
+
+/// Per-transaction cap introduced by EIP-7825
+const TX_GAS_LIMIT: u64 = 1 << 24;
+
+/// A piece of chain state the EVM asked for that is not cached yet
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StateKey {
+    Account(Address),
+    Storage(Address, U256),
+    BlockHash(u64),
+}
+
+/// State the EVM asked for that is neither cached nor could be fetched
+#[derive(Debug)]
+pub enum StateError {
+    Missing(StateKey),
+    Fetch(StateKey, String),
+}
+
+impl Display for StateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Missing(key) => write!(f, "state not cached: {key:?}"),
+            Self::Fetch(key, e) => write!(f, "failed to fetch {key:?}: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for StateError {}
+
+impl DBErrorMarker for StateError {}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AccountSnapshot {
+    pub balance: U256,
+    pub nonce: u64,
+    pub code: Bytes,
+}
+
+/// Lazily populated state at a fixed block, serialisable as a snapshot file
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct StateCache {
+    pub block: u64,
+    pub timestamp: u64,
+    pub accounts: HashMap<Address, AccountSnapshot>,
+    pub storage: HashMap<Address, HashMap<U256, U256>>,
+    pub block_hashes: HashMap<u64, B256>,
+}
+
+impl StateCache {
+    pub fn set_storage(&mut self, address: Address, slot: U256, value: U256) {
+        self.storage.entry(address).or_default().insert(slot, value);
+    }
+
+    fn contains(&self, key: StateKey) -> bool {
+        match key {
+            StateKey::Account(address) => self.accounts.contains_key(&address),
+            StateKey::Storage(address, slot) => self
+                .storage
+                .get(&address)
+                .is_some_and(|slots| slots.contains_key(&slot)),
+            StateKey::BlockHash(number) => self.block_hashes.contains_key(&number),
+        }
+    }
+
+    /// Fetch `key` as of the cached block and add it to the cache
+    async fn fetch<'a>(
+        &mut self,
+        provider: &SolverProvider,
+        key: StateKey,
+    ) -> Result<(), CustomError<'a>> {
+        let block = BlockId::number(self.block);
+
+        match key {
+            StateKey::Account(address) => {
+                let balance = provider.get_balance(address).block_id(block).await?;
+                let nonce = provider
+                    .get_transaction_count(address)
+                    .block_id(block)
+                    .await?;
+                let code = provider.get_code_at(address).block_id(block).await?;
+
+                self.accounts.insert(
+                    address,
+                    AccountSnapshot {
+                        balance,
+                        nonce,
+                        code,
+                    },
+                );
+            }
+            StateKey::Storage(address, slot) => {
+                let value = provider
+                    .get_storage_at(address, slot)
+                    .block_id(block)
+                    .await?;
+                self.set_storage(address, slot, value);
+            }
+            StateKey::BlockHash(number) => {
+                let hash = provider
+                    .get_block_by_number(number.into())
+                    .await?
+                    .map(|block| block.header.hash)
+                    .unwrap_or_default();
+                self.block_hashes.insert(number, hash);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// The cache handed to revm, filling misses from the provider as execution reaches them.
+/// Fetches block on `handle`, so it must be used off the async runtime's worker threads.
+struct CacheDb<'c> {
+    cache: &'c mut StateCache,
+    provider: Option<(SolverProvider, Handle)>,
+    code: HashMap<B256, Bytecode>,
+}
+
+impl<'c> CacheDb<'c> {
+    fn new(cache: &'c mut StateCache, provider: Option<(SolverProvider, Handle)>) -> Self {
+        Self {
+            cache,
+            provider,
+            code: HashMap::new(),
+        }
+    }
+
+    /// Make sure `key` is cached, fetching it when a provider is available
+    fn load(&mut self, key: StateKey) -> Result<(), StateError> {
+        if self.cache.contains(key) {
+            return Ok(());
+        }
+        let Some((provider, handle)) = &self.provider else {
+            return Err(StateError::Missing(key));
+        };
+
+        handle
+            .block_on(self.cache.fetch(provider, key))
+            .map_err(|e| StateError::Fetch(key, e.to_string()))
+    }
+}
+
+impl Database for CacheDb<'_> {
+    type Error = StateError;
+
+    fn basic(&mut self, address: Address) -> Result<Option<AccountInfo>, Self::Error> {
+        let key = StateKey::Account(address);
+        self.load(key)?;
+        let account = self
+            .cache
+            .accounts
+            .get(&address)
+            .ok_or(StateError::Missing(key))?;
+
+        let (code_hash, code) = if account.code.is_empty() {
+            (KECCAK_EMPTY, Bytecode::default())
+        } else {
+            let code = Bytecode::new_raw(account.code.clone());
+            (keccak256(&account.code), code)
+        };
+        self.code.insert(code_hash, code.clone());
+
+        Ok(Some(
+            AccountInfo::default()
+                .with_balance(account.balance)
+                .with_nonce(account.nonce)
+                .with_code_and_hash(code, code_hash),
+        ))
+    }
+
+    fn code_by_hash(&mut self, code_hash: B256) -> Result<Bytecode, Self::Error> {
+        Ok(self.code.get(&code_hash).cloned().unwrap_or_default())
+    }
+
+    fn storage(&mut self, address: Address, index: U256) -> Result<U256, Self::Error> {
+        let key = StateKey::Storage(address, index);
+        self.load(key)?;
+        self.cache
+            .storage
+            .get(&address)
+            .and_then(|slots| slots.get(&index))
+            .copied()
+            .ok_or(StateError::Missing(key))
+    }
+
+    fn block_hash(&mut self, number: u64) -> Result<B256, Self::Error> {
+        let key = StateKey::BlockHash(number);
+        self.load(key)?;
+        self.cache
+            .block_hashes
+            .get(&number)
+            .copied()
+            .ok_or(StateError::Missing(key))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulationTx {
+    pub from: Address,
+    pub to: Address,
+    pub data: Bytes,
+    pub value: U256,
+}
+
+impl SimulationTx {
+    pub fn call(to: Address, data: impl Into<Bytes>) -> Self {
+        Self {
+            from: Address::ZERO,
+            to,
+            data: data.into(),
+            value: U256::ZERO,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct SimulationResult {
+    pub success: bool,
+    pub output: Bytes,
+    pub gas_used: u64,
+    pub revert_reason: Option<String>,
+}
+
+impl Display for SimulationResult {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "success: {}\ngas_used: {}\noutput: {}\nrevert_reason: {}",
+            self.success,
+            self.gas_used,
+            self.output,
+            self.revert_reason.as_deref().unwrap_or("-")
+        )
+    }
+}
+
+/// Ground truth for a hop next to the solver's own quote
+#[derive(Debug, Clone)]
+pub struct QuoteComparison {
+    pub pool: Address,
+    pub protocol: Protocol,
+    pub amount_in: U256,
+    pub expected: U256,
+    pub actual: U256,
+}
+
+impl QuoteComparison {
+    pub fn diff(&self) -> BigInt {
+        self.expected.to_big_int() - self.actual.to_big_int()
+    }
+}
+
+impl Display for QuoteComparison {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:?} {}: in {} expected {} actual {} diff {}",
+            self.protocol,
+            self.pool,
+            self.amount_in,
+            self.expected,
+            self.actual,
+            self.diff()
+        )
+    }
+}
+
+/// In-process EVM on top of the state at `cache.block`
+pub struct Simulator {
+    provider: Option<SolverProvider>,
+    pub cache: StateCache,
+}
+
+impl Simulator {
+    /// Fork the chain at `block`, fetching state from the provider on demand
+    pub async fn new<'a>(
+        provider: &SolverProvider,
+        block: u64,
+    ) -> Result<Simulator, CustomError<'a>> {
+        let header = provider
+            .get_block_by_number(block.into())
+            .await?
+            .ok_or(CustomError::NotFound("simulation block"))?
+            .header;
+
+        let mut cache = StateCache {
+            block,
+            timestamp: header.timestamp,
+            ..Default::default()
+        };
+        cache.block_hashes.insert(block, header.hash);
+
+        Ok(Simulator {
+            provider: Some(provider.clone()),
+            cache,
+        })
+    }
+
+    /// Run entirely from a snapshot file, any state it lacks is an error
+    pub fn from_snapshot<'a>(path: &str) -> Result<Simulator, CustomError<'a>> {
+        let reader = BufReader::new(File::open(path)?);
+
+        Ok(Simulator {
+            provider: None,
+            cache: from_reader(reader)?,
+        })
+    }
+
+    pub fn save_snapshot<'a>(&self, path: &str) -> Result<(), CustomError<'a>> {
+        let mut file = fs::File::create(path)?;
+        file.write_all(serde_json::to_string(&self.cache)?.as_bytes())?;
+        Ok(())
+    }
+
+    /// Execute `tx` on a blocking thread, fetching the state it touches that isn't cached yet
+    pub async fn simulate<'a>(
+        &mut self,
+        tx: &SimulationTx,
+    ) -> Result<SimulationResult, CustomError<'a>> {
+        let mut cache = std::mem::take(&mut self.cache);
+        let provider = self
+            .provider
+            .clone()
+            .map(|provider| (provider, Handle::current()));
+        let tx = tx.clone();
+
+        let (cache, outcome) = tokio::task::spawn_blocking(move || {
+            let block = cache.block;
+            let timestamp = cache.timestamp;
+
+            let mut evm = Context::mainnet()
+                .with_db(CacheDb::new(&mut cache, provider))
+                .modify_cfg_chained(|cfg| {
+                    cfg.disable_nonce_check = true;
+                    cfg.disable_balance_check = true;
+                    cfg.disable_base_fee = true;
+                })
+                .modify_block_chained(|env| {
+                    env.number = U256::from(block + 1);
+                    env.timestamp = U256::from(timestamp + 12);
+                    env.gas_limit = BLOCK_GAS_LIMIT;
+                    env.basefee = 0;
+                })
+                .build_mainnet();
+
+            let tx_env = TxEnv::builder()
+                .caller(tx.from)
+                .kind(TxKind::Call(tx.to))
+                .data(tx.data)
+                .value(tx.value)
+                .gas_limit(TX_GAS_LIMIT)
+                .gas_price(0)
+                .build_fill();
+
+            let outcome = evm
+                .transact(tx_env)
+                .map(|res| res.result)
+                .map_err(|err| err.to_string());
+            drop(evm);
+
+            (cache, outcome)
+        })
+        .await
+        .map_err(|e| CustomError::SimulationError(e.to_string()))?;
+        self.cache = cache;
+
+        outcome
+            .map(SimulationResult::from)
+            .map_err(CustomError::SimulationError)
+    }
+
+    /// Execute the on-chain quote for a hop and compare it with the solver's pool math
+    pub async fn compare_hop<'a>(
+        &mut self,
+        pool_data_v3: &v3::PoolData,
+        hop: &IArbExecutor::Hop,
+    ) -> Result<QuoteComparison, CustomError<'a>> {
+        let protocol =
+            Protocol::from_id(hop.protocol).ok_or(CustomError::NotFound("hop protocol"))?;
+
+        let actual = match protocol {
+            Protocol::UniswapV2 => {
+                let factory = self
+                    .call(hop.pool, IUniswapV2Pair::factoryCall {}.abi_encode())
+                    .await?;
+                let factory = IUniswapV2Pair::factoryCall::abi_decode_returns(&factory)?;
+                let router = match factory {
+                    UNISWAP_V2_FACTORY => UNISWAP_V2_ROUTER,
+                    SUSHI_V2_FACTORY => SUSHI_V2_ROUTER,
+                    _ => return Err(CustomError::AddressNotFound(factory)),
+                };
+
+                let output = self
+                    .call(
+                        router,
+                        IUniswapV2Router::getAmountsOutCall {
+                            amountIn: hop.amountIn,
+                            path: vec![hop.tokenIn, hop.tokenOut],
+                        }
+                        .abi_encode(),
+                    )
+                    .await?;
+                let amounts = IUniswapV2Router::getAmountsOutCall::abi_decode_returns(&output)?;
+                amounts.last().copied().unwrap_or_default()
+            }
+            Protocol::UniswapV3 => {
+                let fee = pool_data_v3
+                    .data
+                    .get(&hop.pool)
+                    .map(|token_data| token_data.fee)
+                    .ok_or_else(|| CustomError::AddressNotFound(hop.pool))?;
+
+                let output = self
+                    .call(
+                        UNISWAP_V3_QUOTER_V2,
+                        IQuoterV2::quoteExactInputSingleCall {
+                            params: IQuoterV2::QuoteExactInputSingleParams {
+                                tokenIn: hop.tokenIn,
+                                tokenOut: hop.tokenOut,
+                                amountIn: hop.amountIn,
+                                fee: U24::from(fee),
+                                sqrtPriceLimitX96: U160::ZERO,
+                            },
+                        }
+                        .abi_encode(),
+                    )
+                    .await?;
+                IQuoterV2::quoteExactInputSingleCall::abi_decode_returns(&output)?.amountOut
+            }
+            Protocol::Curve => {
+                let output = self
+                    .call(
+                        hop.pool,
+                        CurvePool::get_dyCall {
+                            i: hop.i,
+                            j: hop.j,
+                            dx: hop.amountIn,
+                        }
+                        .abi_encode(),
+                    )
+                    .await?;
+                CurvePool::get_dyCall::abi_decode_returns(&output)?
+            }
+        };
+
+        Ok(QuoteComparison {
+            pool: hop.pool,
+            protocol,
+            amount_in: hop.amountIn,
+            expected: hop.amountOut,
+            actual,
+        })
+    }
+
+    /// Compare every hop of a path, feeding each hop the solver's own output of the previous one
+    pub async fn compare_path<'a>(
+        &mut self,
+        pool_data_v2: &v2::PoolData,
+        pool_data_v3: &v3::PoolData,
+        curve_pool_data: &curve::PoolData,
+        path: &ShortestPath,
+        amount_in: U256,
+    ) -> Result<Vec<QuoteComparison>, CustomError<'a>> {
+        let hops = quote_hops(pool_data_v2, pool_data_v3, curve_pool_data, path, amount_in)?;
+        let mut comparisons = Vec::with_capacity(hops.len());
+
+        for hop in hops.iter() {
+            comparisons.push(self.compare_hop(pool_data_v3, hop).await?);
+        }
+
+        Ok(comparisons)
+    }
+
+    /// Simulate a view call and return its output, failing on revert
+    async fn call<'a>(&mut self, to: Address, data: Vec<u8>) -> Result<Bytes, CustomError<'a>> {
+        let result = self.simulate(&SimulationTx::call(to, data)).await?;
+
+        if result.success {
+            Ok(result.output)
+        } else {
+            Err(CustomError::SimulationError(
+                result
+                    .revert_reason
+                    .unwrap_or_else(|| "execution reverted".to_string()),
+            ))
+        }
+    }
+}
+
+impl From<ExecutionResult> for SimulationResult {
+    fn from(result: ExecutionResult) -> Self {
+        match result {
+            ExecutionResult::Success { gas, output, .. } => Self {
+                success: true,
+                output: output.into_data(),
+                gas_used: gas.tx_gas_used(),
+                revert_reason: None,
+            },
+            ExecutionResult::Revert { gas, output, .. } => Self {
+                success: false,
+                revert_reason: decode_revert_reason(&output),
+                output,
+                gas_used: gas.tx_gas_used(),
+            },
+            ExecutionResult::Halt { reason, gas, .. } => Self {
+                success: false,
+                output: Bytes::new(),
+                gas_used: gas.tx_gas_used(),
+                revert_reason: Some(format!("{reason:?}")),
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTRACT: Address = address!("0x00000000000000000000000000000000000000C0");
+
+    // PUSH1 0x00 SLOAD PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+    const RETURN_SLOT_0: [u8; 11] = [
+        0x60, 0x00, 0x54, 0x60, 0x00, 0x52, 0x60, 0x20, 0x60, 0x00, 0xf3,
+    ];
+
+    // PUSH1 0x00 PUSH1 0x00 REVERT
+    const REVERT: [u8; 5] = [0x60, 0x00, 0x60, 0x00, 0xfd];
+
+    fn set_code(simulator: &mut Simulator, code: &'static [u8]) {
+        simulator.cache.accounts.entry(CONTRACT).or_default().code = Bytes::from_static(code);
+    }
+
+    fn offline_simulator() -> Simulator {
+        let mut cache = StateCache::default();
+        cache
+            .accounts
+            .insert(Address::ZERO, AccountSnapshot::default());
+
+        Simulator {
+            provider: None,
+            cache,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_simulate_from_cache() {
+        let mut simulator = offline_simulator();
+        set_code(&mut simulator, &RETURN_SLOT_0);
+        simulator
+            .cache
+            .set_storage(CONTRACT, U256::ZERO, U256::from(42));
+
+        let result = simulator
+            .simulate(&SimulationTx::call(CONTRACT, Bytes::new()))
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert!(result.gas_used > 21_000);
+        assert_eq!(U256::from_be_slice(&result.output), U256::from(42));
+    }
+
+    #[tokio::test]
+    async fn test_simulate_revert() {
+        let mut simulator = offline_simulator();
+        set_code(&mut simulator, &REVERT);
+
+        let result = simulator
+            .simulate(&SimulationTx::call(CONTRACT, Bytes::new()))
+            .await
+            .unwrap();
+
+        assert!(!result.success);
+        assert!(result.output.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_simulate_missing_state_offline() {
+        let mut simulator = offline_simulator();
+        set_code(&mut simulator, &RETURN_SLOT_0);
+
+        let result = simulator
+            .simulate(&SimulationTx::call(CONTRACT, Bytes::new()))
+            .await;
+
+        assert!(matches!(result, Err(CustomError::SimulationError(_))));
+    }
+}
@@ -0,0 +1,216 @@
+use super::*;
+use alloy::{providers::CallItem, sol_types::SolCall};
+use std::ops::RangeInclusive;
+use uniswap_v3_sdk::prelude::{MAX_TICK_I32, MIN_TICK_I32};
+use utils::metrics::record_rpc_error;
+
+// Calls packed into one Multicall3 `eth_call`, and batches in flight at once
+const CALLS_PER_BATCH: usize = 500;
+const MAX_CONCURRENT_BATCHES: usize = 4;
+
+/// Bitmap words a pool with `tick_spacing` can have initialized ticks in
+pub fn word_range(tick_spacing: i32) -> RangeInclusive<i16> {
+    let min_word = (MIN_TICK_I32 / tick_spacing) >> 8;
+    let max_word = (MAX_TICK_I32 / tick_spacing) >> 8;
+
+    min_word as i16..=max_word as i16
+}
+
+/// Ticks flagged as initialized in the bitmap `word`
+pub fn initialized_ticks(word: i16, bitmap: U256, tick_spacing: i32) -> Vec<i32> {
+    (0..256)
+        .filter(|&bit| bitmap.bit(bit))
+        .map(|bit| ((i32::from(word) << 8) + bit as i32) * tick_spacing)
+        .collect()
+}
+
+/// Send `calls` through Multicall3 in batches pinned to `block`, `None` for every call that reverted
+pub(crate) async fn aggregate<'a, C: SolCall + 'static>(
+    provider: &SolverProvider,
+    block: u64,
+    calls: Vec<(Address, C)>,
+) -> Result<Vec<Option<C::Return>>, CustomError<'a>> {
+    let batches: Vec<_> = calls
+        .chunks(CALLS_PER_BATCH)
+        .map(|chunk| {
+            let items = chunk.iter().map(|(target, call)| {
+                CallItem::<C>::new(*target, call.abi_encode().into()).allow_failure(true)
+            });
+            provider
+                .multicall()
+                .dynamic::<C>()
+                .block(BlockId::number(block))
+                .extend_calls(items)
+        })
+        .collect();
+
+    let results: Vec<_> = futures::stream::iter(&batches)
+        .map(|batch| batch.aggregate3())
+        .buffered(MAX_CONCURRENT_BATCHES)
+        .collect()
+        .await;
+
+    let mut returns = Vec::with_capacity(calls.len());
+    for result in results {
+        let result = result.inspect_err(|_| record_rpc_error("multicall"))?;
+        returns.extend(result.into_iter().map(Result::ok));
+    }
+
+    Ok(returns)
+}
+
+fn to_all<C: Clone>(pools: &[Address], call: C) -> Vec<(Address, C)> {
+    pools.iter().map(|pool| (*pool, call.clone())).collect()
+}
+
+/// Price, liquidity and spacing of a pool, read in the same block as its ticks
+struct PoolState {
+    pool: Address,
+    current_tick: I24,
+    sqrt_price_x96: U160,
+    liquidity: u128,
+    tick_spacing: i32,
+}
+
+/// Snapshot many v3 pools in a handful of multicalls, all pinned to `block`.
+/// Pools with any failed read are left out rather than stored with partial ticks.
+pub async fn snapshot_ticks<'a>(
+    provider: &SolverProvider,
+    pools: &[Address],
+    block: u64,
+) -> Result<Vec<TickDataReader>, CustomError<'a>> {
+    let slot0s = aggregate(provider, block, to_all(pools, IUniswapV3Pool::slot0Call {})).await?;
+    let liquidities = aggregate(
+        provider,
+        block,
+        to_all(pools, IUniswapV3Pool::liquidityCall {}),
+    )
+    .await?;
+    let spacings = aggregate(
+        provider,
+        block,
+        to_all(pools, IUniswapV3Pool::tickSpacingCall {}),
+    )
+    .await?;
+
+    let states: Vec<PoolState> = pools
+        .iter()
+        .zip(slot0s.into_iter().zip(liquidities).zip(spacings))
+        .filter_map(|(pool, ((slot0, liquidity), spacing))| {
+            let (Some(slot0), Some(liquidity), Some(spacing)) = (slot0, liquidity, spacing) else {
+                log::error!("Pool {pool}: state unavailable at block {block}");
+                return None;
+            };
+
+            Some(PoolState {
+                pool: *pool,
+                current_tick: slot0.tick,
+                sqrt_price_x96: slot0.sqrtPriceX96,
+                liquidity,
+                tick_spacing: spacing.as_i32(),
+            })
+        })
+        .filter(|state| state.tick_spacing > 0)
+        .collect();
+
+    // Every word of every pool, then every initialized tick found in them
+    let words: Vec<(usize, i16)> = states
+        .iter()
+        .enumerate()
+        .flat_map(|(i, state)| word_range(state.tick_spacing).map(move |word| (i, word)))
+        .collect();
+    let bitmaps = aggregate(
+        provider,
+        block,
+        words
+            .iter()
+            .map(|(i, word)| {
+                (
+                    states[*i].pool,
+                    IUniswapV3Pool::tickBitmapCall::new((*word,)),
+                )
+            })
+            .collect(),
+    )
+    .await?;
+
+    let mut failed = vec![false; states.len()];
+    let mut ticks: Vec<(usize, i32)> = Vec::new();
+    for ((i, word), bitmap) in words.into_iter().zip(bitmaps) {
+        match bitmap {
+            Some(bitmap) => ticks.extend(
+                initialized_ticks(word, bitmap, states[i].tick_spacing)
+                    .into_iter()
+                    .map(|tick| (i, tick)),
+            ),
+            None => failed[i] = true,
+        }
+    }
+
+    let tick_infos = aggregate(
+        provider,
+        block,
+        ticks
+            .iter()
+            .map(|(i, tick)| {
+                let tick = I24::try_from(*tick).unwrap_or_default();
+                (states[*i].pool, IUniswapV3Pool::ticksCall::new((tick,)))
+            })
+            .collect(),
+    )
+    .await?;
+
+    let mut pool_ticks: Vec<Vec<TickSync>> = vec![Vec::new(); states.len()];
+    for ((i, index), info) in ticks.into_iter().zip(tick_infos) {
+        match info {
+            Some(info) => pool_ticks[i].push(TickSync {
+                index,
+                liquidity_gross: info.liquidityGross,
+                liquidity_net: info.liquidityNet,
+                is_init: info.initialized,
+            }),
+            None => failed[i] = true,
+        }
+    }
+
+    Ok(states
+        .into_iter()
+        .zip(pool_ticks)
+        .zip(failed)
+        .filter_map(|((state, ticks), failed)| {
+            if failed {
+                log::error!("Pool {}: tick reads failed at block {block}", state.pool);
+                return None;
+            }
+
+            Some(TickDataReader {
+                block,
+                pool: state.pool,
+                current_tick: state.current_tick,
+                sqrt_price_x96: state.sqrt_price_x96,
+                liquidity: state.liquidity,
+                ticks,
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn word_range_covers_usable_ticks() {
+        assert_eq!(word_range(1), -3466..=3465);
+        assert_eq!(word_range(60), -58..=57);
+        assert_eq!(word_range(200), -18..=17);
+    }
+
+    #[test]
+    fn initialized_ticks_are_scaled_by_spacing() {
+        let bitmap = U256::from(1u8) | (U256::from(1u8) << 255);
+
+        assert_eq!(initialized_ticks(0, bitmap, 60), vec![0, 255 * 60]);
+        assert_eq!(initialized_ticks(-1, bitmap, 10), vec![-256 * 10, -10]);
+    }
+}
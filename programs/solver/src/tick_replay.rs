@@ -0,0 +1,356 @@
+use super::*;
+use alloy::sol_types::SolEvent;
+use std::collections::BTreeMap;
+use utils::backfill::Backfill;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickLiquidity {
+    pub liquidity_gross: u128,
+    pub liquidity_net: i128,
+}
+
+/// Tick table and price of a v3 pool rebuilt from its `Initialize`, `Swap`, `Mint` and `Burn`
+/// events alone. Serialises as a checkpoint that later replays resume from at `next_block`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickReplay {
+    pub pool: Address,
+    /// First block whose events are not applied yet
+    pub next_block: u64,
+    ticks: BTreeMap<i32, TickLiquidity>,
+    #[serde(default)]
+    current_tick: I24,
+    /// Zero until the pool's `Initialize` is replayed
+    #[serde(default)]
+    sqrt_price_x96: U160,
+    #[serde(default)]
+    liquidity: u128,
+}
+
+impl TickReplay {
+    /// An empty table for `pool`, replayed from `from_block`, usually the pool's creation
+    pub fn new(pool: Address, from_block: u64) -> Self {
+        Self {
+            pool,
+            next_block: from_block,
+            ticks: BTreeMap::new(),
+            current_tick: I24::ZERO,
+            sqrt_price_x96: U160::ZERO,
+            liquidity: 0,
+        }
+    }
+
+    /// Event signatures the replay applies, for filtering logs
+    pub fn signatures() -> Vec<B256> {
+        vec![
+            IUniswapV3Pool::Initialize::SIGNATURE_HASH,
+            IUniswapV3Pool::Swap::SIGNATURE_HASH,
+            IUniswapV3Pool::Mint::SIGNATURE_HASH,
+            IUniswapV3Pool::Burn::SIGNATURE_HASH,
+        ]
+    }
+
+    fn update(&mut self, tick: i32, gross_delta: i128, net_delta: i128) {
+        let entry = self.ticks.entry(tick).or_default();
+        entry.liquidity_gross = entry.liquidity_gross.saturating_add_signed(gross_delta);
+        entry.liquidity_net += net_delta;
+
+        if entry.liquidity_gross == 0 {
+            self.ticks.remove(&tick);
+        }
+    }
+
+    /// Add (`amount > 0`) or remove (`amount < 0`) a position's liquidity between two ticks
+    pub fn apply_position(&mut self, tick_lower: i32, tick_upper: i32, amount: i128) {
+        if amount == 0 {
+            return;
+        }
+
+        // Crossing the lower tick upwards adds the position, crossing the upper one removes it
+        self.update(tick_lower, amount, amount);
+        self.update(tick_upper, amount, -amount);
+
+        // A position around the current tick is active right away, as in the pool's `_modifyPosition`
+        let current_tick = self.current_tick.as_i32();
+        if tick_lower <= current_tick && current_tick < tick_upper {
+            self.liquidity = self.liquidity.saturating_add_signed(amount);
+        }
+    }
+
+    /// Apply an `Initialize`, `Swap`, `Mint` or `Burn` of this pool, returning whether the log was one
+    pub fn apply_log(&mut self, log: &Log) -> bool {
+        if log.address() != self.pool {
+            return false;
+        }
+
+        if let Ok(swap) = IUniswapV3Pool::Swap::decode_log(&log.inner) {
+            self.current_tick = swap.tick;
+            self.sqrt_price_x96 = swap.sqrtPriceX96;
+            self.liquidity = swap.liquidity;
+        } else if let Ok(initialize) = IUniswapV3Pool::Initialize::decode_log(&log.inner) {
+            self.current_tick = initialize.tick;
+            self.sqrt_price_x96 = initialize.sqrtPriceX96;
+        } else if let Ok(mint) = IUniswapV3Pool::Mint::decode_log(&log.inner) {
+            self.apply_position(
+                mint.tickLower.as_i32(),
+                mint.tickUpper.as_i32(),
+                mint.amount as i128,
+            );
+        } else if let Ok(burn) = IUniswapV3Pool::Burn::decode_log(&log.inner) {
+            self.apply_position(
+                burn.tickLower.as_i32(),
+                burn.tickUpper.as_i32(),
+                -(burn.amount as i128),
+            );
+        } else {
+            return false;
+        }
+
+        true
+    }
+
+    /// Apply the logs of blocks up to `to_block` in chain order and move the checkpoint past it.
+    /// Logs from blocks already replayed are skipped, so overlapping ranges are safe to resume.
+    pub fn replay(&mut self, logs: &[Log], to_block: u64) {
+        for log in logs {
+            let block = log.block_number.unwrap_or_default();
+            if block >= self.next_block && block <= to_block {
+                self.apply_log(log);
+            }
+        }
+
+        self.next_block = self.next_block.max(to_block + 1);
+    }
+
+    /// Fetch and replay the pool's events from the checkpoint up to `to_block`
+    pub async fn sync<'a>(
+        &mut self,
+        provider: &SolverProvider,
+        to_block: u64,
+    ) -> Result<(), CustomError<'a>> {
+        Self::sync_all(provider, std::slice::from_mut(self), to_block).await
+    }
+
+    /// Fetch and replay the events of every pool in `replays` with shared `eth_getLogs` calls,
+    /// from the earliest checkpoint among them up to `to_block`
+    pub async fn sync_all<'a>(
+        provider: &SolverProvider,
+        replays: &mut [TickReplay],
+        to_block: u64,
+    ) -> Result<(), CustomError<'a>> {
+        let Some(from_block) = replays.iter().map(|replay| replay.next_block).min() else {
+            return Ok(());
+        };
+        let filter = Filter::new()
+            .address(replays.iter().map(|replay| replay.pool).collect::<Vec<_>>())
+            .event_signature(Self::signatures());
+
+        Backfill::new(filter)
+            .run(provider, from_block, to_block, |logs, to| {
+                let mut by_pool: HashMap<Address, Vec<Log>> = HashMap::new();
+                for log in logs {
+                    by_pool.entry(log.address()).or_default().push(log);
+                }
+                // Pools without logs in the range still move their checkpoint past it
+                for replay in replays.iter_mut() {
+                    replay.replay(by_pool.get(&replay.pool).map_or(&[], Vec::as_slice), to);
+                }
+                Ok::<_, CustomError>(())
+            })
+            .await?;
+
+        Ok(())
+    }
+
+    /// The pool as stored in `ticks.json` at the last replayed block, `None` before its `Initialize`
+    pub fn snapshot(&self) -> Option<TickDataReader> {
+        if self.sqrt_price_x96.is_zero() {
+            return None;
+        }
+
+        Some(TickDataReader {
+            block: self.next_block.saturating_sub(1),
+            pool: self.pool,
+            current_tick: self.current_tick,
+            sqrt_price_x96: self.sqrt_price_x96,
+            liquidity: self.liquidity,
+            ticks: self.ticks(),
+        })
+    }
+
+    /// Initialized ticks in ascending order, as stored in `ticks.json`
+    pub fn ticks(&self) -> Vec<TickSync> {
+        self.ticks
+            .iter()
+            .map(|(index, liquidity)| TickSync {
+                index: *index,
+                liquidity_gross: liquidity.liquidity_gross,
+                liquidity_net: liquidity.liquidity_net,
+                is_init: true,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::I256;
+    use alloy::primitives::{aliases::U24, Log as PrimitiveLog};
+
+    const POOL: Address = address!("0x8ad599c3A0ff1De082011EFDDc58f1908eb6e6D8");
+
+    fn log(block: u64, event: &impl SolEvent) -> Log {
+        Log {
+            inner: PrimitiveLog {
+                address: POOL,
+                data: event.encode_log_data(),
+            },
+            block_number: Some(block),
+            ..Default::default()
+        }
+    }
+
+    fn mint(tick_lower: i32, tick_upper: i32, amount: u128) -> IUniswapV3Pool::Mint {
+        IUniswapV3Pool::Mint {
+            sender: Address::ZERO,
+            owner: Address::ZERO,
+            tickLower: I24::try_from(tick_lower).unwrap(),
+            tickUpper: I24::try_from(tick_upper).unwrap(),
+            amount,
+            amount0: U256::ZERO,
+            amount1: U256::ZERO,
+        }
+    }
+
+    fn burn(tick_lower: i32, tick_upper: i32, amount: u128) -> IUniswapV3Pool::Burn {
+        IUniswapV3Pool::Burn {
+            owner: Address::ZERO,
+            tickLower: I24::try_from(tick_lower).unwrap(),
+            tickUpper: I24::try_from(tick_upper).unwrap(),
+            amount,
+            amount0: U256::ZERO,
+            amount1: U256::ZERO,
+        }
+    }
+
+    fn initialize(tick: i32) -> IUniswapV3Pool::Initialize {
+        IUniswapV3Pool::Initialize {
+            sqrtPriceX96: U160::from(1u128 << 96),
+            tick: I24::try_from(tick).unwrap(),
+        }
+    }
+
+    fn swap(tick: i32, liquidity: u128) -> IUniswapV3Pool::Swap {
+        IUniswapV3Pool::Swap {
+            sender: Address::ZERO,
+            recipient: Address::ZERO,
+            amount0: I256::ZERO,
+            amount1: I256::ZERO,
+            sqrtPriceX96: U160::from(2u128 << 96),
+            liquidity,
+            tick: I24::try_from(tick).unwrap(),
+        }
+    }
+
+    /// `TickSync` has no `PartialEq`, so tables are compared as tuples
+    fn table(replay: &TickReplay) -> Vec<(i32, u128, i128)> {
+        replay
+            .ticks()
+            .iter()
+            .map(|tick| (tick.index, tick.liquidity_gross, tick.liquidity_net))
+            .collect()
+    }
+
+    #[test]
+    fn positions_sign_lower_and_upper_ticks() {
+        let mut replay = TickReplay::new(POOL, 0);
+        replay.replay(
+            &[
+                log(1, &mint(-60, 60, 100)),
+                log(2, &mint(0, 120, 50)),
+                log(3, &burn(-60, 60, 40)),
+            ],
+            3,
+        );
+
+        assert_eq!(
+            table(&replay),
+            vec![(-60, 60, 60), (0, 50, 50), (60, 60, -60), (120, 50, -50)]
+        );
+    }
+
+    #[test]
+    fn fully_burnt_ticks_are_dropped() {
+        let mut replay = TickReplay::new(POOL, 0);
+        replay.replay(
+            &[
+                log(1, &mint(-10, 10, 7)),
+                log(2, &burn(-10, 10, 0)),
+                log(3, &burn(-10, 10, 7)),
+            ],
+            3,
+        );
+
+        assert!(replay.ticks().is_empty());
+    }
+
+    #[test]
+    fn resumes_from_checkpoint_without_double_counting() {
+        let logs = [log(1, &mint(-60, 60, 100)), log(5, &mint(-60, 60, 10))];
+
+        let mut replay = TickReplay::new(POOL, 0);
+        replay.replay(&logs[..1], 3);
+        assert_eq!(replay.next_block, 4);
+
+        let checkpoint = serde_json::to_string(&replay).unwrap();
+        let mut replay: TickReplay = serde_json::from_str(&checkpoint).unwrap();
+        // The resumed range overlaps the first one
+        replay.replay(&logs, 5);
+
+        assert_eq!(replay.next_block, 6);
+        assert_eq!(table(&replay), vec![(-60, 110, 110), (60, 110, -110)]);
+    }
+
+    #[test]
+    fn ignores_other_pools_and_events() {
+        let mut replay = TickReplay::new(POOL, 0);
+        let mut other_pool = log(1, &mint(-60, 60, 100));
+        other_pool.inner.address = Address::ZERO;
+        let created = IUniswapV3Factory::PoolCreated {
+            token0: Address::ZERO,
+            token1: Address::ZERO,
+            fee: U24::from(500),
+            tickSpacing: I24::ONE,
+            pool: POOL,
+        };
+
+        assert!(!replay.apply_log(&other_pool));
+        assert!(!replay.apply_log(&log(1, &created)));
+        assert!(replay.ticks().is_empty());
+    }
+
+    #[test]
+    fn tracks_price_and_active_liquidity() {
+        let mut replay = TickReplay::new(POOL, 0);
+        assert!(replay.snapshot().is_none());
+
+        replay.replay(
+            &[
+                log(2, &initialize(0)),
+                log(3, &mint(-60, 60, 100)),
+                // Out of range of the current tick, so not active
+                log(3, &mint(60, 120, 30)),
+                log(4, &swap(70, 130)),
+                log(5, &burn(60, 120, 10)),
+            ],
+            5,
+        );
+
+        let snapshot = replay.snapshot().unwrap();
+        assert_eq!(snapshot.block, 5);
+        assert_eq!(snapshot.current_tick, I24::try_from(70).unwrap());
+        assert_eq!(snapshot.sqrt_price_x96, U160::from(2u128 << 96));
+        assert_eq!(snapshot.liquidity, 120);
+        assert_eq!(snapshot.ticks.len(), 3);
+    }
+}
@@ -0,0 +1,294 @@
+use super::*;
+use alloy::primitives::keccak256;
+use alloy::sol_types::SolValue;
+use std::collections::BTreeMap;
+use uniswap_v2_sdk::prelude::compute_pair_address;
+use uniswap_v3_sdk::prelude::{compute_pool_address, FeeAmount};
+
+/// Only the pool a tick snapshot belongs to matters for consistency
+#[derive(Debug, Deserialize)]
+pub struct TickPool {
+    pub pool: Address,
+}
+
+/// Entries a step left out on purpose, with the reason, so their absence is no inconsistency
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct Skipped {
+    /// Tokens whose metadata couldn't be read
+    #[serde(default)]
+    pub tokens: BTreeMap<Address, String>,
+    /// V3 pools whose ticks couldn't be snapshotted
+    #[serde(default)]
+    pub ticks: BTreeMap<Address, String>,
+}
+
+/// The resource files as produced by the separate bins
+#[derive(Debug, Default)]
+pub struct Resources {
+    pub pools_v2: Vec<Address>,
+    pub pools_v3: Vec<Address>,
+    pub tokens_to_pool_v2: Vec<Pools>,
+    pub tokens_to_pool_v3: Vec<Pools>,
+    pub token_metadata: Vec<TokenMetadata>,
+    pub curve_pools: Vec<CurvePools>,
+    pub ticks: Vec<TickPool>,
+    pub skipped: Skipped,
+}
+
+/// A single inconsistency between the resource files
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    pub file: &'static str,
+    pub pool: Address,
+    pub message: String,
+    /// Why the step producing the missing entry skipped it, when it recorded doing so
+    pub skipped: Option<String>,
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {} {}", self.file, self.pool, self.message)?;
+        if let Some(reason) = &self.skipped {
+            write!(f, ", skipped: {reason}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Pair address for Uniswap or Sushi, whose factories share the v2 salt but not the init code
+fn is_v2_pair(pool: &Pools) -> bool {
+    let salt = keccak256((pool.token0, pool.token1).abi_encode_packed());
+
+    pool.address == compute_pair_address(UNISWAP_V2_FACTORY, pool.token0, pool.token1)
+        || pool.address == SUSHI_V2_FACTORY.create2(salt, SUSHI_V2_INIT_CODE_HASH)
+}
+
+fn is_v3_pool(pool: &Pools) -> bool {
+    pool.token0 != pool.token1
+        && pool.address
+            == compute_pool_address(
+                UNISWAP_V3_FACTORY,
+                pool.token0,
+                pool.token1,
+                FeeAmount::from(u32::from(pool.fee)),
+                None,
+                None,
+            )
+}
+
+impl Resources {
+    fn check_pairs(
+        &self,
+        file: &'static str,
+        pools: &[Pools],
+        listed: &[Address],
+        is_derived: fn(&Pools) -> bool,
+        issues: &mut Vec<Issue>,
+    ) {
+        let metadata: HashSet<Address> = self.token_metadata.iter().map(|m| m.address).collect();
+        let listed: HashSet<&Address> = listed.iter().collect();
+        let mut seen = HashSet::new();
+        let mut issue = |pool: Address, message: String, skipped: Option<String>| {
+            issues.push(Issue {
+                file,
+                pool,
+                message,
+                skipped,
+            })
+        };
+
+        for pool in pools {
+            if !seen.insert(pool.address) {
+                issue(pool.address, "is listed more than once".to_string(), None);
+            }
+            if pool.token0 >= pool.token1 {
+                issue(pool.address, "has token0 >= token1".to_string(), None);
+            } else if !is_derived(pool) {
+                issue(
+                    pool.address,
+                    "does not match the address derived from its tokens".to_string(),
+                    None,
+                );
+            }
+            for token in [pool.token0, pool.token1] {
+                if !metadata.contains(&token) {
+                    issue(
+                        pool.address,
+                        format!("token {token} has no metadata"),
+                        self.skipped.tokens.get(&token).cloned(),
+                    );
+                }
+            }
+            if !listed.contains(&pool.address) {
+                issue(
+                    pool.address,
+                    "is missing from the pool list".to_string(),
+                    None,
+                );
+            }
+        }
+
+        for pool in listed.into_iter().filter(|pool| !seen.contains(*pool)) {
+            issue(*pool, "is listed without its tokens".to_string(), None);
+        }
+    }
+
+    /// Every inconsistency across the files, in file order
+    pub fn validate(&self) -> Vec<Issue> {
+        let mut issues = Vec::new();
+
+        self.check_pairs(
+            "uniswapv2_tokens_to_pool.json",
+            &self.tokens_to_pool_v2,
+            &self.pools_v2,
+            is_v2_pair,
+            &mut issues,
+        );
+        self.check_pairs(
+            "uniswapv3_tokens_to_pool.json",
+            &self.tokens_to_pool_v3,
+            &self.pools_v3,
+            is_v3_pool,
+            &mut issues,
+        );
+
+        let metadata: HashSet<Address> = self.token_metadata.iter().map(|m| m.address).collect();
+        for pool in &self.curve_pools {
+            for token in pool
+                .tokens
+                .iter()
+                .filter(|token| !metadata.contains(*token))
+            {
+                issues.push(Issue {
+                    file: "curve_tokens_to_pool.json",
+                    pool: pool.address,
+                    message: format!("token {token} has no metadata"),
+                    skipped: self.skipped.tokens.get(token).cloned(),
+                });
+            }
+        }
+
+        let ticked: HashSet<Address> = self.ticks.iter().map(|tick| tick.pool).collect();
+        let pools_v3: HashSet<Address> = self.pools_v3.iter().copied().collect();
+        for pool in pools_v3.iter().filter(|pool| !ticked.contains(*pool)) {
+            issues.push(Issue {
+                file: "ticks.json",
+                pool: *pool,
+                message: "v3 pool has no tick data".to_string(),
+                skipped: self.skipped.ticks.get(pool).cloned(),
+            });
+        }
+        for pool in ticked.iter().filter(|pool| !pools_v3.contains(*pool)) {
+            issues.push(Issue {
+                file: "ticks.json",
+                pool: *pool,
+                message: "has tick data but is not a listed v3 pool".to_string(),
+                skipped: None,
+            });
+        }
+
+        issues
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAI: Address = address!("0x6B175474E89094C44Da98b954EedeAC495271d0F");
+    const USDC: Address = address!("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const DAI_USDC_V2: Address = address!("0xAE461cA67B15dc8dc81CE7615e0320dA1A9aB8D5");
+    const DAI_USDC_V3: Address = address!("0x5777d92f208679DB4b9778590Fa3CAB3aC9e2168");
+
+    fn metadata(address: Address) -> TokenMetadata {
+        TokenMetadata {
+            address,
+            name: String::new(),
+            symbol: String::new(),
+            decimals: 18,
+        }
+    }
+
+    fn resources() -> Resources {
+        Resources {
+            pools_v2: vec![DAI_USDC_V2],
+            pools_v3: vec![DAI_USDC_V3],
+            tokens_to_pool_v2: vec![Pools {
+                token0: DAI,
+                token1: USDC,
+                fee: 3000,
+                address: DAI_USDC_V2,
+            }],
+            tokens_to_pool_v3: vec![Pools {
+                token0: DAI,
+                token1: USDC,
+                fee: 100,
+                address: DAI_USDC_V3,
+            }],
+            token_metadata: vec![metadata(DAI), metadata(USDC)],
+            curve_pools: vec![],
+            ticks: vec![TickPool { pool: DAI_USDC_V3 }],
+            skipped: Skipped::default(),
+        }
+    }
+
+    #[test]
+    fn consistent_resources_pass() {
+        assert_eq!(resources().validate(), vec![]);
+    }
+
+    #[test]
+    fn reports_every_inconsistency() {
+        let mut resources = resources();
+        resources.tokens_to_pool_v2[0].token0 = USDC;
+        resources.tokens_to_pool_v2[0].token1 = DAI;
+        resources.tokens_to_pool_v3[0].fee = 500;
+        resources.token_metadata.pop();
+        resources.ticks.clear();
+
+        let messages: Vec<String> = resources
+            .validate()
+            .iter()
+            .map(|issue| issue.message.clone())
+            .collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "has token0 >= token1".to_string(),
+                format!("token {USDC} has no metadata"),
+                "does not match the address derived from its tokens".to_string(),
+                format!("token {USDC} has no metadata"),
+                "v3 pool has no tick data".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn flags_recorded_skips() {
+        let mut resources = resources();
+        resources.token_metadata.pop();
+        resources.ticks.clear();
+        resources
+            .skipped
+            .tokens
+            .insert(USDC, "decimals() reverted".to_string());
+
+        let skipped: Vec<Option<String>> = resources
+            .validate()
+            .into_iter()
+            .map(|issue| issue.skipped)
+            .collect();
+
+        // The tick snapshot was not recorded as skipped, so its absence still counts
+        assert_eq!(
+            skipped,
+            vec![
+                Some("decimals() reverted".to_string()),
+                Some("decimals() reverted".to_string()),
+                None,
+            ]
+        );
+    }
+}
@@ -0,0 +1,77 @@
+use solver::{enums::CurveFamily, pools::curve::parity::CurveFixture};
+use std::{fs::File, io::BufReader};
+
+/// Written by `arb-data curve-fixtures --block <N> --output tests/fixtures`
+const FIXTURES: &str = concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/tests/fixtures/curve_fixtures.json"
+);
+
+fn fixtures(family: CurveFamily) -> Vec<CurveFixture> {
+    let file = File::open(FIXTURES).unwrap();
+    let fixtures: Vec<CurveFixture> = serde_json::from_reader(BufReader::new(file)).unwrap();
+
+    fixtures
+        .into_iter()
+        .filter(|fixture| fixture.family == family)
+        .collect()
+}
+
+fn assert_parity(family: CurveFamily) {
+    let fixtures = fixtures(family);
+    assert!(
+        fixtures.iter().any(|fixture| fixture.unsupported.is_none()),
+        "no supported {family:?} fixtures"
+    );
+
+    for fixture in fixtures {
+        // Only quotes read from a node say anything about parity with the contracts
+        assert!(
+            fixture.block.is_some(),
+            "{} has no block, record it with `arb-data curve-fixtures`",
+            fixture.pool.address
+        );
+
+        // The family is derived from the recorded state, so a stale label would go unnoticed
+        assert_eq!(fixture.pool.family(), family);
+
+        // Recorded to track how far off the solver is, known not to match yet
+        if let Some(reason) = &fixture.unsupported {
+            let mismatches = fixture.mismatches().len();
+            println!(
+                "{} ({reason}): {mismatches} quotes differ",
+                fixture.pool.address
+            );
+            continue;
+        }
+
+        let mismatches = fixture.mismatches();
+        assert!(
+            mismatches.is_empty(),
+            "{} of {} quotes differ: {mismatches:#?}",
+            mismatches.len(),
+            fixture.quotes.len()
+        );
+    }
+}
+
+#[test]
+fn legacy_pools_match_get_dy() {
+    assert_parity(CurveFamily::Legacy);
+}
+
+#[test]
+fn precise_pools_match_get_dy() {
+    assert_parity(CurveFamily::Precise);
+}
+
+#[test]
+fn reverting_quotes_are_recorded() {
+    let reverts = fixtures(CurveFamily::Legacy)
+        .iter()
+        .flat_map(|fixture| &fixture.quotes)
+        .filter(|quote| quote.dy.is_none())
+        .count();
+
+    assert!(reverts > 0);
+}
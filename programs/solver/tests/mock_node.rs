@@ -0,0 +1,195 @@
+use alloy::{
+    primitives::{address, Address, Bytes, U256},
+    providers::{ProviderBuilder, WsConnect},
+};
+use mock_node::{Fixtures, MockNode};
+use serde::de::DeserializeOwned;
+use solver::{
+    filter::PoolFilter,
+    parser::EnvParser,
+    pools::*,
+    scanner::scan,
+    simulation::{SimulationTx, Simulator},
+    SolverProvider,
+};
+use std::{collections::HashMap, fs::File, io::BufReader, process::Stdio, time::Duration};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader as AsyncBufReader},
+    process::Command,
+    time::{sleep, timeout},
+};
+use uniswap_sdk_core::prelude::BigInt;
+
+const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures");
+const UNISWAP_PAIR: Address = address!("0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc");
+const CURVE_3POOL: Address = address!("0xbEbc44782C7dB0a1A60Cb6fe97d0b483032FF1C7");
+// Returns storage slot 0, which holds 42
+const SLOT_READER: Address = address!("0x00000000000000000000000000000000000000C0");
+
+fn fixture<T: DeserializeOwned>(name: &str) -> T {
+    let file = File::open(format!("{FIXTURES}/{name}")).unwrap();
+    serde_json::from_reader(BufReader::new(file)).unwrap()
+}
+
+async fn spawn_node() -> MockNode {
+    let fixtures = Fixtures::load(format!("{FIXTURES}/node.json")).unwrap();
+    MockNode::spawn(fixtures).await.unwrap()
+}
+
+async fn connect(node: &MockNode) -> SolverProvider {
+    ProviderBuilder::new()
+        .connect_ws(WsConnect::new(node.ws_address()))
+        .await
+        .unwrap()
+}
+
+/// What `EnvParser::new` reads from the fixture files, without going through the environment
+fn env_parser(ws_address: String) -> EnvParser {
+    EnvParser {
+        ws_address,
+        pool_address: fixture("pool_address.json"),
+        token_metadata: fixture("token_metadata.json"),
+        pools_v2: fixture("pools_v2.json"),
+        pools_v3: fixture("pools_v3.json"),
+        curve_pools: fixture("curve_pools.json"),
+        tick_map: HashMap::new(),
+        backrun: None,
+        backtest: None,
+        pool_filter: PoolFilter::default(),
+    }
+}
+
+#[tokio::test]
+async fn load_pools_reads_reserves_and_curve_balances() {
+    let node = spawn_node().await;
+    let provider = connect(&node).await;
+    let env_parser = env_parser(node.ws_address());
+    let token_map = token_metadata_to_tokens(&env_parser.token_metadata);
+
+    let (pool_data_v2, _, curve_pool_data) = load_pools(&provider, &env_parser, &token_map)
+        .await
+        .unwrap();
+
+    let pair = &pool_data_v2.data[&UNISWAP_PAIR];
+    assert_eq!(pair.reserve0, BigInt::from(30_000_000_000_000u128));
+    assert_eq!(pair.reserve1, BigInt::from(10_000u128 * 10u128.pow(18)));
+
+    // Balances come back through Multicall3, equal once scaled to the same precision
+    let xp = &curve_pool_data.data[&CURVE_3POOL].xp;
+    assert!(xp[0] > BigInt::ZERO);
+    assert!(xp.iter().all(|x| *x == xp[0]));
+}
+
+#[tokio::test]
+async fn scan_applies_subscribed_logs() {
+    let node = spawn_node().await;
+    let provider = connect(&node).await;
+    let env_parser = env_parser(node.ws_address());
+    let token_map = token_metadata_to_tokens(&env_parser.token_metadata);
+    let (pool_data_v2, pool_data_v3, curve_pool_data) =
+        load_pools(&provider, &env_parser, &token_map)
+            .await
+            .unwrap();
+
+    // Both `Sync` logs of the fixture are pushed to the pool subscription
+    let applied = r#"events_processed_total{kind="sync",protocol="v2"} 2"#;
+    let wait = timeout(Duration::from_secs(10), async {
+        while !utils::metrics::render().contains(applied) {
+            sleep(Duration::from_millis(50)).await;
+        }
+    });
+
+    // `scan` runs until its subscriptions end, which the mock node never does
+    tokio::select! {
+        result = scan(
+            &provider,
+            token_map,
+            env_parser.pool_address.single(),
+            pool_data_v2,
+            pool_data_v3,
+            curve_pool_data,
+            env_parser.pool_filter,
+            None,
+        ) => panic!("scan stopped early: {result:?}"),
+        waited = wait => waited.expect("scan did not apply the subscribed logs"),
+    }
+}
+
+#[tokio::test]
+async fn simulator_fetches_state_while_executing() {
+    let node = spawn_node().await;
+    let provider = connect(&node).await;
+    let mut simulator = Simulator::new(&provider, 102).await.unwrap();
+
+    let result = simulator
+        .simulate(&SimulationTx::call(SLOT_READER, Bytes::new()))
+        .await
+        .unwrap();
+
+    assert!(result.success);
+    assert_eq!(U256::from_be_slice(&result.output), U256::from(42));
+    // What the call touched stays cached for the next one
+    assert_eq!(
+        simulator.cache.storage[&SLOT_READER][&U256::ZERO],
+        U256::from(42)
+    );
+}
+
+#[tokio::test]
+async fn solver_answers_path_queries() {
+    let node = spawn_node().await;
+    let workdir = std::env::temp_dir().join(format!("solver-mock-node-{}", std::process::id()));
+    std::fs::create_dir_all(&workdir).unwrap();
+
+    let mut solver = Command::new(env!("CARGO_BIN_EXE_solver"))
+        .current_dir(&workdir)
+        .env("WEBSOCKET_ENDPOINT", node.ws_address())
+        .env("POOL_PATH", format!("{FIXTURES}/pool_address.json"))
+        .env("POOLS_V2_PATH", format!("{FIXTURES}/pools_v2.json"))
+        .env("POOLS_V3_PATH", format!("{FIXTURES}/pools_v3.json"))
+        .env(
+            "METADATA_FILE_PATH",
+            format!("{FIXTURES}/token_metadata.json"),
+        )
+        .env("CURVE_TOKENS_PATH", format!("{FIXTURES}/curve_pools.json"))
+        .env("TICKS_PATH", format!("{FIXTURES}/ticks.json"))
+        .env_remove("BACKRUN_CONFIG_PATH")
+        .env_remove("BACKTEST_CONFIG_PATH")
+        .env_remove("POOL_FILTER_PATH")
+        .env_remove("CURVE_ERROR_POOLS_PATH")
+        .env_remove("SIMULATION_SNAPSHOT")
+        .env_remove("METRICS_ADDRESS")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .unwrap();
+
+    // 1 WETH to USDC
+    let query = r#"{"token_a":"0xC02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2","token_b":"0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48","amount_in":"0xde0b6b3a7640000"}"#;
+    let mut stdin = solver.stdin.take().unwrap();
+    stdin
+        .write_all(format!("{query}\n").as_bytes())
+        .await
+        .unwrap();
+    stdin.flush().await.unwrap();
+
+    let mut lines = AsyncBufReader::new(solver.stdout.take().unwrap()).lines();
+    let route = timeout(Duration::from_secs(30), async {
+        while let Some(line) = lines.next_line().await.unwrap() {
+            if line.starts_with("Route at block") {
+                return line;
+            }
+        }
+        panic!("solver exited without answering");
+    })
+    .await
+    .expect("solver did not answer the path query");
+
+    assert!(route.starts_with("Route at block 102: 1.000000000000000000 WETH ->"));
+    assert!(route.ends_with("USDC"));
+
+    solver.kill().await.unwrap();
+    std::fs::remove_dir_all(workdir).unwrap();
+}